@@ -80,9 +80,9 @@ fn read_data() -> Vec<(String, i32)> {
 
     pairs
 }
-use pathmap::utils::ByteMask;
-// FnMut(&ByteMask, &mut [W], Option<&V>, &[u8])
-fn val_count_cata<V>(_bm: &ByteMask, vals: &mut[usize], _val: Option<&V>, _path: &[u8]) -> usize {
+use pathmap::utils::{ByteMask, TakableSlice};
+// FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8])
+fn val_count_cata<V>(_bm: &ByteMask, vals: &mut TakableSlice<usize>, _val: Option<&V>, _path: &[u8]) -> usize {
     1 + vals.iter().copied().sum::<usize>()
 }
 #[divan::bench()]
@@ -228,12 +228,6 @@ pub fn derive_poly_zipper(input: TokenStream) -> TokenStream {
                     }
                 }
 
-                fn val_count(&self) -> usize {
-                    match self {
-                        #(#variant_arms => inner.val_count(),)*
-                    }
-                }
-
                 fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
                     match self {
                         #(#variant_arms => inner.descend_to(k),)*
@@ -281,6 +275,34 @@ pub fn derive_poly_zipper(input: TokenStream) -> TokenStream {
         }
     };
 
+    // Generate TrieSummary trait implementation
+    let trie_summary_impl = {
+        let variant_arms = &variant_arms;
+        quote! {
+            impl #impl_generics pathmap::zipper::TrieSummary<V> for #enum_name #ty_generics
+            where
+                #(#inner_types: pathmap::zipper::TrieSummary<V>,)*
+                #where_clause
+            {
+                fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+                    where
+                        W: Clone,
+                        AlgF: Fn(&pathmap::utils::ByteMask, &mut pathmap::utils::TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+                {
+                    match self {
+                        #(#variant_arms => inner.summarize_fallible(alg_f),)*
+                    }
+                }
+
+                fn val_count(&self) -> usize {
+                    match self {
+                        #(#variant_arms => inner.val_count(),)*
+                    }
+                }
+            }
+        }
+    };
+
     // Generate ZipperConcrete trait implementation
     let zipper_concrete_impl = {
         let variant_arms = &variant_arms;
@@ -448,6 +470,7 @@ pub fn derive_poly_zipper(input: TokenStream) -> TokenStream {
         #zipper_read_only_conditional_values_impl
         // #zipper_forking_impl
         #zipper_moving_impl
+        #trie_summary_impl
         #zipper_concrete_impl
         #zipper_absolute_path_impl
         #zipper_path_buffer_impl
@@ -458,3 +481,150 @@ pub fn derive_poly_zipper(input: TokenStream) -> TokenStream {
 
     TokenStream::from(expanded)
 }
+
+// See the docs for `Lattice` in `pathmap::ring::Lattice`
+#[proc_macro_derive(Lattice)]
+pub fn derive_lattice(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields = struct_fields(&input, "Lattice");
+
+    let mut generics = input.generics.clone();
+    add_field_bounds(&mut generics, fields, quote! { pathmap::ring::Lattice + Clone });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let both_ident = quote! { pathmap::ring::AlgebraicResult::Identity(pathmap::ring::SELF_IDENT | pathmap::ring::COUNTER_IDENT) };
+    let pjoin_body = field_wise_op(fields, quote! { pathmap::ring::Lattice::pjoin }, &both_ident, false);
+    let pmeet_body = field_wise_op(fields, quote! { pathmap::ring::Lattice::pmeet }, &both_ident, false);
+
+    let expanded = quote! {
+        impl #impl_generics pathmap::ring::Lattice for #struct_name #ty_generics #where_clause {
+            fn pjoin(&self, other: &Self) -> pathmap::ring::AlgebraicResult<Self> {
+                #pjoin_body
+            }
+            fn pmeet(&self, other: &Self) -> pathmap::ring::AlgebraicResult<Self> {
+                #pmeet_body
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+// See the docs for `DistributiveLattice` in `pathmap::ring::DistributiveLattice`
+#[proc_macro_derive(DistributiveLattice)]
+pub fn derive_distributive_lattice(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let fields = struct_fields(&input, "DistributiveLattice");
+
+    let mut generics = input.generics.clone();
+    add_field_bounds(&mut generics, fields, quote! { pathmap::ring::DistributiveLattice + Clone + Default });
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    //A field that is subtracted away is replaced by its default, and the struct is only subtracted away
+    // when every field is
+    let none = quote! { pathmap::ring::AlgebraicResult::None };
+    let psubtract_body = field_wise_op(fields, quote! { pathmap::ring::DistributiveLattice::psubtract }, &none, true);
+
+    let expanded = quote! {
+        impl #impl_generics pathmap::ring::DistributiveLattice for #struct_name #ty_generics #where_clause {
+            fn psubtract(&self, other: &Self) -> pathmap::ring::AlgebraicResult<Self> {
+                #psubtract_body
+            }
+        }
+    };
+    TokenStream::from(expanded)
+}
+
+/// Returns the fields of a struct, panicking if the input isn't a struct
+fn struct_fields<'a>(input: &'a DeriveInput, macro_name: &str) -> &'a Fields {
+    match &input.data {
+        Data::Struct(data_struct) => &data_struct.fields,
+        _ => panic!("{macro_name} can only be derived for structs"),
+    }
+}
+
+/// Adds a `bounds` predicate to the where clause for the type of each field
+fn add_field_bounds(generics: &mut syn::Generics, fields: &Fields, bounds: proc_macro2::TokenStream) {
+    let where_clause = generics.make_where_clause();
+    for field in fields.iter() {
+        let ty = &field.ty;
+        where_clause.predicates.push(syn::parse_quote! { #ty: #bounds });
+    }
+}
+
+/// Generates the body of a binary algebraic operation, by applying `op` to each pair of fields and
+/// merging the results together with `AlgebraicResult::merge`
+///
+/// The merged element is accumulated as a nested tuple, e.g. `((f0, f1), f2)`, and then converted into
+/// the struct.  If any field's result is `None`, the result for the whole struct is `None`, unless
+/// `default_for_none` is set.  In that case, the whole struct is `None` only if every field's result is
+/// `None`, and otherwise the fields whose result is `None` are set to `Default::default()`.
+fn field_wise_op(fields: &Fields, op: proc_macro2::TokenStream, empty_result: &proc_macro2::TokenStream, default_for_none: bool) -> proc_macro2::TokenStream {
+    let members: Vec<syn::Member> = fields.members().collect();
+    if members.is_empty() {
+        return empty_result.clone();
+    }
+
+    let results: Vec<syn::Ident> = (0..members.len()).map(|i| quote::format_ident!("result_{}", i)).collect();
+    let vals: Vec<syn::Ident> = (0..members.len()).map(|i| quote::format_ident!("val_{}", i)).collect();
+
+    let mut body = quote! {
+        #(let #results = #op(&self.#members, &other.#members);)*
+    };
+    if default_for_none {
+        body.extend(quote! {
+            if #(#results.is_none())&&* {
+                return pathmap::ring::AlgebraicResult::None
+            }
+            #(let #results = match #results {
+                pathmap::ring::AlgebraicResult::None => pathmap::ring::AlgebraicResult::Element(Default::default()),
+                result => result,
+            };)*
+        });
+    }
+
+    let first_member = &members[0];
+    let mut acc_self = quote! { self.#first_member.clone() };
+    let mut acc_other = quote! { other.#first_member.clone() };
+    let mut acc_pattern = {
+        let val = &vals[0];
+        quote! { #val }
+    };
+    let first_result = &results[0];
+    body.extend(quote! { let merged = #first_result; });
+    for ((member, result), val) in members.iter().zip(results.iter()).zip(vals.iter()).skip(1) {
+        body.extend(quote! {
+            let merged = merged.merge(#result, |which_arg| {
+                match which_arg {
+                    0 => Some(#acc_self),
+                    1 => Some(#acc_other),
+                    _ => unreachable!()
+                }
+            }, |which_arg| {
+                match which_arg {
+                    0 => Some(self.#member.clone()),
+                    1 => Some(other.#member.clone()),
+                    _ => unreachable!()
+                }
+            }, |acc, field| {
+                match (acc, field) {
+                    (Some(acc), Some(field)) => pathmap::ring::AlgebraicResult::Element((acc, field)),
+                    _ => pathmap::ring::AlgebraicResult::None
+                }
+            });
+        });
+        acc_self = quote! { (#acc_self, self.#member.clone()) };
+        acc_other = quote! { (#acc_other, other.#member.clone()) };
+        acc_pattern = quote! { (#acc_pattern, #val) };
+    }
+
+    let construct = match fields {
+        Fields::Named(_) => quote! { Self { #(#members: #vals),* } },
+        _ => quote! { Self ( #(#vals),* ) },
+    };
+    body.extend(quote! {
+        merged.map(|#acc_pattern| #construct)
+    });
+    body
+}
@@ -83,11 +83,11 @@ use fast_slice_utils::starts_with;
 
 use crate::{
     morphisms::Catamorphism,
-    utils::{BitMask, ByteMask, find_prefix_overlap},
+    utils::{BitMask, ByteMask, TakableSlice, find_prefix_overlap},
     zipper::{
         Zipper, ZipperValues, ZipperForking, ZipperAbsolutePath, ZipperIteration,
         ZipperMoving, ZipperPathBuffer, ZipperReadOnlyValues,
-        ZipperConcrete, ZipperReadOnlyConditionalValues,
+        ZipperConcrete, ZipperReadOnlyConditionalValues, TrieSummary,
     },
 };
 
@@ -1334,6 +1334,19 @@ where Storage: AsRef<[u8]>
         self.origin_node_depth = self.stack[0].node_depth;
         self
     }
+
+    /// Internal method to count the values at and below the focus, by iterating a fork of the zipper
+    fn count_vals(&self) -> usize {
+        let mut zipper = self.clone().with_root_here();
+        let mut count = 0;
+        if zipper.is_val() {
+            count += 1;
+        }
+        while zipper.to_next_val() {
+            count += 1;
+        }
+        count
+    }
 }
 
 impl<'tree, Storage> ZipperReadOnlyConditionalValues<'tree, ()> for ACTZipper<'tree, Storage, ()>
@@ -1682,6 +1695,36 @@ where Storage: AsRef<[u8]>
     }
 }
 
+impl<'tree, Storage> TrieSummary<()> for ACTZipper<'tree, Storage, ()>
+where Storage: AsRef<[u8]>
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&()>, &[u8]) -> Result<W, E>
+    {
+        self.fork_read_zipper().into_cata_jumping_cached_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        self.count_vals()
+    }
+}
+
+impl<'tree, Storage> TrieSummary<u64> for ACTZipper<'tree, Storage, u64>
+where Storage: AsRef<[u8]>
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&u64>, &[u8]) -> Result<W, E>
+    {
+        self.fork_read_zipper().into_cata_jumping_cached_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        self.count_vals()
+    }
+}
+
 impl<'tree, Storage> ZipperReadOnlyValues<'tree, ()> for ACTZipper<'tree, Storage, ()>
 where Storage: AsRef<[u8]>
 {
@@ -1737,22 +1780,6 @@ where Storage: AsRef<[u8]>
     /// Returns the path from the zipper's root to the current focus
     fn path(&self) -> &[u8] { &self.path[self.origin_depth..] }
 
-    /// Returns the total number of values contained at and below the zipper's focus, including the focus itself
-    ///
-    /// WARNING: This is not a cheap method. It may have an order-N cost
-    fn val_count(&self) -> usize {
-        let mut zipper = self.clone();
-        zipper.reset();
-        let mut count = 0;
-        if zipper.is_val() {
-            count += 1;
-        }
-        while zipper.to_next_val() {
-            count += 1;
-        }
-        count
-    }
-
     /// Moves the zipper deeper into the trie, to the `key` specified relative to the current zipper focus
     ///
     /// Returns `true` if the zipper points to an existing path within the tree, otherwise `false`.  The
@@ -2056,7 +2083,7 @@ where Storage: AsRef<[u8]>
 mod tests {
     use super::{ArenaCompactTree, ACTZipper};
     use crate::{
-        morphisms::Catamorphism, PathMap, zipper::{zipper_iteration_tests, zipper_moving_tests, ZipperIteration, ZipperMoving, ZipperValues}
+        morphisms::Catamorphism, PathMap, utils::TakableSlice, zipper::{zipper_iteration_tests, zipper_moving_tests, ZipperIteration, ZipperMoving, ZipperValues}
     };
 
     zipper_moving_tests::zipper_moving_tests!(arena_compact_zipper,
@@ -2149,7 +2176,7 @@ mod tests {
             format!("('{path}' {val:?} {bm:?}\n{children})")
         });
         let act = ArenaCompactTree::from_zipper(btm.read_zipper(), |&v| v);
-        let act_value = act.read_zipper_u64().into_cata_side_effect(|bm, ch: &mut[String], val: Option<&u64>, path| {
+        let act_value = act.read_zipper_u64().into_cata_side_effect(|bm, ch: &mut TakableSlice<String>, val: Option<&u64>, path| {
             let path = std::str::from_utf8(path).unwrap();
             let children = ch.join(", ");
             format!("('{path}' {val:?} {bm:?}\n{children})")
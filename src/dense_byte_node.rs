@@ -130,6 +130,15 @@ impl<V: Clone + Send + Sync, A: Allocator, Cf: CoFree<V=V, A=A>> ByteNode<Cf, A>
     pub fn with_capacity_in(capacity: usize, alloc: A) -> Self {
         Self::new_with_fields_in(ByteMask::EMPTY, ValuesVec::with_capacity_in(capacity, alloc.clone()), alloc)
     }
+    /// Creates a node that holds only values, taking `vals` in the order of the bits set in `mask`
+    ///
+    /// Panics if the number of bits set in `mask` doesn't match the number of `vals`.
+    pub fn from_vals_in<I: IntoIterator<Item=V>>(mask: ByteMask, vals: I, alloc: A) -> Self {
+        let mut values = ValuesVec::with_capacity_in(mask.count_bits(), alloc.clone());
+        values.v.extend(vals.into_iter().map(|val| Cf::new(None, Some(val))));
+        assert_eq!(values.v.len(), mask.count_bits());
+        Self::new_with_fields_in(mask, values, alloc)
+    }
     #[inline]
     fn new_with_fields_in(mask: ByteMask, values: ValuesVec<Cf, A>, alloc: A) -> Self {
         Self {
@@ -2114,7 +2123,8 @@ impl<V: Clone + Send + Sync + Lattice, A: Allocator, Cf: CoFree<V=V, A=A>, Other
         }
     }
 
-    //GOAT, kept for now, for reference, but this code is unreachable using the current Lattice interface
+    //GOAT, kept for now, for reference, but this code is unreachable using the current Lattice interface.
+    // `PathMap::join_all` merges N operands in one descent with zippers (see `nary_op`), one level above this
     // fn join_all(xs: &[&Self]) -> Self {
     //     let alloc = xs[0].alloc.clone();
     //     let mut jm: ByteMask = ByteMask::EMPTY;
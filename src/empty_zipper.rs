@@ -1,6 +1,6 @@
 
 use crate::zipper::*;
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 
 /// A [`Zipper`] type that moves over a completely empty trie
 #[derive(Clone, Default)]
@@ -35,7 +35,6 @@ impl ZipperMoving for EmptyZipper {
     fn at_root(&self) -> bool { self.path.len() == self.path_start_idx }
     fn reset(&mut self) { self.path.truncate(self.path_start_idx) }
     fn path(&self) -> &[u8] { &self.path[self.path_start_idx..] }
-    fn val_count(&self) -> usize { 0 }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
         self.path.extend_from_slice(k.as_ref());
     }
@@ -97,6 +96,17 @@ impl<V> ZipperForking<V> for EmptyZipper {
     fn fork_read_zipper<'a>(&'a self) -> Self::ReadZipperT<'a> { Self::new_at_path(self.origin_path()) }
 }
 
+impl<V> TrieSummary<V> for EmptyZipper {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        alg_f(&ByteMask::EMPTY, &mut TakableSlice::default(), None, &[])
+    }
+    fn val_count(&self) -> usize { 0 }
+}
+
 impl<'a, V: Clone + Send + Sync> ZipperReadOnlyValues<'a, V> for EmptyZipper {
     fn get_val(&self) -> Option<&'a V> { None }
 }
@@ -38,7 +38,6 @@ impl ZipperMoving for FullZipper {
     fn at_root(&self) -> bool { self.path.len() == 0 }
     fn reset(&mut self) { self.path.clear() }
     fn path(&self) -> &[u8] { &self.path[..] }
-    fn val_count(&self) -> usize { usize::MAX/2 } // usize::MAX is a dangerous default for overflow
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
         self.path.extend_from_slice(k.as_ref());
     }
@@ -126,6 +125,8 @@ impl <V: TrieValue, A: Allocator> ZipperWriting<V, A> for NullZipper {
     fn join_into_take<Z: ZipperSubtries<V, A> + ZipperWriting<V, A>>(&mut self, _src_zipper: &mut Z, prune: bool) -> AlgebraicStatus where V: Lattice { AlgebraicStatus::Element }
     fn join_k_path_into(&mut self, _byte_cnt: usize, _prune: bool) -> bool where V: Lattice { false }
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { false }
+    fn subtract_k_path_into(&mut self, _byte_cnt: usize, _prune: bool) -> bool where V: Lattice + DistributiveLattice { false }
+    fn join_k_path_at_into(&mut self, _offset: usize, _byte_cnt: usize, _prune: bool) -> bool where V: Lattice { false }
     fn insert_prefix<K: AsRef<[u8]>>(&mut self, _prefix: K) -> bool { false }
     fn remove_prefix(&mut self, _n: usize) -> bool { false }
     fn meet_into<Z: ZipperSubtries<V, A>>(&mut self, _read_zipper: &Z, _prune: bool) -> AlgebraicStatus where V: Lattice { AlgebraicStatus::Element }
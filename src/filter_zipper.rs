@@ -0,0 +1,688 @@
+use core::marker::PhantomData;
+
+use crate::alloc::Allocator;
+use crate::utils::{BitMask, ByteMask, TakableSlice};
+use crate::morphisms::summarize_from_focus;
+use crate::PathMap;
+use crate::trie_node::{AbstractNodeRef, TrieNodeODRc};
+use crate::virtual_zipper::{ascend_virtual, jump_source_up, step_to_sibling};
+use crate::zipper::*;
+
+/// A [Zipper] type that wraps another `Zipper`, and exposes only the values accepted by a predicate
+///
+/// The predicate is called with the [`origin_path`](ZipperAbsolutePath::origin_path) and the value at each
+/// path.  By default, only the rejected values are hidden, and the paths leading to them remain visible.
+/// After [with_branch_hiding](FilterZipper::with_branch_hiding), the branches that don't lead to any accepted
+/// value are hidden as well, so the `FilterZipper` behaves exactly as if it were a zipper over a map containing
+/// only the accepted values.
+///
+/// Hiding branches requires visiting the subtries below the focus to find an accepted value.  A summary
+/// callback can be supplied with [with_summary](FilterZipper::with_summary) to skip the subtries known to
+/// contain no accepted values.
+///
+/// The `FilterZipper` implements the same traits as [PrefixZipper], except for the traits that expose the
+/// source trie's nodes directly.  So it can be passed to [serialize_paths](crate::paths_serialization::serialize_paths),
+/// [graft](ZipperWriting::graft), or a [catamorphism](crate::morphisms::Catamorphism).
+///
+/// ```
+/// use pathmap::{PathMap, utils::ByteMask, zipper::*};
+///
+/// let map: PathMap<u64> = [("apple", 3), ("apricot", 8), ("banana", 12)].into_iter().collect();
+/// let mut z = FilterZipper::new(map.read_zipper(), |_path: &[u8], val: &u64| *val > 5).with_branch_hiding();
+///
+/// assert_eq!(z.child_mask(), ByteMask::from_iter([b'a', b'b']));
+/// z.descend_to(b"ap");
+/// assert_eq!(z.child_mask(), ByteMask::from(b'r'));
+/// z.descend_to(b"ple");
+/// assert!(!z.path_exists());
+/// assert_eq!(z.val(), None);
+///
+/// z.reset();
+/// let filtered = z.make_map().unwrap();
+/// assert_eq!(filtered.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>(), vec![(b"apricot".to_vec(), 8), (b"banana".to_vec(), 12)]);
+/// ```
+pub struct FilterZipper<V, Z, P, S = fn(&[u8]) -> bool> {
+    source: Z,
+    predicate: P,
+    summary: S,
+    hide_branches: bool,
+    /// The visible child masks of the focus and its ancestors, keyed by the length of the path
+    masks: Vec<(usize, ByteMask)>,
+    /// `true` if the focus is inside a hidden branch
+    hidden: bool,
+    /// The path the focus jumped up from, used to find the child leading back to it
+    focus_buf: Vec<u8>,
+    _marker: PhantomData<V>,
+}
+
+fn no_summary(_path: &[u8]) -> bool { true }
+
+impl<V, Z, P> FilterZipper<V, Z, P>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+{
+    /// Creates a new `FilterZipper` wrapping the supplied `source` zipper, which hides the values for
+    /// which `predicate` returns `false`
+    pub fn new(source: Z, predicate: P) -> Self {
+        Self::from_parts(source, predicate, no_summary, false)
+    }
+}
+
+impl<V, Z, P, S> FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn from_parts(mut source: Z, predicate: P, summary: S, hide_branches: bool) -> Self {
+        source.reset();
+        let mut z = Self {
+            source,
+            predicate,
+            summary,
+            hide_branches,
+            masks: vec![],
+            hidden: false,
+            focus_buf: vec![],
+            _marker: PhantomData,
+        };
+        z.moved();
+        z
+    }
+
+    /// Hides the branches that don't lead to any value accepted by the predicate
+    ///
+    /// This method resets the zipper.
+    pub fn with_branch_hiding(self) -> Self {
+        Self::from_parts(self.source, self.predicate, self.summary, true)
+    }
+
+    /// Supplies a `summary` callback, used to skip the branches known to contain no accepted values, and
+    /// hides the branches that don't lead to any accepted value
+    ///
+    /// The `summary` callback is called with the `origin_path` of a branch, and should return `false` only if
+    /// no value at or below that path would be accepted by the predicate.  The branches for which it returns
+    /// `false` are hidden without being visited.  This method resets the zipper.
+    pub fn with_summary<NewS: Fn(&[u8]) -> bool>(self, summary: NewS) -> FilterZipper<V, Z, P, NewS> {
+        FilterZipper::from_parts(self.source, self.predicate, summary, true)
+    }
+
+    /// Consumes the `FilterZipper`, and returns the source zipper
+    pub fn into_source(self) -> Z {
+        self.source
+    }
+
+    /// Internal method to check whether the source's value at its focus is accepted by the predicate
+    #[inline]
+    fn accepts_source_val(&self) -> bool {
+        self.source.val().is_some_and(|val| (self.predicate)(self.source.origin_path(), val))
+    }
+
+    /// Internal method to recompute the state that depends on the focus, which must be called whenever
+    /// the focus moves
+    fn moved(&mut self) {
+        self.moved_with(None)
+    }
+
+    /// Internal method to recompute the state that depends on the focus, where `known_child` is a child
+    /// byte of the focus whose visibility is already known, so its subtrie isn't searched again
+    fn moved_with(&mut self, known_child: Option<(u8, bool)>) {
+        if !self.hide_branches {
+            return
+        }
+        let depth = self.source.path().len();
+        while self.masks.last().is_some_and(|(mask_depth, _)| *mask_depth > depth) {
+            self.masks.pop();
+        }
+        if self.masks.last().is_none_or(|(mask_depth, _)| *mask_depth < depth) {
+            let mut candidates = self.source.child_mask();
+            let mut mask = ByteMask::EMPTY;
+            if let Some((byte, visible)) = known_child && candidates.test_bit(byte) {
+                candidates.clear_bit(byte);
+                if visible {
+                    mask.set_bit(byte);
+                }
+            }
+            mask |= self.visible_children(candidates);
+            self.masks.push((depth, mask));
+        }
+        self.hidden = depth > 0 && !(self.source.path_exists() && (self.masks.last().unwrap().1 != ByteMask::EMPTY || self.accepts_source_val()));
+    }
+
+    /// Internal method to find the bytes in `candidates` that lead from the source's focus to an accepted value
+    fn visible_children(&mut self, candidates: ByteMask) -> ByteMask {
+        let mut mask = ByteMask::EMPTY;
+        for byte in candidates.iter() {
+            self.source.descend_to_byte(byte);
+            if self.source_subtrie_accepted() {
+                mask.set_bit(byte);
+            }
+            self.source.ascend_byte();
+        }
+        mask
+    }
+
+    /// Internal method to move the source up to the next point where the filtered trie could stop
+    ///
+    /// The levels passed over don't branch in the source, so whether the child leading back down has any
+    /// accepted value follows from the state of the focus that was left, plus the values passed over.
+    fn jump_up(&mut self, branch_only: bool) {
+        if !self.hide_branches {
+            jump_source_up(&mut self.source, branch_only);
+            return
+        }
+        let from_accepted = !self.hidden;
+        self.focus_buf.clear();
+        self.focus_buf.extend_from_slice(self.source.path());
+        let source_existed = jump_source_up(&mut self.source, branch_only);
+        let depth = self.source.path().len();
+        //`ascend_until` never passes over a value, but `ascend_until_branch` might
+        let child_visible = source_existed && (from_accepted || (branch_only && self.accepts_val_below(depth)));
+        self.moved_with(Some((self.focus_buf[depth], child_visible)));
+    }
+
+    /// Internal method to check whether the predicate accepts a value on the path in `focus_buf`, below the
+    /// source's focus at `depth` and above the end of the path
+    fn accepts_val_below(&mut self, depth: usize) -> bool {
+        let end = self.focus_buf.len() - 1;
+        let mut found = false;
+        let mut cur = depth;
+        while cur < end {
+            self.source.descend_to_byte(self.focus_buf[cur]);
+            cur += 1;
+            if !(self.summary)(self.source.origin_path()) {
+                break
+            }
+            if self.accepts_source_val() {
+                found = true;
+                break
+            }
+        }
+        self.source.ascend(cur - depth);
+        found
+    }
+
+    /// Internal method to search for an accepted value at or below the source's focus, returning the
+    /// source to its original focus before returning
+    fn source_subtrie_accepted(&mut self) -> bool {
+        let base_depth = self.source.path().len();
+        let found = 'search: loop {
+            if (self.summary)(self.source.origin_path()) {
+                if self.accepts_source_val() {
+                    break true
+                }
+                if self.source.descend_first_byte() {
+                    continue
+                }
+            }
+            loop {
+                if self.source.path().len() == base_depth {
+                    break 'search false
+                }
+                if self.source.to_next_sibling_byte() {
+                    break
+                }
+                self.source.ascend_byte();
+            }
+        };
+        let depth = self.source.path().len();
+        self.source.ascend(depth - base_depth);
+        found
+    }
+}
+
+impl<V, Z, P, S> ZipperConcrete for FilterZipper<V, Z, P, S> {
+    /// The predicate may depend on the path, so the subtries of a `FilterZipper` can't be considered
+    /// shared, even if the source's subtries are
+    fn shared_node_id(&self) -> Option<u64> {
+        None
+    }
+    fn is_shared(&self) -> bool {
+        false
+    }
+}
+
+impl<V, Z, P, S> ZipperValues<V> for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+{
+    fn val(&self) -> Option<&V> {
+        self.source.val().filter(|val| (self.predicate)(self.source.origin_path(), val))
+    }
+}
+
+impl<'source, V, Z, P, S> ZipperReadOnlyValues<'source, V> for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperReadOnlyValues<'source, V>,
+        P: Fn(&[u8], &V) -> bool,
+{
+    fn get_val(&self) -> Option<&'source V> {
+        self.source.get_val().filter(|val| (self.predicate)(self.source.origin_path(), val))
+    }
+}
+
+impl<'source, V, Z, P, S> ZipperReadOnlyConditionalValues<'source, V> for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperReadOnlyConditionalValues<'source, V>,
+        P: Fn(&[u8], &V) -> bool,
+{
+    type WitnessT = Z::WitnessT;
+    fn witness<'w>(&self) -> Self::WitnessT { self.source.witness() }
+    fn get_val_with_witness<'w>(&self, witness: &'w Self::WitnessT) -> Option<&'w V> where 'source: 'w {
+        self.source.get_val_with_witness(witness).filter(|val| (self.predicate)(self.source.origin_path(), val))
+    }
+}
+
+impl<V, Z, P, S> ZipperPathBuffer for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V> + ZipperPathBuffer,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    unsafe fn origin_path_assert_len(&self, len: usize) -> &[u8] { unsafe{ self.source.origin_path_assert_len(len) } }
+    fn prepare_buffers(&mut self) { self.source.prepare_buffers() }
+    fn reserve_buffers(&mut self, path_len: usize, stack_depth: usize) { self.source.reserve_buffers(path_len, stack_depth) }
+}
+
+impl<V, Z, P, S> Zipper for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn path_exists(&self) -> bool {
+        !self.hidden && self.source.path_exists()
+    }
+    fn is_val(&self) -> bool {
+        self.accepts_source_val()
+    }
+    fn child_count(&self) -> usize {
+        self.child_mask().count_bits()
+    }
+    fn child_mask(&self) -> ByteMask {
+        if self.hide_branches {
+            self.masks.last().unwrap().1
+        } else {
+            self.source.child_mask()
+        }
+    }
+}
+
+impl<V, Z, P, S> ZipperMoving for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn at_root(&self) -> bool {
+        self.source.at_root()
+    }
+
+    fn reset(&mut self) {
+        self.source.reset();
+        self.moved();
+    }
+
+    #[inline]
+    fn path(&self) -> &[u8] {
+        self.source.path()
+    }
+
+    fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
+        let path = k.as_ref();
+        if path.is_empty() {
+            return
+        }
+        self.source.descend_to(path);
+        self.moved();
+    }
+
+    fn descend_to_byte(&mut self, k: u8) {
+        self.source.descend_to_byte(k);
+        self.moved();
+    }
+
+    fn ascend(&mut self, steps: usize) -> bool {
+        //The subtrie left behind is below the child on the path, so an accepted value in it makes that child
+        //visible, and if it was the only level passed then the child's visibility is the same as the focus'
+        let from_depth = self.source.path().len();
+        let to_depth = from_depth.saturating_sub(steps);
+        let child = self.source.path().get(to_depth).copied();
+        let from_accepted = !self.hidden;
+        let ascended = self.source.ascend(steps);
+        let known_child = child.filter(|_| from_accepted || from_depth - to_depth == 1).map(|byte| (byte, from_accepted));
+        self.moved_with(known_child);
+        ascended
+    }
+
+    fn ascend_until(&mut self) -> bool {
+        ascend_virtual(self, false, |z, branch_only| z.jump_up(branch_only))
+    }
+
+    fn ascend_until_branch(&mut self) -> bool {
+        ascend_virtual(self, true, |z, branch_only| z.jump_up(branch_only))
+    }
+
+    fn to_next_sibling_byte(&mut self) -> bool {
+        if self.hide_branches {
+            return step_to_sibling(self, true)
+        }
+        self.source.to_next_sibling_byte()
+    }
+
+    fn to_prev_sibling_byte(&mut self) -> bool {
+        if self.hide_branches {
+            return step_to_sibling(self, false)
+        }
+        self.source.to_prev_sibling_byte()
+    }
+}
+
+impl<V, Z, P, S> TrieSummary<V> for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V> + ZipperForking<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        let mut z = self.fork_read_zipper();
+        summarize_from_focus(&mut z, self.val(), alg_f)
+    }
+}
+
+impl<V, Z, P, S> ZipperAbsolutePath for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn origin_path(&self) -> &[u8] {
+        self.source.origin_path()
+    }
+    fn root_prefix_path(&self) -> &[u8] {
+        self.source.root_prefix_path()
+    }
+}
+
+impl<V, Z, P, S> ZipperIteration for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{ }
+
+impl<'a, V, Z, P, S> ZipperReadOnlyIteration<'a, V> for FilterZipper<V, Z, P, S> where Z: ZipperAbsolutePath + ZipperReadOnlyValues<'a, V>, P: Fn(&[u8], &V) -> bool, S: Fn(&[u8]) -> bool { }
+
+impl<'a, V, Z, P, S> ZipperReadOnlyConditionalIteration<'a, V> for FilterZipper<V, Z, P, S> where Z: ZipperAbsolutePath + ZipperReadOnlyConditionalValues<'a, V>, P: Fn(&[u8], &V) -> bool, S: Fn(&[u8]) -> bool { }
+
+impl<V, Z, P, S> ZipperForking<V> for FilterZipper<V, Z, P, S>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V> + ZipperForking<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    type ReadZipperT<'a> = FilterZipper<V, Z::ReadZipperT<'a>, &'a P, &'a S> where Self: 'a;
+    fn fork_read_zipper<'a>(&'a self) -> Self::ReadZipperT<'a> {
+        FilterZipper::from_parts(self.source.fork_read_zipper(), &self.predicate, &self.summary, self.hide_branches)
+    }
+}
+
+impl<V, Z, P, S> FilterZipper<V, Z, P, S>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    /// Internal method to remove the values hidden by the filter from `map`, which contains the source's
+    /// subtrie below the focus
+    fn filter_map<A: Allocator>(&self, map: &mut PathMap<V, A>) {
+        let mut origin_path = self.source.origin_path().to_vec();
+        let origin_len = origin_path.len();
+        let mut hidden_paths = vec![];
+        let mut rz = map.read_zipper();
+        while let Some(val) = rz.to_next_get_val() {
+            origin_path.truncate(origin_len);
+            origin_path.extend_from_slice(rz.path());
+            let summary_accepts = !self.hide_branches || (origin_len+1..=origin_path.len()).all(|len| (self.summary)(&origin_path[..len]));
+            if !summary_accepts || !(self.predicate)(&origin_path, val) {
+                hidden_paths.push(rz.path().to_vec());
+            }
+        }
+        drop(rz);
+        for path in hidden_paths {
+            map.remove_val_at(path, self.hide_branches);
+        }
+        if map.root_val().is_some() && !self.accepts_source_val() {
+            map.root_val_mut().take();
+        }
+    }
+}
+
+impl<V, Z, P, S, A: Allocator> ZipperSubtries<V, A> for FilterZipper<V, Z, P, S>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperAbsolutePath + ZipperSubtries<V, A>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    fn make_map(&self) -> Option<PathMap<Self::V, A>> {
+        if self.hidden {
+            return None
+        }
+        let mut map = self.source.make_map()?;
+        self.filter_map(&mut map);
+        Some(map)
+    }
+}
+
+impl<V, Z, P, S, A: Allocator> zipper_priv::ZipperPriv for FilterZipper<V, Z, P, S>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperAbsolutePath + ZipperSubtries<V, A> + zipper_priv::ZipperPriv<V=V, A=A>,
+        P: Fn(&[u8], &V) -> bool,
+        S: Fn(&[u8]) -> bool,
+{
+    type V = V;
+    type A = A;
+    /// The filtered subtrie shares all the unmodified nodes with the source, but the nodes along the paths to
+    /// the hidden values must be copied
+    fn get_focus(&self) -> AbstractNodeRef<'_, Self::V, Self::A> {
+        match self.make_map().and_then(|map| map.into_root().0) {
+            Some(node) => AbstractNodeRef::OwnedRc(node),
+            None => AbstractNodeRef::None,
+        }
+    }
+    fn try_borrow_focus(&self) -> Option<&TrieNodeODRc<Self::V, Self::A>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FilterZipper;
+    use crate::alloc::GlobalAlloc;
+    use crate::morphisms::Catamorphism;
+    use crate::trie_map::PathMap;
+    use crate::utils::{ByteMask, TakableSlice};
+    use crate::zipper::*;
+
+    #[test]
+    fn filter_zipper_test1() {
+        let map: PathMap<u64> = (0u64..300).map(|i| (format!("{}:{}:{}", i % 7, i % 13, i), i)).collect();
+        let accept = |_path: &[u8], val: &u64| val % 10 < 2 && val % 7 != 3;
+        let expected: PathMap<u64> = map.iter().filter(|(k, v)| accept(k, v)).map(|(k, v)| (k, *v)).collect();
+
+        //Without branch hiding, the paths stay visible
+        let mut z = FilterZipper::new(map.read_zipper(), accept);
+        let mut contents = vec![];
+        while let Some(val) = z.to_next_get_val() {
+            contents.push((z.path().to_vec(), *val));
+        }
+        assert_eq!(contents, expected.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>());
+        z.descend_to(b"3:");
+        assert!(z.path_exists());
+        assert_eq!(z.child_mask(), map.read_zipper_at_path(b"3:").child_mask());
+
+        //With branch hiding, the zipper matches the filtered map
+        let mut z = FilterZipper::new(map.read_zipper(), accept).with_branch_hiding();
+        let mut expected_z = expected.read_zipper();
+        assert_eq!(z.child_mask(), expected_z.child_mask());
+        z.descend_to(b"3:");
+        expected_z.descend_to(b"3:");
+        assert!(!z.path_exists());
+        assert_eq!(z.child_mask(), ByteMask::EMPTY);
+        z.move_to_path(b"4:");
+        expected_z.move_to_path(b"4:");
+        assert_eq!(z.child_mask(), expected_z.child_mask());
+        assert_eq!(z.descend_until(), expected_z.descend_until());
+        assert_eq!(z.path(), expected_z.path());
+        z.reset();
+        expected_z.reset();
+        let mut steps = 0;
+        while z.to_next_step() {
+            assert!(expected_z.to_next_step());
+            assert_eq!(z.path(), expected_z.path());
+            assert_eq!(z.val(), expected_z.val());
+            steps += 1;
+        }
+        assert!(!expected_z.to_next_step());
+        assert!(steps > 0);
+
+        //The filtered view can be passed to the functions that consume zippers
+        let z = FilterZipper::new(map.read_zipper(), accept).with_branch_hiding();
+        assert_eq!(z.val_count(), expected.val_count());
+        assert_eq!(z.make_map().unwrap().iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+        let mut grafted = PathMap::new();
+        grafted.write_zipper_at_path(b"filtered:").graft(&z);
+        assert_eq!(grafted.read_zipper_at_path(b"filtered:").make_map().unwrap().iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+        let cata_count = z.into_cata_side_effect(|_mask, children: &mut TakableSlice<usize>, val, _path| {
+            children.iter().sum::<usize>() + val.is_some() as usize
+        });
+        assert_eq!(cata_count, expected.val_count());
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn filter_zipper_serialize_test() {
+        use crate::paths_serialization::serialize_paths;
+
+        let map: PathMap<()> = (0u64..100).map(|i| (format!("{i}:{}", i * i), ())).collect();
+        let accept = |path: &[u8], _val: &()| path[0] == b'1';
+        let expected: PathMap<()> = map.iter().filter(|(k, v)| accept(k, v)).map(|(k, _)| (k, ())).collect();
+
+        let mut filtered_buf = vec![];
+        serialize_paths(FilterZipper::new(map.read_zipper(), accept).with_branch_hiding(), &mut filtered_buf).unwrap();
+        let mut expected_buf = vec![];
+        serialize_paths(expected.read_zipper(), &mut expected_buf).unwrap();
+        assert_eq!(filtered_buf, expected_buf);
+    }
+
+    #[test]
+    fn filter_zipper_summary_test() {
+        let map: PathMap<u64> = [("a:1", 1), ("a:2", 2), ("b:1", 3), ("b:2", 4), ("c:1", 5)].into_iter().collect();
+        let visited = std::cell::RefCell::new(vec![]);
+        let mut z = FilterZipper::new(map.read_zipper(), |path: &[u8], _val: &u64| {
+            visited.borrow_mut().push(path.to_vec());
+            path.ends_with(b"2")
+        }).with_summary(|path: &[u8]| path[0] != b'c');
+
+        //The summary skips "c" without calling the predicate
+        assert_eq!(z.child_mask(), ByteMask::from_iter([b'a', b'b']));
+        assert!(visited.borrow().iter().all(|path| !path.starts_with(b"c")));
+        z.descend_to(b"a:");
+        assert_eq!(z.child_mask(), ByteMask::from(b'2'));
+
+        z.reset();
+        let mut contents = vec![];
+        while z.to_next_val() {
+            contents.push(z.path().to_vec());
+        }
+        assert_eq!(contents, vec![b"a:2".to_vec(), b"b:2".to_vec()]);
+
+        //The predicate sees the full origin path
+        let mut z = FilterZipper::new(map.read_zipper_at_path(b"b:"), |path: &[u8], _val: &u64| path == b"b:1").with_branch_hiding();
+        assert_eq!(z.child_mask(), ByteMask::from(b'1'));
+        assert!(z.to_next_val());
+        assert_eq!(z.origin_path(), b"b:1");
+        assert_eq!(z.val(), Some(&3));
+    }
+
+    #[test]
+    fn filter_zipper_ascend_test() {
+        use core::cell::Cell;
+
+        let map: PathMap<u64> = (0u64..400).map(|i| (format!("{}:{}:{}", i % 5, i % 11, i), i)).collect();
+        let calls = Cell::new(0);
+        let accept = |_path: &[u8], val: &u64| {
+            calls.set(calls.get() + 1);
+            val % 97 == 96 || *val == 2
+        };
+        let expected: PathMap<u64> = map.iter().filter(|(_k, v)| **v % 97 == 96 || **v == 2).map(|(k, v)| (k, *v)).collect();
+
+        //Ascending stops at the same places as in the filtered map, including from hidden paths
+        let mut z = FilterZipper::new(map.read_zipper(), accept).with_branch_hiding();
+        let mut expected_z = expected.read_zipper();
+        for path in [&b"2:2:387"[..], b"2:2:2", b"2:2:3", b"3:4:3", b"1:10:"] {
+            for branch_only in [false, true] {
+                z.move_to_path(path);
+                expected_z.move_to_path(path);
+                assert_eq!(z.path_exists(), expected_z.path_exists());
+                loop {
+                    let ascended = if branch_only { z.ascend_until_branch() } else { z.ascend_until() };
+                    let expected_ascended = if branch_only { expected_z.ascend_until_branch() } else { expected_z.ascend_until() };
+                    assert_eq!(ascended, expected_ascended);
+                    assert_eq!(z.path(), expected_z.path());
+                    assert_eq!(z.child_mask(), expected_z.child_mask());
+                    if !ascended {
+                        break
+                    }
+                }
+            }
+        }
+
+        //Each subtrie is searched once while ascending, not once for every byte of the path above it
+        let chain = "c".repeat(20);
+        let mut map: PathMap<u64> = (0u64..50).map(|i| (format!("x:{chain}:{i}"), i)).collect();
+        map.set_val_at(b"y", 1000);
+        let mut z = FilterZipper::new(map.read_zipper(), |_path: &[u8], val: &u64| {
+            calls.set(calls.get() + 1);
+            *val == 1000
+        }).with_branch_hiding();
+        z.descend_to(format!("x:{chain}:0"));
+        assert!(!z.path_exists());
+        calls.set(0);
+        assert!(z.ascend_until());
+        assert_eq!(z.path(), b"");
+        assert_eq!(z.child_mask(), ByteMask::from(b'y'));
+        assert!(calls.get() < 50);
+    }
+
+    type Predicate = fn(&[u8], &()) -> bool;
+    type FZ<'a, V, A=GlobalAlloc> = FilterZipper<V, ReadZipperUntracked<'a, 'static, V, A>, Predicate>;
+    fn accept_all(_path: &[u8], _val: &()) -> bool { true }
+
+    zipper_moving_tests::zipper_moving_tests!(filter_zipper,
+        |keys: &[&[u8]]| {
+            keys.iter().map(|k| (k, ())).collect::<PathMap<()>>()
+        },
+        |trie: &mut PathMap<()>, path: &[u8]| -> FZ<'_, ()> {
+            FilterZipper::new(trie.read_zipper_at_path(path), accept_all as Predicate).with_branch_hiding()
+        }
+    );
+
+    zipper_iteration_tests::zipper_iteration_tests!(filter_zipper,
+        |keys: &[&[u8]]| {
+            keys.iter().map(|k| (k, ())).collect::<PathMap<()>>()
+        },
+        |trie: &mut PathMap<()>, path: &[u8]| -> FZ<'_, ()> {
+            FilterZipper::new(trie.read_zipper_at_path(path), accept_all as Predicate).with_branch_hiding()
+        }
+    );
+}
@@ -0,0 +1,496 @@
+use core::cell::OnceCell;
+
+use crate::alloc::{global_alloc, Allocator, GlobalAlloc};
+use crate::PathMap;
+use crate::ring::Lattice;
+use crate::trie_node::{AbstractNodeRef, TrieNodeODRc};
+use crate::utils::{BitMask, ByteMask};
+use crate::virtual_zipper::{ascend_virtual, SyncedSources};
+use crate::zipper::*;
+
+/// Zipper that traverses a virtual trie formed by the union of the tries of any number of source zippers
+///
+/// The values of the sources at each path are merged by a closure, so the merged values are created as
+/// the zipper moves, and nothing is materialized until [make_map](ZipperSubtries::make_map) is called.  This
+/// makes it possible to query several layers of a trie, e.g. a "current" and a "staging" layer, as if they
+/// had been joined.
+///
+/// All the source zippers move together, so each source is positioned at the `JoinZipper`'s path, whether
+/// or not the path exists in that source.
+///
+/// The subtrie materialized below the focus, e.g. to [graft](ZipperWriting::graft) it, is kept until the
+/// zipper moves.  It is allocated in the allocator supplied to [new_in](JoinZipper::new_in) or
+/// [with_merge_in](JoinZipper::with_merge_in), which should be the allocator of the sources.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::zipper::*;
+///
+/// let current: PathMap<u64> = [("apple", 1), ("banana", 2)].into_iter().collect();
+/// let staging: PathMap<u64> = [("banana", 20), ("cherry", 30)].into_iter().collect();
+///
+/// //Staged values take precedence over current ones
+/// let mut z = JoinZipper::with_merge([current.read_zipper(), staging.read_zipper()], |vals: &[Option<&u64>]| {
+///     vals[1].or(vals[0]).copied()
+/// });
+/// let mut contents = vec![];
+/// while z.to_next_val() {
+///     contents.push((z.path().to_vec(), *z.val().unwrap()));
+/// }
+/// assert_eq!(contents, vec![(b"apple".to_vec(), 1), (b"banana".to_vec(), 20), (b"cherry".to_vec(), 30)]);
+/// ```
+pub struct JoinZipper<V: Clone + Send + Sync, Z, F, A: Allocator = GlobalAlloc> {
+    /// The sources, and the state computed from them at the focus, which is discarded when the focus moves
+    sources: SyncedSources<Vec<Z>, JoinFocus<V, A>>,
+    merge: F,
+    alloc: A,
+}
+
+/// The state of a [JoinZipper] at its focus, each part computed the first time it's requested after each move
+struct JoinFocus<V: Clone + Send + Sync, A: Allocator> {
+    /// The merged value
+    val: OnceCell<Option<V>>,
+    /// The root of the materialized subtrie below the focus
+    node: OnceCell<Option<TrieNodeODRc<V, A>>>,
+}
+
+impl<V: Clone + Send + Sync, A: Allocator> JoinFocus<V, A> {
+    fn new() -> Self {
+        Self { val: OnceCell::new(), node: OnceCell::new() }
+    }
+}
+
+/// The default merge function for [JoinZipper], which joins all the values that are present
+fn join_vals<V: Clone + Lattice>(vals: &[Option<&V>]) -> Option<V> {
+    let mut present = vals.iter().flatten();
+    let first = (*present.next()?).clone();
+    Some(present.fold(first, |mut joined, val| {
+        joined.join_into((*val).clone());
+        joined
+    }))
+}
+
+impl<V, Z> JoinZipper<V, Z, fn(&[Option<&V>]) -> Option<V>>
+    where
+        V: Clone + Send + Sync + Lattice,
+        Z: ZipperMoving + ZipperValues<V>,
+{
+    /// Creates a new `JoinZipper` over `sources`, which joins the values with their [Lattice] implementation
+    ///
+    /// Panics if `sources` is empty.
+    pub fn new<Sources: IntoIterator<Item=Z>>(sources: Sources) -> Self {
+        Self::with_merge(sources, join_vals)
+    }
+}
+
+impl<V, Z, A> JoinZipper<V, Z, fn(&[Option<&V>]) -> Option<V>, A>
+    where
+        V: Clone + Send + Sync + Lattice,
+        Z: ZipperMoving + ZipperValues<V>,
+        A: Allocator,
+{
+    /// Creates a new `JoinZipper` over `sources` in the specified allocator, which joins the values with
+    /// their [Lattice] implementation
+    ///
+    /// Panics if `sources` is empty.
+    pub fn new_in<Sources: IntoIterator<Item=Z>>(sources: Sources, alloc: A) -> Self {
+        Self::with_merge_in(sources, join_vals, alloc)
+    }
+}
+
+impl<V, Z, F> JoinZipper<V, Z, F>
+    where
+        V: Clone + Send + Sync,
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+{
+    /// Creates a new `JoinZipper` over `sources`, using `merge` to create the value at each path
+    ///
+    /// `merge` is called with the value of each source, in the same order as `sources`, and its result
+    /// becomes the value of the `JoinZipper`.  Returning `None` hides the value, but not the path.  `merge`
+    /// should return `None` when no source has a value, otherwise [ascend_until](ZipperMoving::ascend_until)
+    /// may pass over the created value.  Each source zipper is reset to its root.
+    ///
+    /// Panics if `sources` is empty.
+    pub fn with_merge<Sources: IntoIterator<Item=Z>>(sources: Sources, merge: F) -> Self {
+        let sources: Vec<Z> = sources.into_iter().collect();
+        assert!(!sources.is_empty(), "JoinZipper requires at least one source");
+        Self {
+            sources: SyncedSources::new(sources),
+            merge,
+            alloc: global_alloc(),
+        }
+    }
+}
+
+impl<V, Z, F, A> JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+        A: Allocator,
+{
+    /// Creates a new `JoinZipper` over `sources` in the specified allocator, using `merge` to create the value
+    /// at each path
+    ///
+    /// See [with_merge](JoinZipper::with_merge) for the behavior of `merge`.  Panics if `sources` is empty.
+    pub fn with_merge_in<Sources: IntoIterator<Item=Z>>(sources: Sources, merge: F, alloc: A) -> Self {
+        let sources: Vec<Z> = sources.into_iter().collect();
+        assert!(!sources.is_empty(), "JoinZipper requires at least one source");
+        Self {
+            sources: SyncedSources::new(sources),
+            merge,
+            alloc,
+        }
+    }
+
+    /// Returns a reference to the source zipper at `source_idx`
+    pub fn source(&self, source_idx: usize) -> &Z {
+        &self.sources.sources()[source_idx]
+    }
+
+    /// Returns the number of source zippers
+    pub fn source_count(&self) -> usize {
+        self.sources.sources().len()
+    }
+
+    /// Consumes the `JoinZipper`, and returns the source zippers
+    pub fn into_sources(self) -> Vec<Z> {
+        self.sources.into_sources()
+    }
+}
+
+impl<V, Z, F, A> JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        A: Allocator,
+{
+    /// Internal method to access the state at the focus
+    #[inline]
+    fn focus(&self) -> &JoinFocus<V, A> {
+        self.sources.cached(|_sources| JoinFocus::new())
+    }
+}
+
+impl<V, Z, F, A> ZipperValues<V> for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        Z: ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+        A: Allocator,
+{
+    fn val(&self) -> Option<&V> {
+        self.focus().val.get_or_init(|| {
+            let vals: Vec<Option<&V>> = self.sources.sources().iter().map(|source| source.val()).collect();
+            (self.merge)(&vals)
+        }).as_ref()
+    }
+}
+
+impl<V, Z, F, A> Zipper for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        A: Allocator,
+        Z: Zipper + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+{
+    fn path_exists(&self) -> bool {
+        self.sources.sources().iter().any(|source| source.path_exists())
+    }
+    fn is_val(&self) -> bool {
+        //NOTE: the merge function can hide a value, so we can't just ask the sources
+        self.val().is_some()
+    }
+    fn child_count(&self) -> usize {
+        self.child_mask().count_bits()
+    }
+    fn child_mask(&self) -> ByteMask {
+        self.sources.sources().iter().fold(ByteMask::EMPTY, |mask, source| mask | source.child_mask())
+    }
+}
+
+impl<V, Z, F, A> ZipperMoving for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        A: Allocator,
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+{
+    fn at_root(&self) -> bool {
+        self.sources.at_root()
+    }
+
+    fn reset(&mut self) {
+        self.sources.reset()
+    }
+
+    fn path(&self) -> &[u8] {
+        self.sources.path()
+    }
+
+    fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
+        self.sources.descend_to(k.as_ref())
+    }
+
+    fn descend_to_byte(&mut self, k: u8) {
+        self.sources.descend_to_byte(k)
+    }
+
+    fn ascend(&mut self, steps: usize) -> bool {
+        self.sources.ascend(steps)
+    }
+
+    fn ascend_until(&mut self) -> bool {
+        ascend_virtual(self, false, |z, branch_only| z.sources.jump_up(branch_only))
+    }
+
+    fn ascend_until_branch(&mut self) -> bool {
+        ascend_virtual(self, true, |z, branch_only| z.sources.jump_up(branch_only))
+    }
+}
+
+impl<V, Z, F, A> ZipperIteration for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        A: Allocator,
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+{ }
+
+impl<V, Z, F, A> ZipperAbsolutePath for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync,
+        A: Allocator,
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+{
+    /// Returns the origin path of the first source zipper
+    fn origin_path(&self) -> &[u8] {
+        self.source(0).origin_path()
+    }
+    /// Returns the root prefix path of the first source zipper
+    fn root_prefix_path(&self) -> &[u8] {
+        self.source(0).root_prefix_path()
+    }
+}
+
+impl<V, Z, F, A> JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperMoving + ZipperValues<V> + ZipperForking<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+        A: Allocator,
+{
+    /// Internal method to materialize the merged subtrie below the focus, by traversing a `JoinZipper` over
+    /// forks of the sources
+    fn materialize(&self) -> PathMap<V, A> {
+        let forks: Vec<Z::ReadZipperT<'_>> = self.sources.sources().iter().map(|source| source.fork_read_zipper()).collect();
+        let mut z = JoinZipper::with_merge_in(forks, &self.merge, self.alloc.clone());
+        let mut map = PathMap::new_in(self.alloc.clone());
+        let mut wz = map.write_zipper();
+        while z.to_next_val() {
+            wz.move_to_path(z.path());
+            wz.set_val(z.val().unwrap().clone());
+        }
+        drop(wz);
+        map
+    }
+
+    /// Internal method to get the root of the materialized subtrie below the focus, which is only
+    /// materialized once until the focus moves
+    fn focus_node(&self) -> Option<&TrieNodeODRc<V, A>> {
+        self.focus().node.get_or_init(|| {
+            if !self.path_exists() {
+                return None
+            }
+            self.materialize().into_root().0
+        }).as_ref()
+    }
+}
+
+impl<V, Z, F, A> ZipperSubtries<V, A> for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperMoving + ZipperValues<V> + ZipperForking<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+        A: Allocator,
+{
+    fn make_map(&self) -> Option<PathMap<V, A>> {
+        #[cfg(not(feature = "graft_root_vals"))]
+        let root_val = None;
+        #[cfg(feature = "graft_root_vals")]
+        let root_val = self.val().cloned();
+
+        let root_node = self.focus_node().cloned();
+        if root_node.is_none() && root_val.is_none() {
+            return None
+        }
+        Some(PathMap::new_with_root_in(root_node, root_val, self.alloc.clone()))
+    }
+}
+
+impl<V, Z, F, A> zipper_priv::ZipperPriv for JoinZipper<V, Z, F, A>
+    where
+        V: Clone + Send + Sync + Unpin,
+        Z: ZipperMoving + ZipperValues<V> + ZipperForking<V>,
+        F: Fn(&[Option<&V>]) -> Option<V>,
+        A: Allocator,
+{
+    type V = V;
+    type A = A;
+    /// The merged trie doesn't exist in memory, so it is materialized the first time it's needed at each
+    /// focus, which is the slowest case
+    fn get_focus(&self) -> AbstractNodeRef<'_, Self::V, Self::A> {
+        match self.focus_node() {
+            Some(node) => AbstractNodeRef::BorrowedRc(node),
+            None => AbstractNodeRef::None,
+        }
+    }
+    fn try_borrow_focus(&self) -> Option<&TrieNodeODRc<Self::V, Self::A>> {
+        None
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PathMap;
+    use crate::alloc::GlobalAlloc;
+    use crate::zipper::*;
+    use crate::utils::ByteMask;
+
+    #[test]
+    fn join_zipper_test1() {
+        let maps: Vec<PathMap<Vec<u64>>> = (0u64..3).map(|k| {
+            (0u64..50).filter(|i| (i + k) % 3 != 0)
+                .map(|i| (format!("{}:{}", i % (7 + k), i % 4), vec![k]))
+                .collect()
+        }).collect();
+        let expected = maps.iter().fold(PathMap::new(), |acc, map| acc.join(map));
+
+        //Iteration visits the values of the join, in order
+        let mut z = JoinZipper::new(maps.iter().map(|map| map.read_zipper()));
+        let mut contents = vec![];
+        while z.to_next_val() {
+            contents.push((z.path().to_vec(), z.val().unwrap().clone()));
+        }
+        assert_eq!(contents, expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+
+        //Movement matches the materialized join
+        let mut z = JoinZipper::new(maps.iter().map(|map| map.read_zipper()));
+        let mut expected_z = expected.read_zipper();
+        z.descend_to(b"1");
+        expected_z.descend_to(b"1");
+        assert_eq!(z.child_mask(), expected_z.child_mask());
+        assert_eq!(z.descend_until(), expected_z.descend_until());
+        assert_eq!(z.path(), expected_z.path());
+        assert_eq!(z.val(), expected_z.val());
+        assert_eq!(z.ascend_until(), expected_z.ascend_until());
+        assert_eq!(z.path(), expected_z.path());
+        z.descend_to(b"nothing");
+        assert!(!z.path_exists());
+        assert_eq!(z.val(), None);
+        z.reset();
+
+        //k-path iteration
+        let mut z_paths = vec![];
+        let mut found = z.descend_first_k_path(2);
+        while found {
+            z_paths.push(z.path().to_vec());
+            found = z.to_next_k_path(2);
+        }
+        let mut expected_paths = vec![];
+        let mut found = expected_z.descend_first_k_path(2);
+        while found {
+            expected_paths.push(expected_z.path().to_vec());
+            found = expected_z.to_next_k_path(2);
+        }
+        assert_eq!(z_paths, expected_paths);
+
+        //`make_map` materializes the subtrie below the focus
+        z.descend_to(b"2");
+        let sub_map = z.make_map().unwrap();
+        let expected_sub_map = expected.read_zipper_at_path(b"2").make_map().unwrap();
+        assert_eq!(sub_map.iter().collect::<Vec<_>>(), expected_sub_map.iter().collect::<Vec<_>>());
+
+        //A `JoinZipper` can be the source of a graft
+        let mut grafted = PathMap::new();
+        grafted.write_zipper_at_path(b"joined:").graft(&z);
+        assert_eq!(grafted.read_zipper_at_path(b"joined:").make_map().unwrap().iter().collect::<Vec<_>>(), expected_sub_map.iter().collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn join_zipper_test2() {
+        let current: PathMap<u64> = [("a", 1), ("ab", 2), ("b", 3)].into_iter().collect();
+        let staging: PathMap<u64> = [("a", 10), ("abc", 20), ("c", 30)].into_iter().collect();
+
+        //Only keep the paths in both layers, and sum their values
+        let mut z = JoinZipper::with_merge([current.read_zipper_at_path(b"a"), staging.read_zipper_at_path(b"a")], |vals: &[Option<&u64>]| {
+            Some(*vals[0]? + *vals[1]?)
+        });
+        assert_eq!(z.origin_path(), b"a");
+        assert_eq!(z.root_prefix_path(), b"a");
+        assert_eq!(z.val(), Some(&11));
+        assert_eq!(z.child_mask(), ByteMask::from_iter([b'b']));
+        z.descend_to_byte(b'b');
+        assert!(z.path_exists());
+        assert!(!z.is_val());
+        assert_eq!(z.origin_path(), b"ab");
+        assert!(!z.to_next_val());
+        assert_eq!(z.path(), b"");
+        assert_eq!(z.source_count(), 2);
+    }
+
+    /// The materialized subtrie is kept until the zipper moves
+    #[test]
+    fn join_zipper_test3() {
+        use crate::trie_node::AbstractNodeRef;
+        use crate::zipper::zipper_priv::ZipperPriv;
+
+        let current: PathMap<u64> = [("a:1", 1), ("a:2", 2), ("b:1", 3)].into_iter().collect();
+        let staging: PathMap<u64> = [("a:3", 30), ("b:2", 40)].into_iter().collect();
+        let mut z = JoinZipper::new([current.read_zipper(), staging.read_zipper()]);
+        z.descend_to(b"a:");
+        let (AbstractNodeRef::BorrowedRc(first), AbstractNodeRef::BorrowedRc(second)) = (z.get_focus(), z.get_focus()) else {
+            panic!("expected the materialized node to be borrowed");
+        };
+        assert!(first.ptr_eq(second));
+        let first = first.clone();
+        assert!(z.make_map().unwrap().root().unwrap().ptr_eq(&first));
+
+        z.move_to_path(b"b:");
+        let sub_map = z.make_map().unwrap();
+        assert!(!sub_map.root().unwrap().ptr_eq(&first));
+        assert_eq!(sub_map.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>(), vec![(b"1".to_vec(), 3), (b"2".to_vec(), 40)]);
+        z.descend_to(b"nothing");
+        assert!(matches!(z.get_focus(), AbstractNodeRef::None));
+        assert!(z.make_map().is_none());
+    }
+
+    type Merge = fn(&[Option<&()>]) -> Option<()>;
+    type JZ<'a, V, A=GlobalAlloc> = JoinZipper<V, ReadZipperUntracked<'a, 'static, V, A>, Merge>;
+
+    /// Spreads the keys over three sources, so each path may be found in any of them
+    fn split_keys(keys: &[&[u8]]) -> Vec<PathMap<()>> {
+        (0..3).map(|source_idx| {
+            keys.iter().enumerate().filter(|(key_idx, _key)| key_idx % 3 == source_idx).map(|(_key_idx, key)| (key, ())).collect()
+        }).collect()
+    }
+
+    zipper_moving_tests::zipper_moving_tests!(join_zipper,
+        split_keys,
+        |tries: &mut Vec<PathMap<()>>, path: &[u8]| -> JZ<'_, ()> {
+            JoinZipper::with_merge(tries.iter().map(|trie| trie.read_zipper_at_path(path)), |vals: &[Option<&()>]| {
+                vals.iter().flatten().next().map(|_| ())
+            })
+        }
+    );
+
+    zipper_iteration_tests::zipper_iteration_tests!(join_zipper,
+        split_keys,
+        |tries: &mut Vec<PathMap<()>>, path: &[u8]| -> JZ<'_, ()> {
+            JoinZipper::with_merge(tries.iter().map(|trie| trie.read_zipper_at_path(path)), |vals: &[Option<&()>]| {
+                vals.iter().flatten().next().map(|_| ())
+            })
+        }
+    );
+}
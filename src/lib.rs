@@ -135,6 +135,13 @@ mod product_zipper;
 mod empty_zipper;
 mod prefix_zipper;
 mod overlay_zipper;
+mod trie_join;
+mod virtual_zipper;
+mod join_zipper;
+mod meet_zipper;
+mod subtract_zipper;
+mod filter_zipper;
+mod map_zipper;
 mod trie_ref;
 mod dense_byte_node;
 pub(crate) mod line_list_node;
@@ -955,15 +955,15 @@ impl<V: Clone + Send + Sync, A: Allocator> LineListNode<V, A> {
             return Ok((Some(ValOrChild::from_union::<IS_CHILD>(payload)), false));
         }
 
-        //If this node is empty, insert the new key-payload into slot_0
+        //If we have an empty (dangling) payload anywhere along the new key, remove it
+        self.remove_dangling_payload_along_key(key);
+
+        //If this node is empty, possibly because the only payload was dangling, insert the new key-payload into slot_0
         if !self.is_used::<0>() {
             let created_subnode = unsafe{ self.set_payload_0_no_overflow(key, IS_CHILD, payload) };
             return Ok((None, created_subnode))
         }
 
-        //If we have an empty (dangling) payload anywhere along the new key, remove it
-        self.remove_dangling_payload_along_key(key);
-
         //If the key has overlap with slot_0, split the key, and add the payload to the child
         let node_key_0 = unsafe{ self.key_unchecked::<0>() };
         let mut overlap = find_prefix_overlap(key, node_key_0);
@@ -1782,8 +1782,15 @@ impl<V: Clone + Send + Sync, A: Allocator> TrieNode<V, A> for LineListNode<V, A>
                 if prune {
                     return Some(self.take_payload::<1>().unwrap().into_val())
                 } else {
-                    //Turn the value into an empty node
-                    return Some(self.swap_payload::<1>(ValOrChild::Child(TrieNodeODRc::new_empty())).into_val())
+                    //If the other slot already keeps this path, then just remove the value
+                    let node_key_0 = unsafe{ self.key_unchecked::<0>() };
+                    let overlap = find_prefix_overlap(node_key_0, node_key_1);
+                    if node_key_1.len() == overlap {
+                        return Some(self.take_payload::<1>().unwrap().into_val())
+                    } else {
+                        //Otherwise, turn the value into an empty node
+                        return Some(self.swap_payload::<1>(ValOrChild::Child(TrieNodeODRc::new_empty())).into_val())
+                    }
                 }
             }
         }
@@ -1838,6 +1845,8 @@ impl<V: Clone + Send + Sync, A: Allocator> TrieNode<V, A> for LineListNode<V, A>
         0
     }
     fn node_set_branch(&mut self, key: &[u8], new_node: TrieNodeODRc<V, A>) -> Result<bool, TrieNodeODRc<V, A>> {
+        //Keys that continue past `key` within this node belong to the branch being replaced
+        self.node_remove_all_branches(key, false);
         self.set_payload_abstract::<true>(key, new_node.into())
             .map(|(_, created_subnode)| created_subnode)
     }
@@ -3234,6 +3243,55 @@ mod tests {
         assert_eq!(inner_node.as_tagged().node_get_val(b"anana"), Some(&1));
     }
 
+    /// Sets a value through a node whose only payload is a dangling empty child along the new key
+    #[test]
+    fn test_line_list_set_val_over_dangling_payload() {
+        let mut node = LineListNode::<u64, GlobalAlloc>::new_in(global_alloc());
+        node.node_set_val(b"wxyz", 0).unwrap_or_else(|_| panic!());
+        assert_eq!(node.node_remove_val(b"wxyz", false), Some(0));
+        assert!(node.is_used::<0>());
+
+        assert_eq!(node.node_set_val(b"wxyz2", 1).map_err(|_| 0), Ok((None, false)));
+        assert_eq!(node.node_get_val(b"wxyz2"), Some(&1));
+        assert_eq!(node.node_get_val(b"wxyz"), None);
+        assert!(!node.is_used::<1>());
+    }
+
+    /// Removes a value in slot_1 without pruning, when slot_0 holds a child at the same key
+    #[test]
+    fn test_line_list_remove_val_slot_1_with_child() {
+        let mut child = LineListNode::<u64, GlobalAlloc>::new_in(global_alloc());
+        child.node_set_val(b"p", 1).unwrap_or_else(|_| panic!());
+        let mut node = LineListNode::<u64, GlobalAlloc>::new_in(global_alloc());
+        node.node_set_branch(b":", TrieNodeODRc::new_in(child, global_alloc())).unwrap_or_else(|_| panic!());
+        node.node_set_val(b":", 0).unwrap_or_else(|_| panic!());
+        assert!(node.is_used_child_0());
+        assert!(node.is_used_value_1());
+
+        assert_eq!(node.node_remove_val(b":", false), Some(0));
+        assert!(!node.is_used::<1>());
+        assert_eq!(node.node_get_val(b":"), None);
+        let (bytes_used, child_node) = node.node_get_child(b":").unwrap();
+        assert_eq!(bytes_used, 1);
+        assert_eq!(child_node.as_tagged().node_get_val(b"p"), Some(&1));
+    }
+
+    /// Sets a branch at a key that is a prefix of a key already in the node
+    #[test]
+    fn test_line_list_set_branch_over_longer_key() {
+        let mut child = LineListNode::<u64, GlobalAlloc>::new_in(global_alloc());
+        child.node_set_val(b"z1", 2).unwrap_or_else(|_| panic!());
+        let mut node = LineListNode::<u64, GlobalAlloc>::new_in(global_alloc());
+        node.node_set_val(b"wxyz1", 0).unwrap_or_else(|_| panic!());
+
+        node.node_set_branch(b"wxy", TrieNodeODRc::new_in(child, global_alloc())).unwrap_or_else(|_| panic!());
+        assert_eq!(node.node_get_val(b"wxyz1"), None);
+        assert!(!node.is_used::<1>());
+        let (bytes_used, child_node) = node.node_get_child(b"wxy").unwrap();
+        assert_eq!(bytes_used, 3);
+        assert_eq!(child_node.as_tagged().node_get_val(b"z1"), Some(&2));
+    }
+
 }
 
 //GOAT, merge wrappers for lattice impls on primitives
@@ -3261,21 +3319,9 @@ mod tests {
 // Anyway, let me know your thoughts.
 
 
-//GOAT, steps to moving `val_count` method onto a new "TrieSummary" trait, and removing it from the ZipperMoving trait
-// (and remove the node_val_count method from the TrieNode trait)
-//
-//GOAT - Wait until TrieNode trait is re-jigged to put root values on the node and not the
-// parent, because there is bound to be a lot of edge-case logic to account for root values
-// under the current TrieNode trait contract.  I'd prefer to only write this code once.
-//
-// 1. implement a caching_cata inner loop that uses the TrieNode API instead of the zipper API
-//       a. This means it has no access to the origin_path, and the cata implementation needs
-//          to maintain its own path_buffer
-//       b. It means paths are relative to the node where it is called
-//       c. It borrows the zipper, rather than taking ownership.
-//       d. It works from the focus, rather than from a the root.
-//       e. It can then be implemented on more zipper types that don't support ReadOnlyValues
-//          and ZipperMoving
-// 2. implement a val_count convenience on top of 1.
+//GOAT, `val_count` now lives on the `TrieSummary` trait, but the `ReadZipperCore` and `WriteZipperCore`
+// impls still use the node_val_count method on the TrieNode trait as a fast path.  Once root values
+// live on the node, `TrieSummary::summarize` should get a TrieNode-level inner loop, and then
+// node_val_count can be removed.
 
 //GOAT, Paths in caching Cata:  https://github.com/Adam-Vandervorst/PathMap/pull/8#discussion_r2004828957
\ No newline at end of file
@@ -0,0 +1,404 @@
+use core::cell::{Cell, OnceCell, RefCell};
+use core::marker::PhantomData;
+
+use crate::utils::{ByteMask, TakableSlice};
+use crate::zipper::*;
+
+/// A [Zipper] type that wraps another `Zipper`, and presents the result of a mapping function in place of
+/// each of the source's values
+///
+/// The mapped values are created lazily, so the `MapZipper` can be passed to a
+/// [catamorphism](crate::morphisms::Catamorphism), [ArenaCompactTree::from_zipper](crate::arena_compact::ArenaCompactTree::from_zipper)
+/// or [serialize_paths_with_auxdata](crate::paths_serialization::serialize_paths_with_auxdata) without first
+/// building a mapped [PathMap](crate::PathMap).  Unlike [OverlayZipper::with_mapping], the mapping function
+/// returns an owned value, so it may compute a new value of any type.
+///
+/// The mapped values don't exist in the trie, so the `MapZipper` doesn't implement [ZipperReadOnlyValues].
+/// Instead, the values borrowed through [ZipperReadOnlyConditionalValues] are owned by the
+/// [`witness`](ZipperReadOnlyConditionalValues::witness), and they live as long as it does.
+///
+/// ```
+/// use pathmap::{PathMap, zipper::*};
+///
+/// let map: PathMap<u64> = [("one", 1), ("two", 2), ("three", 3)].into_iter().collect();
+/// let mut z = MapZipper::new(map.read_zipper(), |val: &u64| format!("#{val}"));
+///
+/// let mut contents = vec![];
+/// while z.to_next_val() {
+///     contents.push((z.path().to_vec(), z.val().unwrap().clone()));
+/// }
+/// assert_eq!(contents, vec![(b"one".to_vec(), "#1".to_string()), (b"three".to_vec(), "#3".to_string()), (b"two".to_vec(), "#2".to_string())]);
+/// ```
+pub struct MapZipper<V, U, Z, F> {
+    source: Z,
+    mapping: F,
+    /// The mapped value at the focus, computed the first time it's requested after each move
+    val_cache: OnceCell<Option<U>>,
+    _marker: PhantomData<V>,
+}
+
+/// A [`witness`](ZipperReadOnlyConditionalValues::witness) type used by [`MapZipper`]
+///
+/// In addition to the source zipper's witness, it owns the mapped values that have been borrowed with it.
+///
+/// The borrowed values must live as long as the witness, so the witness can't free any of them.  Borrowing
+/// the same source value again reuses the last mapped value, but every other borrow boxes a new value.  So
+/// a witness used to visit every value in a trie holds a mapped copy of each, and a short-lived witness
+/// should be preferred when the mapped values aren't needed together.
+pub struct MapZipperWitness<W, U> {
+    source_witness: W,
+    vals: RefCell<Vec<Box<U>>>,
+    /// The address of the source value that the last of `vals` was mapped from, or `0` if there is none
+    last_source: Cell<usize>,
+}
+
+impl<W, U> MapZipperWitness<W, U> {
+    /// Internal method to borrow the value mapped from `source_val` for the lifetime of the witness, calling
+    /// `mapping` only if it isn't the source value mapped last
+    fn keep<V>(&self, source_val: &V, mapping: impl FnOnce(&V) -> U) -> &U {
+        //Zero-sized values all share an address, so it doesn't identify them
+        let source_addr = if size_of::<V>() == 0 { 0 } else { source_val as *const V as usize };
+        let mut vals = self.vals.borrow_mut();
+        if source_addr == 0 || source_addr != self.last_source.get() {
+            vals.push(Box::new(mapping(source_val)));
+            self.last_source.set(source_addr);
+        }
+        let val_ptr: *const U = &**vals.last().unwrap();
+        //SAFETY: The boxed values are never dropped, moved out of their `Box`, or mutated before the witness
+        // is dropped.  Growing the `Vec` moves the `Box` pointers, but not the values they point to
+        unsafe{ &*val_ptr }
+    }
+}
+
+impl<V, U, Z, F> MapZipper<V, U, Z, F>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    /// Creates a new `MapZipper` wrapping the supplied `source` zipper, and presenting the result of
+    /// `mapping` for each value
+    pub fn new(source: Z, mapping: F) -> Self {
+        Self {
+            source,
+            mapping,
+            val_cache: OnceCell::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Consumes the `MapZipper`, and returns the source zipper
+    pub fn into_source(self) -> Z {
+        self.source
+    }
+
+    /// Internal method to discard the cached value, which must be called whenever the focus moves
+    #[inline]
+    fn moved(&mut self) {
+        self.val_cache.take();
+    }
+}
+
+impl<V, U, Z, F> ZipperConcrete for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperConcrete,
+{
+    /// The mapping only depends on the value, so the source's shared subtries are also shared in the `MapZipper`
+    #[inline]
+    fn shared_node_id(&self) -> Option<u64> { self.source.shared_node_id() }
+    #[inline]
+    fn is_shared(&self) -> bool { self.source.is_shared() }
+}
+
+impl<V, U, Z, F> ZipperValues<U> for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    fn val(&self) -> Option<&U> {
+        self.val_cache.get_or_init(|| self.source.val().map(&self.mapping)).as_ref()
+    }
+}
+
+impl<'a, V, U, Z, F> ZipperReadOnlyConditionalValues<'a, U> for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperReadOnlyConditionalValues<'a, V>,
+        F: Fn(&V) -> U,
+{
+    type WitnessT = MapZipperWitness<Z::WitnessT, U>;
+    fn witness<'w>(&self) -> Self::WitnessT {
+        MapZipperWitness {
+            source_witness: self.source.witness(),
+            vals: RefCell::new(vec![]),
+            last_source: Cell::new(0),
+        }
+    }
+    fn get_val_with_witness<'w>(&self, witness: &'w Self::WitnessT) -> Option<&'w U> where 'a: 'w {
+        let val = self.source.get_val_with_witness(&witness.source_witness)?;
+        Some(witness.keep(val, &self.mapping))
+    }
+}
+
+impl<V, U, Z, F> ZipperPathBuffer for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperPathBuffer + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    unsafe fn origin_path_assert_len(&self, len: usize) -> &[u8] { unsafe{ self.source.origin_path_assert_len(len) } }
+    fn prepare_buffers(&mut self) { self.source.prepare_buffers() }
+    fn reserve_buffers(&mut self, path_len: usize, stack_depth: usize) { self.source.reserve_buffers(path_len, stack_depth) }
+}
+
+impl<V, U, Z, F> Zipper for MapZipper<V, U, Z, F>
+    where
+        Z: Zipper + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    #[inline]
+    fn path_exists(&self) -> bool { self.source.path_exists() }
+    #[inline]
+    fn is_val(&self) -> bool { self.source.is_val() }
+    #[inline]
+    fn child_count(&self) -> usize { self.source.child_count() }
+    #[inline]
+    fn child_mask(&self) -> ByteMask { self.source.child_mask() }
+}
+
+impl<V, U, Z, F> ZipperMoving for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    fn at_root(&self) -> bool { self.source.at_root() }
+    fn reset(&mut self) {
+        self.moved();
+        self.source.reset()
+    }
+    #[inline]
+    fn path(&self) -> &[u8] { self.source.path() }
+    fn move_to_path<K: AsRef<[u8]>>(&mut self, path: K) -> usize {
+        self.moved();
+        self.source.move_to_path(path)
+    }
+    fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
+        self.moved();
+        self.source.descend_to(k)
+    }
+    fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize {
+        self.moved();
+        self.source.descend_to_existing(k)
+    }
+    fn descend_to_val<K: AsRef<[u8]>>(&mut self, k: K) -> usize {
+        self.moved();
+        self.source.descend_to_val(k)
+    }
+    fn descend_to_byte(&mut self, k: u8) {
+        self.moved();
+        self.source.descend_to_byte(k)
+    }
+    fn descend_indexed_byte(&mut self, idx: usize) -> bool {
+        self.moved();
+        self.source.descend_indexed_byte(idx)
+    }
+    fn descend_first_byte(&mut self) -> bool {
+        self.moved();
+        self.source.descend_first_byte()
+    }
+    fn descend_until(&mut self) -> bool {
+        self.moved();
+        self.source.descend_until()
+    }
+    fn ascend(&mut self, steps: usize) -> bool {
+        self.moved();
+        self.source.ascend(steps)
+    }
+    fn ascend_byte(&mut self) -> bool {
+        self.moved();
+        self.source.ascend_byte()
+    }
+    fn ascend_until(&mut self) -> bool {
+        self.moved();
+        self.source.ascend_until()
+    }
+    fn ascend_until_branch(&mut self) -> bool {
+        self.moved();
+        self.source.ascend_until_branch()
+    }
+    fn to_next_sibling_byte(&mut self) -> bool {
+        self.moved();
+        self.source.to_next_sibling_byte()
+    }
+    fn to_prev_sibling_byte(&mut self) -> bool {
+        self.moved();
+        self.source.to_prev_sibling_byte()
+    }
+}
+
+impl<V, U, Z, F> ZipperIteration for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperIteration + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    fn to_next_val(&mut self) -> bool {
+        self.moved();
+        self.source.to_next_val()
+    }
+    fn descend_first_k_path(&mut self, k: usize) -> bool {
+        self.moved();
+        self.source.descend_first_k_path(k)
+    }
+    fn to_next_k_path(&mut self, k: usize) -> bool {
+        self.moved();
+        self.source.to_next_k_path(k)
+    }
+}
+
+impl<'a, V, U, Z, F> ZipperReadOnlyConditionalIteration<'a, U> for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperIteration + ZipperReadOnlyConditionalValues<'a, V>,
+        F: Fn(&V) -> U,
+{ }
+
+impl<V, U, Z, F> ZipperAbsolutePath for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperAbsolutePath + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    fn origin_path(&self) -> &[u8] { self.source.origin_path() }
+    fn root_prefix_path(&self) -> &[u8] { self.source.root_prefix_path() }
+}
+
+impl<V, U, Z, F> ZipperForking<U> for MapZipper<V, U, Z, F>
+    where
+        Z: ZipperForking<V> + ZipperValues<V>,
+        F: Fn(&V) -> U,
+{
+    type ReadZipperT<'a> = MapZipper<V, U, Z::ReadZipperT<'a>, &'a F> where Self: 'a;
+    fn fork_read_zipper<'a>(&'a self) -> Self::ReadZipperT<'a> {
+        MapZipper::new(self.source.fork_read_zipper(), &self.mapping)
+    }
+}
+
+impl<V, U, Z, F> TrieSummary<U> for MapZipper<V, U, Z, F>
+    where
+        Z: TrieSummary<V>,
+        F: Fn(&V) -> U,
+{
+    /// Summarizes the source's subtrie, so the source may re-use the summaries of shared subtries
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&U>, &[u8]) -> Result<W, E>
+    {
+        self.source.summarize_fallible(|mask, children, val, sub_path| {
+            alg_f(mask, children, val.map(&self.mapping).as_ref(), sub_path)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MapZipper;
+    use crate::alloc::GlobalAlloc;
+    use crate::morphisms::Catamorphism;
+    use crate::trie_map::PathMap;
+    use crate::utils::TakableSlice;
+    use crate::zipper::*;
+
+    #[test]
+    fn map_zipper_test1() {
+        let map: PathMap<u64> = (0u64..200).map(|i| (format!("{}:{}", i % 9, i), i)).collect();
+        let expected: PathMap<String> = map.iter().map(|(k, v)| (k, format!("{}", v * 2))).collect();
+
+        //Movement and values match a map of the mapped values
+        let mut z = MapZipper::new(map.read_zipper(), |val: &u64| format!("{}", val * 2));
+        let mut expected_z = expected.read_zipper();
+        while z.to_next_step() {
+            assert!(expected_z.to_next_step());
+            assert_eq!(z.path(), expected_z.path());
+            assert_eq!(z.val(), expected_z.val());
+            assert_eq!(z.child_mask(), expected_z.child_mask());
+        }
+        assert!(!expected_z.to_next_step());
+
+        //Values borrowed through the witness outlive the zipper's movement
+        let mut z = MapZipper::new(map.read_zipper(), |val: &u64| format!("{}", val * 2));
+        let witness = z.witness();
+        let mut vals = vec![];
+        while let Some(val) = z.to_next_get_val_with_witness(&witness) {
+            vals.push(val);
+        }
+        assert_eq!(vals, expected.iter().map(|(_k, v)| v).collect::<Vec<_>>());
+        assert_eq!(witness.vals.borrow().len(), 200);
+
+        //Borrowing the same value again reuses the mapped value
+        let witness = z.witness();
+        z.reset();
+        z.descend_to(b"3:12");
+        let first = z.get_val_with_witness(&witness).unwrap();
+        let second = z.get_val_with_witness(&witness).unwrap();
+        assert!(core::ptr::eq(first, second));
+        assert_eq!(witness.vals.borrow().len(), 1);
+
+        //Catamorphisms and summaries see the mapped values
+        let z = MapZipper::new(map.read_zipper_at_path(b"3:"), |val: &u64| val % 4 == 1);
+        assert_eq!(z.summarize(|_mask, children: &mut TakableSlice<usize>, val, _sub_path| {
+            children.iter().sum::<usize>() + (val == Some(&true)) as usize
+        }), (0u64..200).filter(|i| i % 9 == 3 && i % 4 == 1).count());
+        let total = z.into_cata_jumping_side_effect(|_mask, children: &mut TakableSlice<u64>, _jump, val, _path| {
+            children.iter().sum::<u64>() + val.map_or(0, |is_multiple| *is_multiple as u64)
+        });
+        assert_eq!(total, (0u64..200).filter(|i| i % 9 == 3 && i % 4 == 1).count() as u64);
+    }
+
+    #[cfg(feature = "arena_compact")]
+    #[test]
+    fn map_zipper_act_test() {
+        use crate::arena_compact::ArenaCompactTree;
+
+        let map: PathMap<u64> = (0u64..100).map(|i| (format!("{i}"), i)).collect();
+        let z = MapZipper::new(map.read_zipper(), |val: &u64| val + 1000);
+        let act = ArenaCompactTree::from_zipper(z, |val: &u64| *val);
+        let mut act_z = act.read_zipper_u64();
+        let mut contents = vec![];
+        while act_z.to_next_val() {
+            contents.push((act_z.path().to_vec(), *act_z.get_val().unwrap()));
+        }
+        assert_eq!(contents, map.iter().map(|(k, v)| (k, v + 1000)).collect::<Vec<_>>());
+    }
+
+    #[cfg(feature = "serialization")]
+    #[test]
+    fn map_zipper_serialize_test() {
+        use crate::paths_serialization::serialize_paths_with_auxdata;
+
+        let map: PathMap<u64> = (0u64..100).map(|i| (format!("{i}"), i)).collect();
+        let mut mapped_vals = vec![];
+        let mut buf = vec![];
+        serialize_paths_with_auxdata(MapZipper::new(map.read_zipper(), |val: &u64| val * 3), &mut buf, |k, _path, val| {
+            mapped_vals.push((k, *val));
+        }).unwrap();
+        assert_eq!(mapped_vals, map.iter().enumerate().map(|(k, (_path, val))| (k, val * 3)).collect::<Vec<_>>());
+    }
+
+    type Mapping = fn(&()) -> ();
+    type MZ<'a, V, A=GlobalAlloc> = MapZipper<V, V, ReadZipperUntracked<'a, 'static, V, A>, Mapping>;
+    fn unit_mapping(_val: &()) {}
+
+    zipper_moving_tests::zipper_moving_tests!(map_zipper,
+        |keys: &[&[u8]]| {
+            keys.iter().map(|k| (k, ())).collect::<PathMap<()>>()
+        },
+        |trie: &mut PathMap<()>, path: &[u8]| -> MZ<'_, ()> {
+            MapZipper::new(trie.read_zipper_at_path(path), unit_mapping as Mapping)
+        }
+    );
+
+    zipper_iteration_tests::zipper_iteration_tests!(map_zipper,
+        |keys: &[&[u8]]| {
+            keys.iter().map(|k| (k, ())).collect::<PathMap<()>>()
+        },
+        |trie: &mut PathMap<()>, path: &[u8]| -> MZ<'_, ()> {
+            MapZipper::new(trie.read_zipper_at_path(path), unit_mapping as Mapping)
+        }
+    );
+}
@@ -0,0 +1,245 @@
+use crate::ring::{AlgebraicResult, MergePolicy, MeetPolicy, SELF_IDENT};
+use crate::utils::{BitMask, ByteMask};
+use crate::virtual_zipper::{ascend_virtual, SyncedSources};
+use crate::zipper::*;
+
+/// The value at the focus of a [MeetZipper] or [SubtractZipper](crate::zipper::SubtractZipper), which
+/// is either borrowed from one of the sources, or created by the [MergePolicy]
+pub(crate) enum VirtualVal<V> {
+    None,
+    Source(usize),
+    Owned(V),
+}
+
+/// Zipper that traverses a virtual trie formed by the intersection of the tries of any number of source zippers
+///
+/// The zipper only descends into the bytes present in the [child_mask](Zipper::child_mask) of every source,
+/// and a value exists where every source has a value and the [MergePolicy] doesn't annihilate them.  The
+/// merged value is computed the first time it is requested at each focus, so nothing is allocated unless
+/// the policy creates a new value.
+///
+/// Unlike [PathMap::meet](crate::PathMap::meet), the view doesn't prune paths whose values all
+/// annihilate, so paths that lead to no values may still be visible when moving the zipper.  The
+/// [ZipperIteration] methods will never stop on those paths, however.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::zipper::*;
+///
+/// let a: PathMap<()> = ["apple", "banana", "cherry"].into_iter().map(|k| (k, ())).collect();
+/// let b: PathMap<()> = ["banana", "cherry", "durian"].into_iter().map(|k| (k, ())).collect();
+///
+/// let mut z = MeetZipper::new([a.read_zipper(), b.read_zipper()]);
+/// let mut contents = vec![];
+/// while z.to_next_val() {
+///     contents.push(z.path().to_vec());
+/// }
+/// assert_eq!(contents, vec![b"banana".to_vec(), b"cherry".to_vec()]);
+/// ```
+pub struct MeetZipper<V, Z, P> {
+    sources: SyncedSources<Vec<Z>, VirtualVal<V>>,
+    policy: P,
+}
+
+impl<V, Z> MeetZipper<V, Z, MeetPolicy>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        MeetPolicy: MergePolicy<V>,
+{
+    /// Creates a new `MeetZipper` over `sources`, which meets the values with their [Lattice](crate::ring::Lattice)
+    /// implementation
+    ///
+    /// Panics if `sources` is empty.
+    pub fn new<Sources: IntoIterator<Item=Z>>(sources: Sources) -> Self {
+        Self::with_policy(sources, MeetPolicy)
+    }
+}
+
+impl<V, Z, P> MeetZipper<V, Z, P>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    /// Creates a new `MeetZipper` over `sources`, using `policy` to combine the values
+    ///
+    /// The values are combined from left to right, so the policy's `self_val` is the result of combining
+    /// the values of the earlier sources.  Each source zipper is reset to its root.
+    ///
+    /// Panics if `sources` is empty.
+    pub fn with_policy<Sources: IntoIterator<Item=Z>>(sources: Sources, policy: P) -> Self {
+        let sources: Vec<Z> = sources.into_iter().collect();
+        assert!(!sources.is_empty(), "MeetZipper requires at least one source");
+        Self {
+            sources: SyncedSources::new(sources),
+            policy,
+        }
+    }
+
+    /// Returns a reference to the source zipper at `source_idx`
+    pub fn source(&self, source_idx: usize) -> &Z {
+        &self.sources.sources()[source_idx]
+    }
+
+    /// Consumes the `MeetZipper`, and returns the source zippers
+    pub fn into_sources(self) -> Vec<Z> {
+        self.sources.into_sources()
+    }
+
+    /// Internal method to fold the values of all `sources` with the policy
+    fn meet_vals(&self, sources: &[Z]) -> VirtualVal<V> {
+        let mut result = VirtualVal::Source(0);
+        if sources[0].val().is_none() {
+            return VirtualVal::None
+        }
+        for (idx, source) in sources.iter().enumerate().skip(1) {
+            let Some(val) = source.val() else {
+                return VirtualVal::None
+            };
+            let acc = match &result {
+                VirtualVal::Source(acc_idx) => sources[*acc_idx].val().unwrap(),
+                VirtualVal::Owned(acc) => acc,
+                VirtualVal::None => unreachable!(),
+            };
+            match self.policy.merge(acc, val) {
+                AlgebraicResult::None => return VirtualVal::None,
+                AlgebraicResult::Identity(mask) => {
+                    if mask & SELF_IDENT == 0 {
+                        result = VirtualVal::Source(idx);
+                    }
+                },
+                AlgebraicResult::Element(new_val) => result = VirtualVal::Owned(new_val),
+            }
+        }
+        result
+    }
+}
+
+impl<V, Z, P> ZipperValues<V> for MeetZipper<V, Z, P>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn val(&self) -> Option<&V> {
+        match self.sources.cached(|sources| self.meet_vals(sources)) {
+            VirtualVal::None => None,
+            VirtualVal::Source(idx) => self.source(*idx).val(),
+            VirtualVal::Owned(val) => Some(val),
+        }
+    }
+}
+
+impl<V, Z, P> Zipper for MeetZipper<V, Z, P>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn path_exists(&self) -> bool {
+        self.sources.sources().iter().all(|source| source.path_exists())
+    }
+    fn is_val(&self) -> bool {
+        self.val().is_some()
+    }
+    fn child_count(&self) -> usize {
+        self.child_mask().count_bits()
+    }
+    fn child_mask(&self) -> ByteMask {
+        let mut sources = self.sources.sources().iter();
+        let first = sources.next().unwrap().child_mask();
+        sources.fold(first, |mask, source| mask & source.child_mask())
+    }
+}
+
+impl<V, Z, P> ZipperMoving for MeetZipper<V, Z, P>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn at_root(&self) -> bool {
+        self.sources.at_root()
+    }
+
+    fn reset(&mut self) {
+        self.sources.reset()
+    }
+
+    fn path(&self) -> &[u8] {
+        self.sources.path()
+    }
+
+    fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
+        self.sources.descend_to(k.as_ref())
+    }
+
+    fn descend_to_byte(&mut self, k: u8) {
+        self.sources.descend_to_byte(k)
+    }
+
+    fn ascend(&mut self, steps: usize) -> bool {
+        self.sources.ascend(steps)
+    }
+
+    fn ascend_until(&mut self) -> bool {
+        ascend_virtual(self, false, |z, branch_only| z.sources.jump_up(branch_only))
+    }
+
+    fn ascend_until_branch(&mut self) -> bool {
+        ascend_virtual(self, true, |z, branch_only| z.sources.jump_up(branch_only))
+    }
+}
+
+impl<V, Z, P> ZipperIteration for MeetZipper<V, Z, P>
+    where
+        Z: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{ }
+
+#[cfg(test)]
+mod tests {
+    use crate::PathMap;
+    use crate::alloc::GlobalAlloc;
+    use crate::ring::{AlgebraicResult, MeetPolicy};
+    use crate::zipper::*;
+
+    #[test]
+    fn meet_zipper_test1() {
+        let a: PathMap<u64> = [("a", 1), ("b", 2), ("c", 3)].into_iter().collect();
+        let b: PathMap<u64> = [("a", 10), ("b", 2), ("c", 30)].into_iter().collect();
+
+        //Keep the smaller of the two values, and hide values that are equal
+        let mut z = MeetZipper::with_policy([a.read_zipper(), b.read_zipper()], |a: &u64, b: &u64| {
+            if a == b { AlgebraicResult::None } else { AlgebraicResult::Element(*a.min(b)) }
+        });
+        let mut contents = vec![];
+        while z.to_next_val() {
+            contents.push((z.path().to_vec(), *z.val().unwrap()));
+        }
+        assert_eq!(contents, vec![(b"a".to_vec(), 1), (b"c".to_vec(), 3)]);
+    }
+
+    type MZ<'a, V, A=GlobalAlloc> = MeetZipper<V, ReadZipperUntracked<'a, 'static, V, A>, MeetPolicy>;
+
+    /// Creates three sources containing all the keys, each extended with its own paths, which aren't in the meet
+    fn overlapping_keys(keys: &[&[u8]]) -> Vec<PathMap<()>> {
+        (0..3u8).map(|source_idx| {
+            let mut trie: PathMap<()> = keys.iter().map(|key| (key, ())).collect();
+            for key in keys {
+                trie.set_val_at([key, &[0xF0 + source_idx][..]].concat(), ());
+            }
+            trie
+        }).collect()
+    }
+
+    zipper_moving_tests::zipper_moving_tests!(meet_zipper,
+        overlapping_keys,
+        |tries: &mut Vec<PathMap<()>>, path: &[u8]| -> MZ<'_, ()> {
+            MeetZipper::new(tries.iter().map(|trie| trie.read_zipper_at_path(path)))
+        }
+    );
+
+    zipper_iteration_tests::zipper_iteration_tests!(meet_zipper,
+        overlapping_keys,
+        |tries: &mut Vec<PathMap<()>>, path: &[u8]| -> MZ<'_, ()> {
+            MeetZipper::new(tries.iter().map(|trie| trie.read_zipper_at_path(path)))
+        }
+    );
+}
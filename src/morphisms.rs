@@ -28,11 +28,31 @@
 //! branches below, while the `cata` methods visit the deepest values first, before
 //! returning to higher levels to aggregate information from deeper in the trie.
 //!
+//! The `_pruned` flavors of the side-effect catamorphisms additionally take a `pre_f` closure that is consulted
+//! before a branch is visited.  Returning [PreVisit::Skip](crate::morphisms::PreVisit::Skip) from `pre_f` supplies
+//! the result for the whole branch, so the traversal never descends into it.
+//!
+//! ### Paramorphism
+//!
+//! A catamorphism that additionally provides the `alg` closure with the original subtrie below each
+//! downstream branch.  This is useful when a result needs to incorporate unmodified parts of the source
+//! trie, for example by grafting them.
+//!
+//! ### Trie Summary
+//!
+//! A cached, jumping catamorphism that borrows a zipper instead of consuming it, and summarizes the subtrie
+//! below the zipper's focus.  Paths passed to the `alg` are relative to the focus.  See [TrieSummary](crate::morphisms::TrieSummary).
+//!
 //! ### Anamorphism
 //!
 //! Generate a trie from the root.  Conceptually it is the inverse of catamorphism.  This algorithm proceeds
 //! from a starting point corresponding to a root of a trie, and generates branches and leaves recursively.
 //!
+//! ### Hylomorphism
+//!
+//! An anamorphism followed by a catamorphism, fused together so the intermediate trie is never
+//! materialized.  See [hylo](crate::morphisms::hylo).
+//!
 //! ### Jumping vs Stepping Morphisms
 //!
 //! Ordinary (aka "stepping") morphisms are guaranteed to evaluate the alg once for each individual path byte
@@ -65,13 +85,30 @@
 //!
 //! However, the `side_effect` methods are useful in the implementation of things like serialization, etc.
 //!
+//! ### Children passed to the `alg`
+//!
+//! The results of the downstream branches are passed to the `alg` as a `children: &mut TakableSlice<W>`, which
+//! lets the `alg` move a child's `W` out with [take](crate::utils::TakableSlice::take) without requiring
+//! `W: Default`.  Earlier versions passed `children: &mut [W]`, so this is a breaking change for closures that
+//! annotate the argument's type.  An `alg` that only reads the children can just change the annotation, because
+//! `TakableSlice<W>` implements `Deref<Target=[W]>` and so `children.iter()`, `children.len()` and `children[i]`
+//! behave as before.  An `alg` that used `core::mem::take(&mut children[i])` should call `children.take(i)`
+//! instead, after which the whole slice can no longer be dereferenced.
+//!
+//! ### Incremental Iteration
+//!
+//! The results of a cached catamorphism can be kept in a [CataCache](crate::morphisms::CataCache) and reused the next time the catamorphism
+//! is run, using the methods of [IncrementalCatamorphism](crate::morphisms::IncrementalCatamorphism).  After a trie is modified, only the nodes along
+//! the modified paths need to be recomputed.
+//!
 use core::convert::Infallible;
 use reusing_vec::ReusingQueue;
 
 use crate::utils::*;
-use crate::alloc::Allocator;
+use crate::alloc::{global_alloc, Allocator, GlobalAlloc};
 use crate::PathMap;
 use crate::trie_node::TrieNodeODRc;
+use crate::dense_byte_node::DenseByteNode;
 use crate::zipper;
 use crate::zipper::*;
 
@@ -83,13 +120,13 @@ pub trait Catamorphism<V> {
     /// step (at every byte)
     ///
     /// ## Arguments to `alg_f`:
-    /// `(child_mask: &`[`ByteMask`]`, children: &mut [W], value: Option<&V>, path: &[u8]`
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, value: Option<&V>, path: &[u8]`
     ///
     /// - `child_mask`: A [`ByteMask`] indicating the corresponding byte for each downstream branche in
     /// `children`.
     ///
     /// - `children`: A slice containing all the `W` values from previous invocations of `alg_f` for
-    /// downstream branches.
+    /// downstream branches.  Individual `W` values may be moved out with [`TakableSlice::take`].
     ///
     /// - `value`: A value associated with a given path in the trie, or `None` if the trie has no value at
     /// that path.
@@ -102,7 +139,7 @@ pub trait Catamorphism<V> {
     /// The focus position of the zipper will be ignored and it will be immediately reset to the root.
     fn into_cata_side_effect<W, AlgF>(self, mut alg_f: AlgF) -> W
         where
-        AlgF: FnMut(&ByteMask, &mut [W], Option<&V>, &[u8]) -> W,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
         Self: Sized
     {
         self.into_cata_side_effect_fallible(|mask, children, val, path| -> Result<W, Infallible> {
@@ -114,7 +151,7 @@ pub trait Catamorphism<V> {
     ///
     /// See [Catamorphism::into_cata_side_effect]
     fn into_cata_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, Err>;
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>;
 
     /// Applies a "jumping" catamorphism to the trie
     ///
@@ -122,7 +159,7 @@ pub trait Catamorphism<V> {
     /// path bytes in the trie where there isn't either a `value` or a branch where `children.len() > 1`.
     ///
     /// ## Arguments to `alg_f`:
-    /// `(child_mask: &`[`ByteMask`]`, children: &mut [W], jumped_byte_cnt: usize, value: Option<&V>, path: &[u8]`
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, jumped_byte_cnt: usize, value: Option<&V>, path: &[u8]`
     ///
     /// - `jumped_byte_cnt`: The number of bytes before the `alg_f` will be called again.  The "jumped" substring
     /// is equal to `path[path.len()-jumped_byte_cnt..]`
@@ -131,7 +168,7 @@ pub trait Catamorphism<V> {
     /// behavior
     fn into_cata_jumping_side_effect<W, AlgF>(self, mut alg_f: AlgF) -> W
         where
-        AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> W,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
         Self: Sized
     {
         self.into_cata_jumping_side_effect_fallible(|mask, children, jumped_cnt, val, path| -> Result<W, Infallible> {
@@ -143,7 +180,66 @@ pub trait Catamorphism<V> {
     ///
     /// See [Catamorphism::into_cata_jumping_side_effect]
     fn into_cata_jumping_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, Err>;
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>;
+
+    /// Applies a "stepping" catamorphism that may prune branches of the trie before they are visited
+    ///
+    /// Before the traversal enters a path, `pre_f` is called with the [`origin_path`](ZipperAbsolutePath::origin_path)
+    /// of that position.  If `pre_f` returns [`PreVisit::Skip`], the subtrie below that path is not visited,
+    /// and the supplied `W` is used in place of the result `alg_f` would have produced for that path.  If
+    /// `pre_f` returns [`PreVisit::Descend`], the traversal continues normally.
+    ///
+    /// `pre_f` is called exactly once for each path that is reached, including the root, and always before
+    /// `alg_f` is called for the same path.
+    ///
+    /// See [into_cata_side_effect](Catamorphism::into_cata_side_effect) for explanation of the `alg_f`
+    /// arguments and behavior
+    fn into_cata_pruned_side_effect<W, PreF, AlgF>(self, pre_f: PreF, mut alg_f: AlgF) -> W
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_cata_pruned_side_effect_fallible(pre_f, |mask, children, val, path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, val, path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Catamorphism::into_cata_pruned_side_effect]
+    fn into_cata_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>;
+
+    /// Applies a "jumping" catamorphism that may prune branches of the trie before they are visited
+    ///
+    /// `pre_f` is called at the root, and at the start of each branch, immediately after the branching
+    /// byte and before any jump.  If `pre_f` returns [`PreVisit::Skip`], the entire branch is skipped, and
+    /// the supplied `W` takes the place of the result `alg_f` would have produced for the branch.
+    ///
+    /// See [into_cata_jumping_side_effect](Catamorphism::into_cata_jumping_side_effect) and
+    /// [into_cata_pruned_side_effect](Catamorphism::into_cata_pruned_side_effect) for explanation of the other
+    /// arguments and behavior
+    fn into_cata_jumping_pruned_side_effect<W, PreF, AlgF>(self, pre_f: PreF, mut alg_f: AlgF) -> W
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_cata_jumping_pruned_side_effect_fallible(pre_f, |mask, children, jumped_cnt, val, path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, jumped_cnt, val, path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Catamorphism::into_cata_jumping_pruned_side_effect]
+    fn into_cata_jumping_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>;
 
     /// Applies a **cached**, **stepping**, catamorphism to the trie descending from the zipper's
     /// root, running the `alg_f` at every step (at every byte)
@@ -152,13 +248,13 @@ pub trait Catamorphism<V> {
     /// been previously computed.
     ///
     /// ## Arguments to `alg_f`:
-    /// `(child_mask: &`[`ByteMask`]`, children: &mut [W], val: Option<&V>`
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, val: Option<&V>`
     ///
     /// - `child_mask`: A [`ByteMask`] indicating the corresponding byte for each downstream branche in
     /// `children`.
     ///
     /// - `children`: A slice containing all the `W` values from previous invocations of `alg_f` for
-    /// downstream branches.
+    /// downstream branches.  Individual `W` values may be moved out with [`TakableSlice::take`].
     ///
     /// - `value`: A value associated with a given path in the trie, or `None` if the trie has no value at
     /// that path.
@@ -169,7 +265,7 @@ pub trait Catamorphism<V> {
     fn into_cata_cached<W, AlgF>(self, alg_f: AlgF) -> W
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>) -> W,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> W,
         Self: Sized
     {
         self.into_cata_cached_fallible(|mask, children, val| -> Result<W, Infallible> {
@@ -183,7 +279,7 @@ pub trait Catamorphism<V> {
     fn into_cata_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>) -> Result<W, E>;
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>;
 
     /// Applies a "jumping" catamorphism to the trie
     ///
@@ -194,7 +290,7 @@ pub trait Catamorphism<V> {
     /// been previously computed.
     ///
     /// ## Arguments to `alg_f`:
-    /// `(child_mask: &`[`ByteMask`]`, children: &mut [W], value: Option<&V>, sub_path: &[u8]`
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, value: Option<&V>, sub_path: &[u8]`
     ///
     /// - `sub_path`: A slice of path bytes for which the `alf_f` will not be called.  Consider the
     /// trie below:
@@ -214,7 +310,7 @@ pub trait Catamorphism<V> {
     fn into_cata_jumping_cached<W, AlgF>(self, alg_f: AlgF) -> W
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8]) -> W,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
         Self: Sized
     {
         self.into_cata_jumping_cached_fallible(|mask, children, val, sub_path| -> Result<W, Infallible> {
@@ -228,25 +324,126 @@ pub trait Catamorphism<V> {
     fn into_cata_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, E>;
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>;
+}
+
+/// The result of the `pre_f` closure passed to a pruned catamorphism, e.g. [Catamorphism::into_cata_pruned_side_effect]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PreVisit<W> {
+    /// Visit the subtrie below the path, and compute its `W` with the `alg_f`
+    Descend,
+    /// Don't visit the subtrie below the path, and use the contained `W` in place of its result
+    Skip(W),
+}
+
+/// Provides methods to summarize the subtrie below a zipper's focus, without moving or consuming the zipper
+///
+/// The summary is a cached, jumping catamorphism, with the same call pattern as
+/// [into_cata_jumping_cached](Catamorphism::into_cata_jumping_cached), except that the zipper's focus is
+/// treated as the root of the trie.  Therefore the `sub_path` arguments are relative to the focus, and the
+/// value at the focus is passed to the final invocation of the `alg_f`.
+///
+/// Zippers that have access to the underlying trie nodes will re-use the `W` computed for shared subtries.
+/// Virtual zippers (e.g. [OverlayZipper]) may need to visit the entire subtrie.
+pub trait TrieSummary<V> {
+    /// Summarizes the subtrie below the focus, running `alg_f` at each value and branch
+    ///
+    /// ## Arguments to `alg_f`:
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, value: Option<&V>, sub_path: &[u8]`
+    ///
+    /// See [into_cata_jumping_cached](Catamorphism::into_cata_jumping_cached) for an explanation of the
+    /// arguments.
+    fn summarize<W, AlgF>(&self, alg_f: AlgF) -> W
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
+    {
+        self.summarize_fallible(|mask, children, val, sub_path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, val, sub_path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [TrieSummary::summarize]
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>;
+
+    /// Returns the total number of values contained at and below the zipper's focus, including the focus itself
+    ///
+    /// WARNING: This is not a cheap method. It may have an order-N cost
+    fn val_count(&self) -> usize {
+        self.summarize(|_mask, children: &mut TakableSlice<usize>, val, _sub_path| {
+            children.iter().sum::<usize>() + val.is_some() as usize
+        })
+    }
 }
 
-//TODO GOAT!!: It would be nice to get rid of this Default bound on all morphism Ws.  In this case, the plan
-// for doing that would be to create a new type called a TakableSlice.  It would be able to deref
-// into a regular mutable slice of `T` so it would work just like an ordinary slice.  Additionally
-// there would be special `take(idx: usize)` methods. it would have an additional bitmask to keep
-// track of which elements have already been taken.  So each element would be backed by a MaybeUninit,
-// and taking an element twice would be a panic.  There would be an additional `try_take` method to
-// avoid the panic.
-//Additionally, if `T: Default`, the `take` method would become the ordinary mem::take, and would always
-// succeed, therefore the abstraction would have minimal cost
-//Creating a new TakableSlice would be very cheap as it could be transmuted from an existing slice of T.
-// The tricky part is making sure Drop does the right thing, dropping the elements that were taken, and
-// not double-dropping the ones that weren't.  For this reason, I think the right solution would be to
-// require a `TakableSlice` be borrowed from a `TakableVec`, and then making sure the `TakableVec`
-// methods like `clear` do the right thing.
-//When all this work is done, this object probably deserves a stand-alone crate.
+/// Internal function to summarize the subtrie below a zipper's focus by moving the zipper, for the
+/// [TrieSummary] impls on virtual zippers
+///
+/// `root_val` is passed in place of the zipper's value at the starting focus.  The zipper is returned to
+/// the starting focus before this function returns successfully.
+pub(crate) fn summarize_from_focus<Z, V, W, E, AlgF>(z: &mut Z, root_val: Option<&V>, alg_f: AlgF) -> Result<W, E>
+    where
+    Z: Zipper + ZipperMoving + ZipperValues<V>,
+    AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+{
+    struct Frame {
+        child_mask: ByteMask,
+        child_cnt: usize,
+        child_idx: usize,
+        child_start: usize,
+        path_len: usize,
+        sub_path_start: usize,
+    }
+
+    let root_len = z.path().len();
+    let root_mask = z.child_mask();
+    let root_cnt = root_mask.count_bits();
+    //The root is skipped if it's not a fork or a value, so the first jump starts at the root
+    let skip_root = root_cnt == 1 && root_val.is_none();
+    let mut stack = vec![Frame { child_mask: root_mask, child_cnt: root_cnt, child_idx: 0, child_start: 0, path_len: root_len, sub_path_start: root_len }];
+    let mut children = TakableVec::<W>::new();
+    loop {
+        let at_root = stack.len() == 1;
+        let frame = stack.last_mut().unwrap();
+        if frame.child_idx < frame.child_cnt {
+            let sub_path_start = if skip_root && at_root { root_len } else { frame.path_len + 1 };
+            z.descend_indexed_byte(frame.child_idx);
+            frame.child_idx += 1;
+            //Jump to the next fork, value, or leaf
+            while z.child_count() == 1 && !z.is_val() {
+                z.descend_first_byte();
+            }
+            let child_mask = z.child_mask();
+            stack.push(Frame {
+                child_cnt: child_mask.count_bits(),
+                child_mask,
+                child_idx: 0,
+                child_start: children.len(),
+                path_len: z.path().len(),
+                sub_path_start,
+            });
+            continue;
+        }
 
+        let frame = stack.pop().unwrap();
+        if stack.is_empty() {
+            return if skip_root {
+                Ok(children.pop().unwrap())
+            } else {
+                alg_f(&frame.child_mask, &mut children.takable_slice(..), root_val, &[])
+            }
+        }
+        let w = alg_f(&frame.child_mask, &mut children.takable_slice(frame.child_start..), z.val(), &z.path()[frame.sub_path_start..])?;
+        children.truncate(frame.child_start);
+        z.ascend(frame.path_len - stack.last().unwrap().path_len);
+        children.push(w);
+    }
+}
 
 /// A compatibility shim to provide a 3-function catamorphism API
 ///
@@ -264,7 +461,7 @@ pub struct SplitCata;
 
 #[allow(deprecated)]
 impl SplitCata {
-    pub fn new<'a, V, W, MapF, CollapseF, AlgF>(mut map_f: MapF, mut collapse_f: CollapseF, alg_f: AlgF) -> impl FnMut(&ByteMask, &mut [W], Option<&V>, &[u8]) -> W + 'a
+    pub fn new<'a, V, W, MapF, CollapseF, AlgF>(mut map_f: MapF, mut collapse_f: CollapseF, alg_f: AlgF) -> impl FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W + 'a
         where
         MapF: FnMut(&V, &[u8]) -> W + 'a,
         CollapseF: FnMut(&V, W, &[u8]) -> W + 'a,
@@ -302,9 +499,8 @@ pub struct SplitCataJumping;
 
 #[allow(deprecated)]
 impl SplitCataJumping {
-    pub fn new<'a, V, W, MapF, CollapseF, AlgF, JumpF>(mut map_f: MapF, mut collapse_f: CollapseF, mut alg_f: AlgF, mut jump_f: JumpF) -> impl FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> W + 'a
+    pub fn new<'a, V, W, MapF, CollapseF, AlgF, JumpF>(mut map_f: MapF, mut collapse_f: CollapseF, mut alg_f: AlgF, mut jump_f: JumpF) -> impl FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W + 'a
         where
-        W: Default,
         MapF: FnMut(&V, &[u8]) -> W + 'a,
         CollapseF: FnMut(&V, W, &[u8]) -> W + 'a,
         AlgF: FnMut(&ByteMask, &mut [W], &[u8]) -> W + 'a,
@@ -325,7 +521,7 @@ impl SplitCataJumping {
                 let w = if children.len() > 1 {
                     alg_f(mask, children, path)
                 } else {
-                    core::mem::take(&mut children[0])
+                    children.take(0)
                 };
                 match val {
                     Some(val) => collapse_f(val, w, path),
@@ -355,24 +551,26 @@ impl SplitCataJumping {
 
 impl<'a, Z, V: 'a> Catamorphism<V> for Z where Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperConcrete + ZipperAbsolutePath + ZipperPathBuffer {
     fn into_cata_side_effect_fallible<W, Err, AlgF>(self, mut alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, Err>,
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>,
     {
-        cata_side_effect_body::<Self, V, W, Err, _, false>(self, |mask, children, jump_len, val, path| {
+        cata_side_effect_body::<Self, V, W, Err, _, false>(self, |_z, mask, children, jump_len, val, path| {
             debug_assert!(jump_len == 0);
             alg_f(mask, children, val, path)
         })
     }
-    fn into_cata_jumping_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, Err>
+    fn into_cata_jumping_side_effect_fallible<W, Err, AlgF>(self, mut alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
     {
-        cata_side_effect_body::<Self, V, W, Err, AlgF, true>(self, alg_f)
+        cata_side_effect_body::<Self, V, W, Err, _, true>(self, |_z, mask, children, jump_len, val, path| {
+            alg_f(mask, children, jump_len, val, path)
+        })
     }
     fn into_cata_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>) -> Result<W, E>
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>
     {
-        into_cata_cached_body::<Self, V, W, E, _, DoCache, false, false>(self, |mask, children, val, sub_path, _debug_path| {
+        into_cata_cached_body::<Self, V, W, E, _, _, false, false>(self, DoCache::new(), |_z, mask, children, val, sub_path, _debug_path| {
             debug_assert_eq!(sub_path.len(), 0);
             alg_f(mask, children, val)
         })
@@ -380,22 +578,39 @@ impl<'a, Z, V: 'a> Catamorphism<V> for Z where Z: Zipper + ZipperReadOnlyConditi
     fn into_cata_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, E>
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        into_cata_cached_body::<Self, V, W, E, _, _, true, false>(self, DoCache::new(),
+            |_z, mask, children, val, sub_path, _debug_path| alg_f(mask, children, val, sub_path))
+    }
+    fn into_cata_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, mut alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>
     {
-        into_cata_cached_body::<Self, V, W, E, _, DoCache, true, false>(self,
-            |mask, children, val, sub_path, _debug_path| alg_f(mask, children, val, sub_path))
+        cata_pruned_body::<Self, V, W, Err, _, _, false>(self, pre_f, |mask, children, jump_len, val, path| {
+            debug_assert!(jump_len == 0);
+            alg_f(mask, children, val, path)
+        })
+    }
+    fn into_cata_jumping_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        cata_pruned_body::<Self, V, W, Err, _, _, true>(self, pre_f, alg_f)
     }
 }
 
 impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> Catamorphism<V> for PathMap<V, A> {
     fn into_cata_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>
     {
         let rz = self.into_read_zipper(&[]);
         rz.into_cata_side_effect_fallible(alg_f)
     }
     fn into_cata_jumping_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
-        where AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
     {
         let rz = self.into_read_zipper(&[]);
         rz.into_cata_jumping_side_effect_fallible(alg_f)
@@ -403,7 +618,7 @@ impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> Catamorph
     fn into_cata_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>) -> Result<W, E>
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>
     {
         let rz = self.into_read_zipper(&[]);
         rz.into_cata_cached_fallible(alg_f)
@@ -411,22 +626,253 @@ impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> Catamorph
     fn into_cata_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8]) -> Result<W, E>
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
     {
         let rz = self.into_read_zipper(&[]);
         rz.into_cata_jumping_cached_fallible(alg_f)
     }
+    fn into_cata_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_cata_pruned_side_effect_fallible(pre_f, alg_f)
+    }
+    fn into_cata_jumping_pruned_side_effect_fallible<W, Err, PreF, AlgF>(self, pre_f: PreF, alg_f: AlgF) -> Result<W, Err>
+        where
+        PreF: FnMut(&[u8]) -> PreVisit<W>,
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_cata_jumping_pruned_side_effect_fallible(pre_f, alg_f)
+    }
+}
+
+/// Provides methods to perform a paramorphism on types that can reference or contain a trie
+///
+/// A paramorphism is a [Catamorphism] where the `alg_f` additionally receives the original subtrie for each
+/// downstream branch, as a [TrieRef].  This makes it possible to graft or inspect untouched branches without
+/// a second traversal of the trie.
+pub trait Paramorphism<V: Clone + Send + Sync, A: Allocator = GlobalAlloc>: Catamorphism<V> {
+    /// Applies a "stepping" paramorphism to the trie descending from the zipper's root, running the `alg_f`
+    /// at every step (at every byte)
+    ///
+    /// ## Arguments to `alg_f`:
+    /// `(child_mask: &`[`ByteMask`]`, children: &mut `[`TakableSlice`]`<W>, subtries: &[`[`TrieRef`]`], value: Option<&V>, path: &[u8]`
+    ///
+    /// - `subtries`: A slice containing a [TrieRef] to the subtrie below each downstream branch.  The
+    ///   elements of `subtries` correspond to the elements of `children`.
+    ///
+    /// See [into_cata_side_effect](Catamorphism::into_cata_side_effect) for explanation of other arguments and
+    /// behavior
+    fn into_para_side_effect<W, AlgF>(self, mut alg_f: AlgF) -> W
+        where
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_para_side_effect_fallible(|mask, children, subtries, val, path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, subtries, val, path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Paramorphism::into_para_side_effect]
+    fn into_para_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, Err>;
+
+    /// Applies a "jumping" paramorphism to the trie
+    ///
+    /// The `subtries` passed to `alg_f` begin immediately below the bytes in `child_mask`, so they never
+    /// include the jumped-over path.
+    ///
+    /// See [into_cata_jumping_side_effect](Catamorphism::into_cata_jumping_side_effect) and
+    /// [into_para_side_effect](Paramorphism::into_para_side_effect) for explanation of the arguments and behavior
+    fn into_para_jumping_side_effect<W, AlgF>(self, mut alg_f: AlgF) -> W
+        where
+        AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], usize, Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_para_jumping_side_effect_fallible(|mask, children, subtries, jumped_cnt, val, path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, subtries, jumped_cnt, val, path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Paramorphism::into_para_jumping_side_effect]
+    fn into_para_jumping_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], usize, Option<&V>, &[u8]) -> Result<W, Err>;
+
+    /// Applies a **cached**, **stepping**, paramorphism to the trie descending from the zipper's root
+    ///
+    /// This method may re-use previous calculations of `W`, if the value for a shared subtrie has
+    /// been previously computed.  In that case `alg_f` won't be called for the shared subtrie.
+    ///
+    /// See [into_cata_cached](Catamorphism::into_cata_cached) and
+    /// [into_para_side_effect](Paramorphism::into_para_side_effect) for explanation of the arguments and behavior
+    fn into_para_cached<W, AlgF>(self, alg_f: AlgF) -> W
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>) -> W,
+        Self: Sized
+    {
+        self.into_para_cached_fallible(|mask, children, subtries, val| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, subtries, val))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Paramorphism::into_para_cached]
+    fn into_para_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>) -> Result<W, E>;
+
+    /// Applies a **cached**, **jumping**, paramorphism to the trie
+    ///
+    /// See [into_cata_jumping_cached](Catamorphism::into_cata_jumping_cached) and
+    /// [into_para_side_effect](Paramorphism::into_para_side_effect) for explanation of the arguments and behavior
+    fn into_para_jumping_cached<W, AlgF>(self, alg_f: AlgF) -> W
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_para_jumping_cached_fallible(|mask, children, subtries, val, sub_path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, subtries, val, sub_path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [Paramorphism::into_para_jumping_cached]
+    fn into_para_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, E>;
+}
+
+impl<'a, Z, V, A> Paramorphism<V, A> for Z
+    where
+    V: Clone + Send + Sync + 'a,
+    A: Allocator + 'a,
+    Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperReadOnlySubtries<'a, V, A> + ZipperConcrete + ZipperAbsolutePath + ZipperPathBuffer
+{
+    fn into_para_side_effect_fallible<W, Err, AlgF>(self, mut alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let mut subtries = ParaSubtries::new();
+        cata_side_effect_body::<Self, V, W, Err, _, false>(self, |z, mask, children, jump_len, val, path| {
+            debug_assert!(jump_len == 0);
+            alg_f(mask, children, subtries.collect(z, mask, path), val, path)
+        })
+    }
+    fn into_para_jumping_side_effect_fallible<W, Err, AlgF>(self, mut alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], usize, Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let mut subtries = ParaSubtries::new();
+        cata_side_effect_body::<Self, V, W, Err, _, true>(self, |z, mask, children, jump_len, val, path| {
+            alg_f(mask, children, subtries.collect(z, mask, path), jump_len, val, path)
+        })
+    }
+    fn into_para_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>) -> Result<W, E>
+    {
+        //The full path is needed to locate the subtries, because the zipper may have moved
+        let mut subtries = ParaSubtries::new();
+        into_cata_cached_body::<Self, V, W, E, _, _, false, true>(self, DoCache::new(), |z, mask, children, val, sub_path, path| {
+            debug_assert_eq!(sub_path.len(), 0);
+            alg_f(mask, children, subtries.collect(z, mask, path), val)
+        })
+    }
+    fn into_para_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, E>
+    {
+        let mut subtries = ParaSubtries::new();
+        into_cata_cached_body::<Self, V, W, E, _, _, true, true>(self, DoCache::new(), |z, mask, children, val, sub_path, path| {
+            alg_f(mask, children, subtries.collect(z, mask, path), val, sub_path)
+        })
+    }
+}
+
+impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> Paramorphism<V, A> for PathMap<V, A> {
+    fn into_para_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_para_side_effect_fallible(alg_f)
+    }
+    fn into_para_jumping_side_effect_fallible<W, Err, AlgF>(self, alg_f: AlgF) -> Result<W, Err>
+        where AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], usize, Option<&V>, &[u8]) -> Result<W, Err>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_para_jumping_side_effect_fallible(alg_f)
+    }
+    fn into_para_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>) -> Result<W, E>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_para_cached_fallible(alg_f)
+    }
+    fn into_para_jumping_cached_fallible<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, &[TrieRef<'_, V, A>], Option<&V>, &[u8]) -> Result<W, E>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_para_jumping_cached_fallible(alg_f)
+    }
+}
+
+/// Internal structure to assemble the child [TrieRef]s passed to a paramorphism's `alg_f`, reusing its buffers
+struct ParaSubtries<'a, V: Clone + Send + Sync, A: Allocator> {
+    subtries: Vec<TrieRef<'a, V, A>>,
+    path_buf: Vec<u8>,
+}
+
+impl<'a, V: Clone + Send + Sync + 'a, A: Allocator + 'a> ParaSubtries<'a, V, A> {
+    fn new() -> Self {
+        Self { subtries: vec![], path_buf: vec![] }
+    }
+    /// Collects a [TrieRef] for each branch in `child_mask`, below the node at the absolute `path`
+    ///
+    /// The zipper's focus must be at `path` or above it, which is always the case inside the cata bodies
+    #[inline]
+    fn collect<Z>(&mut self, z: &Z, child_mask: &ByteMask, path: &[u8]) -> &[TrieRef<'a, V, A>]
+        where Z: ZipperAbsolutePath + ZipperReadOnlySubtries<'a, V, A>
+    {
+        let focus_len = z.origin_path().len();
+        debug_assert!(focus_len <= path.len());
+        self.path_buf.clear();
+        self.path_buf.extend_from_slice(&path[focus_len..]);
+        self.subtries.clear();
+        for byte in child_mask.iter() {
+            self.path_buf.push(byte);
+            self.subtries.push(z.trie_ref_at_path(&self.path_buf).into());
+            self.path_buf.pop();
+        }
+        &self.subtries
+    }
 }
 
 #[inline]
 fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z: Z, mut alg_f: AlgF) -> Result<W, Err>
     where
     Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperAbsolutePath + ZipperPathBuffer,
-    AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, Err>
+    AlgF: FnMut(&Z, &ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
 {
     //`stack` holds a "frame" at each forking point above the zipper position.  No frames exist for values
     let mut stack = Vec::<StackFrame>::with_capacity(12);
-    let mut children = Vec::<W>::new();
+    let mut children = TakableVec::<W>::new();
     let mut frame_idx = 0;
 
     z.reset();
@@ -435,7 +881,7 @@ fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z:
     stack.push(StackFrame::from(&z));
     if !z.descend_first_byte() {
         //Empty trie is a special case
-        return alg_f(&ByteMask::EMPTY, &mut [], 0, z.val(), z.origin_path())
+        return alg_f(&z, &ByteMask::EMPTY, &mut TakableSlice::default(), 0, z.val(), z.origin_path())
     }
 
     loop {
@@ -450,7 +896,8 @@ fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z:
 
         if is_leaf {
             //Ascend back to the last fork point from this leaf
-            let cur_w = ascend_to_fork::<Z, V, W, Err, AlgF, JUMPING>(&mut z, &mut alg_f, &mut [])?;
+            let child_start = children.len();
+            let cur_w = ascend_to_fork::<Z, V, W, Err, AlgF, JUMPING>(&mut z, &mut alg_f, &mut children, child_start)?;
             children.push(cur_w);
             stack[frame_idx].child_idx += 1;
 
@@ -466,7 +913,7 @@ fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z:
                     debug_assert_eq!(stack_frame.child_idx, stack_frame.child_cnt);
                     debug_assert_eq!(stack_frame.child_cnt as usize, children.len());
                     let w = if stack_frame.child_cnt != 1 || val.is_some() || !JUMPING {
-                        alg_f(&child_mask, &mut children, 0, val, z.origin_path())?
+                        alg_f(&z, &child_mask, &mut children.takable_slice(..), 0, val, z.origin_path())?
                     } else {
                         children.pop().unwrap()
                     };
@@ -475,9 +922,7 @@ fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z:
                     // Ascend the rest of the way back up to the branch
                     debug_assert_eq!(stack[frame_idx].child_idx, stack[frame_idx].child_cnt);
                     let child_start = children.len() - stack[frame_idx].child_cnt as usize;
-                    let children2 = &mut children[child_start..];
-                    let cur_w = ascend_to_fork::<Z, V, W, Err, AlgF, JUMPING>(&mut z, &mut alg_f, children2)?;
-                    children.truncate(child_start);
+                    let cur_w = ascend_to_fork::<Z, V, W, Err, AlgF, JUMPING>(&mut z, &mut alg_f, &mut children, child_start)?;
                     frame_idx -= 1;
 
                     //Merge the result into the stack frame
@@ -499,18 +944,89 @@ fn cata_side_effect_body<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(mut z:
     }
 }
 
+/// Internal structure to hold a forking point or value above the zipper's position in [cata_pruned_body]
+struct PrunedFrame {
+    child_mask: ByteMask,
+    child_idx: usize,
+    child_cnt: usize,
+    child_start: usize,
+    jump_len: usize,
+}
+
+/// Enters the branch at the zipper's focus, calling `pre_f` and then jumping to the next feature in
+/// `JUMPING` mode.  Returns the `W` if the branch was skipped, otherwise pushes a frame for the branch
+#[inline]
+fn cata_pruned_enter<'a, Z, V: 'a, W, PreF, const JUMPING: bool>(z: &mut Z, pre_f: &mut PreF, stack: &mut Vec<PrunedFrame>, child_start: usize) -> Option<W>
+    where
+    Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperAbsolutePath + ZipperPathBuffer,
+    PreF: FnMut(&[u8]) -> PreVisit<W>,
+{
+    if let PreVisit::Skip(w) = pre_f(z.origin_path()) {
+        return Some(w)
+    }
+    let mut jump_len = 0;
+    if JUMPING {
+        while z.child_count() == 1 && !z.is_val() {
+            z.descend_first_byte();
+            jump_len += 1;
+        }
+    }
+    stack.push(PrunedFrame{ child_mask: z.child_mask(), child_idx: 0, child_cnt: z.child_count(), child_start, jump_len });
+    None
+}
+
+/// The body of the pruned side-effect catamorphisms.  Unlike [cata_side_effect_body], this visits every
+/// branch from its start, so `pre_f` gets a chance to run before anything below the branch is touched
+fn cata_pruned_body<'a, Z, V: 'a, W, Err, PreF, AlgF, const JUMPING: bool>(mut z: Z, mut pre_f: PreF, mut alg_f: AlgF) -> Result<W, Err>
+    where
+    Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperAbsolutePath + ZipperPathBuffer,
+    PreF: FnMut(&[u8]) -> PreVisit<W>,
+    AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
+{
+    let mut stack = Vec::<PrunedFrame>::with_capacity(12);
+    let mut children = TakableVec::<W>::new();
+
+    z.reset();
+    z.prepare_buffers();
+    if let Some(w) = cata_pruned_enter::<Z, V, W, PreF, JUMPING>(&mut z, &mut pre_f, &mut stack, 0) {
+        return Ok(w)
+    }
+
+    loop {
+        let frame = stack.last_mut().unwrap();
+        if frame.child_idx < frame.child_cnt {
+            //Descend the next child branch
+            let descended = z.descend_indexed_byte(frame.child_idx);
+            debug_assert!(descended);
+            frame.child_idx += 1;
+            if let Some(w) = cata_pruned_enter::<Z, V, W, PreF, JUMPING>(&mut z, &mut pre_f, &mut stack, children.len()) {
+                children.push(w);
+                z.ascend_byte();
+            }
+        } else {
+            //All the children are done, so run the alg on this frame and return to the parent
+            let frame = stack.pop().unwrap();
+            let w = alg_f(&frame.child_mask, &mut children.takable_slice(frame.child_start..), frame.jump_len, z.val(), z.origin_path())?;
+            children.truncate(frame.child_start);
+            if stack.is_empty() {
+                return Ok(w)
+            }
+            z.ascend(frame.jump_len + 1);
+            children.push(w);
+        }
+    }
+}
+
 #[inline(always)]
 fn ascend_to_fork<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(z: &mut Z, 
-        alg_f: &mut AlgF, children: &mut [W]
+        alg_f: &mut AlgF, children: &mut TakableVec<W>, child_start: usize
 ) -> Result<W, Err>
     where
     Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperAbsolutePath + ZipperPathBuffer,
-    AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, Err>
+    AlgF: FnMut(&Z, &ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, Err>
 {
     let z_witness = z.witness();
-    let mut w;
     let mut child_mask = ByteMask::from(z.child_mask());
-    let mut children = &mut children[..];
     if JUMPING {
         //This loop runs until we got to a fork or the root.  We will take a spin through the loop
         // for each value we encounter along the way while ascending
@@ -527,13 +1043,14 @@ fn ascend_to_fork<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(z: &mut Z,
                 old_path_len - z.origin_path().len()
             };
 
-            w = alg_f(&child_mask, children, jump_len, old_val, origin_path)?;
+            let w = alg_f(z, &child_mask, &mut children.takable_slice(child_start..), jump_len, old_val, origin_path)?;
+            children.truncate(child_start);
 
             if z.child_count() != 1 || z.at_root() {
                 return Ok(w)
             }
 
-            children = core::array::from_mut(&mut w);
+            children.push(w);
 
             // SAFETY: We will never over-read the path buffer because we only get here after we ascended
             let byte = *unsafe{ z.origin_path_assert_len(old_path_len-jump_len) }.last().unwrap();
@@ -546,7 +1063,8 @@ fn ascend_to_fork<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(z: &mut Z,
             let origin_path = z.origin_path();
             let byte = origin_path.last().copied().unwrap_or(0);
             let val = z.val();
-            w = alg_f(&child_mask, children, 0, val, origin_path)?;
+            let w = alg_f(z, &child_mask, &mut children.takable_slice(child_start..), 0, val, origin_path)?;
+            children.truncate(child_start);
 
             let ascended = z.ascend_byte();
             debug_assert!(ascended);
@@ -555,7 +1073,7 @@ fn ascend_to_fork<'a, Z, V: 'a, W, Err, AlgF, const JUMPING: bool>(z: &mut Z,
                 return Ok(w)
             }
 
-            children = core::array::from_mut(&mut w);
+            children.push(w);
             child_mask = ByteMask::EMPTY;
             child_mask.set_bit(byte);
         }
@@ -653,7 +1171,6 @@ impl Stack {
 pub(crate) fn new_map_from_ana_jumping<'a, V, A: Allocator, WZ, W, CoAlgF, I>(wz: &mut WZ, w: W, mut coalg_f: CoAlgF)
 where
     V: 'static + Clone + Send + Sync + Unpin,
-    W: Default,
     I: IntoIterator<Item=W>,
     WZ: ZipperWriting<V, A> + zipper::ZipperMoving,
     CoAlgF: Copy + FnMut(W, &[u8]) -> (&'a [u8], ByteMask, I, Option<V>),
@@ -677,69 +1194,263 @@ where
 /// without caching, and avoid polluting every public interface with `W: Clone`.
 ///
 /// This is not intended to be a public interface.
-pub(crate) trait CacheStrategy<W> {
-    /// Enable/disable the caching
-    const CACHING: bool;
-
-    /// Implement `Clone`, so that we can put/get values from cache
-    fn clone(_x: &W) -> W;
+pub(crate) trait CacheStrategy<Z, W> {
+    /// Returns the identity of the node at the zipper's focus, if the `W` for the focus may be cached
+    fn node_id(&mut self, z: &Z) -> Option<u64>;
 
     /// Insert a value to cache
+    fn insert(&mut self, addr: Option<u64>, cur_w: &W);
+
+    /// Get a value from cache
+    fn get(&mut self, addr: Option<u64>) -> Option<W>;
+}
+
+/// Cache is disabled
+#[allow(dead_code)] // this is unused for now, but can be used in side_effecting
+struct NoCache;
+
+impl<Z, W> CacheStrategy<Z, W> for NoCache {
     #[inline(always)]
-    fn insert(cache: &mut HashMap<u64, W>, addr: Option<u64>, cur_w: &W) {
-        // Do nothing if caching is disabled
-        if !Self::CACHING {
-            return;
-        }
+    fn node_id(&mut self, _z: &Z) -> Option<u64> { None }
+    #[inline(always)]
+    fn insert(&mut self, _addr: Option<u64>, _cur_w: &W) { }
+    #[inline(always)]
+    fn get(&mut self, _addr: Option<u64>) -> Option<W> { None }
+}
+
+/// Cache is enabled for `W: Clone`, for the duration of a single catamorphism
+pub(crate) struct DoCache<W>(HashMap<u64, W>);
+
+impl<W> DoCache<W> {
+    pub(crate) fn new() -> Self {
+        Self(HashMap::new())
+    }
+}
+
+impl<Z: ZipperConcrete, W: Clone> CacheStrategy<Z, W> for DoCache<W> {
+    #[inline(always)]
+    fn node_id(&mut self, z: &Z) -> Option<u64> {
+        z.shared_node_id()
+    }
+    #[inline(always)]
+    fn insert(&mut self, addr: Option<u64>, cur_w: &W) {
         if let Some(addr) = addr {
-            cache.insert(addr, Self::clone(cur_w));
+            self.0.insert(addr, cur_w.clone());
         }
     }
-
-    /// Get a value from cache
     #[inline(always)]
-    fn get(cache: &HashMap<u64, W>, addr: Option<u64>) -> Option<W> {
-        // Do nothing if caching is disabled
-        if !Self::CACHING {
-            return None;
+    fn get(&mut self, addr: Option<u64>) -> Option<W> {
+        addr.and_then(|addr| self.0.get(&addr).cloned())
+    }
+}
+
+/// Cache is enabled for `W: Clone`, and persists in a [CataCache] between catamorphisms
+struct IncrementalCache<'c, V: Clone + Send + Sync, W, A: Allocator>(&'c mut CataCache<V, W, A>);
+
+impl<'a, Z, V, W, A> CacheStrategy<Z, W> for IncrementalCache<'_, V, W, A>
+    where
+    V: Clone + Send + Sync + 'a,
+    W: Clone,
+    A: Allocator + 'a,
+    Z: zipper_priv::ZipperPriv<V=V, A=A> + zipper_priv::ZipperReadOnlyPriv<'a, V, A>,
+{
+    fn node_id(&mut self, z: &Z) -> Option<u64> {
+        //The value at the focus is stored in the parent node, so the `W` for a focus with a value
+        // can't be associated with the focus node
+        let (_node, key, val) = z.borrow_raw_parts();
+        if !key.is_empty() || val.is_some() {
+            return None
+        }
+        let node = z.try_borrow_focus()?;
+        let addr = node.shared_node_id();
+        //Hold onto the node, so its address can't be reused by a different node while the entry exists
+        self.0.entries.entry(addr).or_insert_with(|| (node.clone(), None));
+        Some(addr)
+    }
+    fn insert(&mut self, addr: Option<u64>, cur_w: &W) {
+        if let Some((_node, w)) = addr.and_then(|addr| self.0.entries.get_mut(&addr)) {
+            *w = Some(cur_w.clone());
         }
-        addr.and_then(|addr| cache.get(&addr).map(Self::clone))
+    }
+    fn get(&mut self, addr: Option<u64>) -> Option<W> {
+        addr.and_then(|addr| self.0.entries.get(&addr)).and_then(|(_node, w)| w.clone())
     }
 }
 
-/// Cache is disabled
-#[allow(dead_code)] // this is unused for now, but can be used in side_effecting
-struct NoCache;
+/// Results from a catamorphism, which can be reused to recompute the catamorphism after the trie is modified
+///
+/// A `CataCache` is passed to the methods of [IncrementalCatamorphism].  The `W` computed for each node
+/// is recorded in the cache, keyed by the node's identity.  When the catamorphism is run again, the `W`
+/// will be reused for every node that is still part of the trie.  Modifying a trie replaces the nodes along
+/// the modified paths, so only those nodes need to be recomputed.
+///
+/// The `CataCache` keeps a reference to each node it holds a result for.  Therefore, writing to the trie
+/// will copy the nodes along the modified path instead of modifying them in place, and nodes that have been
+/// replaced will stay in memory until [evict_stale](Self::evict_stale) or [clear](Self::clear) is called.
+///
+/// WARNING: The results in a `CataCache` are only valid for the `alg_f` closure that computed them.  Reusing
+/// a cache with a different closure will lead to incorrect results.  The cache will be cleared automatically
+/// if it is used to switch between the jumping and the non-jumping methods.
+pub struct CataCache<V: Clone + Send + Sync, W, A: Allocator = GlobalAlloc> {
+    entries: HashMap<u64, (TrieNodeODRc<V, A>, Option<W>)>,
+    jumping: Option<bool>,
+}
+
+impl<V: Clone + Send + Sync, W, A: Allocator> Default for CataCache<V, W, A> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<V: Clone + Send + Sync, W, A: Allocator> CataCache<V, W, A> {
+    /// Creates a new empty `CataCache`
+    pub fn new() -> Self {
+        Self { entries: HashMap::new(), jumping: None }
+    }
+    /// Returns the number of nodes for which the cache holds a result
+    pub fn len(&self) -> usize {
+        self.entries.values().filter(|(_node, w)| w.is_some()).count()
+    }
+    /// Returns `true` if the cache holds no results
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Removes all results from the cache
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.jumping = None;
+    }
+    /// Removes the results for nodes that aren't part of any trie anymore, releasing the nodes
+    ///
+    /// This is an O(n) operation in the number of cached results, so it makes sense to call it after a
+    /// number of modifications have been made, rather than after each catamorphism.
+    pub fn evict_stale(&mut self) {
+        //Releasing a node may leave its children referenced only by the cache, so repeat until nothing changes
+        loop {
+            let old_len = self.entries.len();
+            self.entries.retain(|_addr, (node, _w)| node.refcount() > 1);
+            if self.entries.len() == old_len {
+                break
+            }
+        }
+    }
+    /// Internal method to clear the cache if it's being used in a different mode from before
+    fn set_mode(&mut self, jumping: bool) {
+        if self.jumping != Some(jumping) {
+            self.entries.clear();
+            self.jumping = Some(jumping);
+        }
+    }
+}
 
-impl<W> CacheStrategy<W> for NoCache {
-    const CACHING: bool = false;
-    fn clone(_w: &W) -> W {
-        unreachable!("`NoCache::clone` must not be called, since `CACHING` is disabled")
+/// Provides methods to perform a catamorphism that reuses results from a previous run, held in a [CataCache]
+///
+/// The arguments and behavior of the methods are the same as the corresponding methods of [Catamorphism], except
+/// that results from the `cache` are used for any node that hasn't changed since the cache was filled, and the
+/// cache is updated with the results for new nodes.
+pub trait IncrementalCatamorphism<V: Clone + Send + Sync, A: Allocator> {
+    /// An incremental version of [into_cata_cached](Catamorphism::into_cata_cached)
+    fn into_cata_incremental<W, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> W
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> W,
+        Self: Sized
+    {
+        self.into_cata_incremental_fallible(cache, |mask, children, val| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, val))
+        }).unwrap()
     }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// Results computed before the error are kept in the cache.
+    fn into_cata_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>;
+
+    /// An incremental version of [into_cata_jumping_cached](Catamorphism::into_cata_jumping_cached)
+    fn into_cata_jumping_incremental<W, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> W
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
+        Self: Sized
+    {
+        self.into_cata_jumping_incremental_fallible(cache, |mask, children, val, sub_path| -> Result<W, Infallible> {
+            Ok(alg_f(mask, children, val, sub_path))
+        }).unwrap()
+    }
+
+    /// Allows the closure to return an error, stopping traversal immediately
+    ///
+    /// See [IncrementalCatamorphism::into_cata_jumping_incremental]
+    fn into_cata_jumping_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>;
 }
 
-/// Cache is enabled for `W: Clone`
-pub(crate) struct DoCache;
+impl<'a, Z, V, A> IncrementalCatamorphism<V, A> for Z
+    where
+    V: Clone + Send + Sync + 'a,
+    A: Allocator + 'a,
+    Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperConcrete + ZipperAbsolutePath + ZipperPathBuffer
+        + zipper_priv::ZipperPriv<V=V, A=A> + zipper_priv::ZipperReadOnlyPriv<'a, V, A>,
+{
+    fn into_cata_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>
+    {
+        cache.set_mode(false);
+        into_cata_cached_body::<Self, V, W, E, _, _, false, false>(self, IncrementalCache(cache), |_z, mask, children, val, sub_path, _debug_path| {
+            debug_assert_eq!(sub_path.len(), 0);
+            alg_f(mask, children, val)
+        })
+    }
+    fn into_cata_jumping_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        cache.set_mode(true);
+        into_cata_cached_body::<Self, V, W, E, _, _, true, false>(self, IncrementalCache(cache),
+            |_z, mask, children, val, sub_path, _debug_path| alg_f(mask, children, val, sub_path))
+    }
+}
 
-impl<W: Clone> CacheStrategy<W> for DoCache {
-    const CACHING: bool = true;
-    fn clone(w: &W) -> W { w.clone() }
+impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> IncrementalCatamorphism<V, A> for PathMap<V, A> {
+    fn into_cata_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> Result<W, E>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_cata_incremental_fallible(cache, alg_f)
+    }
+    fn into_cata_jumping_incremental_fallible<W, E, AlgF>(self, cache: &mut CataCache<V, W, A>, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        let rz = self.into_read_zipper([]);
+        rz.into_cata_jumping_incremental_fallible(cache, alg_f)
+    }
 }
 
 pub(crate) fn into_cata_cached_body<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPING: bool, const DEBUG_PATH: bool>(
-    mut zipper: Z, mut alg_f: AlgF
+    mut zipper: Z, mut cache: Cache, mut alg_f: AlgF
 ) -> Result<W, E>
     where
-    Cache: CacheStrategy<W>,
+    Cache: CacheStrategy<Z, W>,
     Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperConcrete + ZipperAbsolutePath + ZipperPathBuffer,
-    AlgF: FnMut(&ByteMask, &mut [W], Option<&V>, &[u8], &[u8]) -> Result<W, E>
+    AlgF: FnMut(&Z, &ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8], &[u8]) -> Result<W, E>
 {
     zipper.reset();
     zipper.prepare_buffers();
 
     let mut stack = Stack::new();
-    let mut children = Vec::<W>::new();
-    let mut cache = HashMap::<u64, W>::new();
+    let mut children = TakableVec::<W>::new();
     stack.push_state(&zipper);
     'outer: loop {
         let frame_mut = stack.last_mut()
@@ -748,12 +1459,12 @@ pub(crate) fn into_cata_cached_body<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPI
         if frame_mut.child_idx < frame_mut.child_cnt {
             zipper.descend_indexed_byte(frame_mut.child_idx as usize);
             frame_mut.child_idx += 1;
-            frame_mut.child_addr = zipper.shared_node_id();
+            frame_mut.child_addr = cache.node_id(&zipper);
 
             // Read and reuse value from cache, if exists
-            if let Some(cache) = Cache::get(&cache, frame_mut.child_addr) {
+            if let Some(cached) = cache.get(frame_mut.child_addr) {
                 // DO NOT modify the W from cache
-                children.push(cache);
+                children.push(cached);
                 zipper.ascend_byte();
                 continue 'outer;
             }
@@ -770,12 +1481,13 @@ pub(crate) fn into_cata_cached_body<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPI
             if is_leaf {
                 // If we encounter a leaf, ascend immediately.
                 // This branch will preserve the current stack frame.
+                let child_start = children.len();
                 let cur_w = ascend_to_fork::<Z, V, W, E, _, JUMPING>(
-                    &mut zipper, &mut |mask, children, jump, val, path| {
-                        alg_f(mask, children, val, &path[path.len()-jump..], path)
-                    }, &mut [])?;
+                    &mut zipper, &mut |z, mask, children, jump, val, path| {
+                        alg_f(z, mask, children, val, &path[path.len()-jump..], path)
+                    }, &mut children, child_start)?;
                 // Put value to cache (1)
-                Cache::insert(&mut cache, frame_mut.child_addr, &cur_w);
+                cache.insert(frame_mut.child_addr, &cur_w);
                 children.push(cur_w);
                 continue 'outer;
             }
@@ -790,7 +1502,6 @@ pub(crate) fn into_cata_cached_body<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPI
         let StackFrame { child_cnt, .. } = stack.pop_mut()
             .expect("we just checked that stack is not empty, pop must return Some");
         let child_start = children.len() - *child_cnt as usize;
-        let children2 = &mut children[child_start..];
 
         if frame_idx == 0 {
             // Final branch
@@ -805,21 +1516,20 @@ pub(crate) fn into_cata_cached_body<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPI
                 } else {
                     &[]
                 };
-                alg_f(&child_mask, children2, value, &[], debug_path)
+                alg_f(&zipper, &child_mask, &mut children.takable_slice(child_start..), value, &[], debug_path)
             };
         }
 
         let cur_w = ascend_to_fork::<Z, V, W, E, _, JUMPING>(
-            &mut zipper, &mut |mask, children, jump, val, path| {
-                alg_f(mask, children, val, &path[path.len()-jump..], path)
-            }, children2)?;
-        children.truncate(child_start);
+            &mut zipper, &mut |z, mask, children, jump, val, path| {
+                alg_f(z, mask, children, val, &path[path.len()-jump..], path)
+            }, &mut children, child_start)?;
 
         // Exit one recursion step
         let frame_mut = stack.last_mut()
             .expect("when we're not at root, expect parent stack");
         // Put value to cache (2) after recursion
-        Cache::insert(&mut cache, frame_mut.child_addr, &cur_w);
+        cache.insert(frame_mut.child_addr, &cur_w);
         children.push(cur_w);
     }
 }
@@ -833,7 +1543,7 @@ fn into_cata_jumping_naive<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPING: bool>
 ) -> Result<W, E>
     where
     Cache: CacheStrategy<W>, Z: Zipper + ZipperReadOnlyValues<'a, V> + ZipperAbsolutePath + ZipperPathBuffer + ZipperConcretePriv,
-    AlgF: FnMut (&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> Result<W, E>
+    AlgF: FnMut (&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> Result<W, E>
 {
     let child_mask = ByteMask::from(z.child_mask());
     let child_count = child_mask.count_bits();
@@ -891,7 +1601,6 @@ fn into_cata_jumping_naive<'a, Z, V: 'a, W, E, AlgF, Cache, const JUMPING: bool>
 pub(crate) fn new_map_from_ana_in<V, W, AlgF, A: Allocator>(w: W, mut alg_f: AlgF, alloc: A) -> PathMap<V, A>
     where
     V: 'static + Clone + Send + Sync + Unpin,
-    W: Default,
     AlgF: FnMut(W, &mut Option<V>, &mut TrieBuilder<V, W, A>, &[u8])
 {
     let mut stack = Vec::<(TrieBuilder<V, W, A>, usize)>::with_capacity(12);
@@ -904,13 +1613,16 @@ pub(crate) fn new_map_from_ana_in<V, W, AlgF, A: Allocator>(w: W, mut alg_f: Alg
     //The root is a special case
     stack.push((TrieBuilder::<V, W, A>::new_in(alloc.clone()), 0));
     alg_f(w, &mut val, &mut stack[frame_idx].0, z.path());
-    stack[frame_idx].0.finalize();
+    stack[frame_idx].0.finalize(&mut val);
     if let Some(val) = core::mem::take(&mut val) {
         z.set_val(val);
     }
+    if let Some(node) = stack[frame_idx].0.take_leaf_vals_node() {
+        z.core().graft_internal(Some(node));
+    }
     loop {
         //Should we descend?
-        if let Some(w_or_node) = stack[frame_idx].0.take_next() {
+        if let Some((w_or_node, child_val)) = stack[frame_idx].0.take_next() {
             //TODO Optimization Opportunity: There is likely a 2x speedup in here, that can be achieved by
             // setting all the children at the same time.  The reason is that the current behavior will create
             // a smaller node (ListNode), and then upgrade it if necessary.  But if we know in advance what
@@ -931,88 +1643,474 @@ pub(crate) fn new_map_from_ana_in<V, W, AlgF, A: Allocator>(w: W, mut alg_f: Alg
             // descends and tries to create the rest of a path, it would be a disaster if that took the form
             // of another node, as opposed to just putting the rest of the path into the existing node.
 
-            let child_path_byte = stack[frame_idx].0.taken_child_byte();
-            z.descend_to_byte(child_path_byte);
-            let mut child_path_len = 1;
+            let child_path_byte = stack[frame_idx].0.taken_child_byte();
+            z.descend_to_byte(child_path_byte);
+            let mut child_path_len = 1;
+
+            if let Some(child_path_remains) = stack[frame_idx].0.taken_child_remaining_path(child_path_byte) {
+                z.descend_to(child_path_remains);
+                child_path_len += child_path_remains.len();
+            }
+
+            //A value pushed with the child takes precedence over a value set for the child's byte
+            let child_val = if child_path_len == 1 {
+                child_val.or_else(|| stack[frame_idx].0.take_child_val(child_path_byte))
+            } else {
+                child_val
+            };
+
+            match w_or_node {
+                // Recursive path with more Ws
+                WOrNode::W(w) => {
+                    debug_assert!(frame_idx < stack.len());
+                    frame_idx += 1;
+                    if frame_idx == stack.len() {
+                        stack.push((TrieBuilder::<V, W, A>::new_in(alloc.clone()), child_path_len));
+                    } else {
+                        stack[frame_idx].0.reset();
+                        stack[frame_idx].1 = child_path_len;
+                    }
+
+                    //Run the alg if we just descended
+                    val = child_val;
+                    alg_f(w, &mut val, &mut stack[frame_idx].0, z.path());
+                    stack[frame_idx].0.finalize(&mut val);
+                    if let Some(val) = core::mem::take(&mut val) {
+                        z.set_val(val);
+                    }
+                    //The focus is new, so it has no children yet, and the values for the single-byte
+                    // paths can be grafted as a whole node before the pushed children are added
+                    if let Some(node) = stack[frame_idx].0.take_leaf_vals_node() {
+                        z.core().graft_internal(Some(node));
+                    }
+                },
+                // Path from a graft, we shouldn't descend
+                WOrNode::Node(node) => {
+                    z.core().graft_internal(node);
+                    if let Some(val) = child_val {
+                        z.set_val(val);
+                    }
+                    z.ascend(child_path_len);
+                }
+            }
+        } else {
+            //Set the remaining values for single-byte paths, where a child with a longer path was pushed
+            let child_val_mask = stack[frame_idx].0.child_val_mask();
+            for byte in child_val_mask.iter() {
+                if let Some(val) = stack[frame_idx].0.take_child_val(byte) {
+                    z.descend_to_byte(byte);
+                    z.set_val(val);
+                    z.ascend_byte();
+                }
+            }
+
+            //If not, we should ascend
+            if frame_idx == 0 {
+                break
+            }
+            z.ascend(stack[frame_idx].1);
+            stack[frame_idx].0.reset();
+            frame_idx -= 1;
+        }
+    }
+    drop(z);
+    new_map
+}
+
+/// Unfolds a trie from `seed` using `coalg_f`, and simultaneously folds it with a "stepping" `alg_f`
+///
+/// The result is the same as calling [`PathMap::new_from_ana`] followed by
+/// [`into_cata_side_effect`](Catamorphism::into_cata_side_effect) on the new map, but no trie nodes are
+/// allocated for the intermediate trie.  Only the path to the current position and the results for
+/// its siblings are held in memory.
+///
+/// `coalg_f` has the same arguments as the closure passed to [`PathMap::new_from_ana`], and `alg_f` has
+/// the same arguments as the closure passed to [`into_cata_side_effect`](Catamorphism::into_cata_side_effect).
+/// Branches that don't contain any values are pruned, just as they would be in the materialized trie, so
+/// `alg_f` is never called for them.
+///
+/// Subtries added with [`TrieBuilder::graft`] are folded in place, without copying them.
+pub fn hylo<S, V, W, CoAlgF, AlgF>(seed: S, coalg_f: CoAlgF, mut alg_f: AlgF) -> W
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, GlobalAlloc>, &[u8]),
+    AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
+{
+    HyloState::<V, S, W, _, _, _, _, false>::new(coalg_f, |mask: &ByteMask, children: &mut TakableSlice<W>, jump_len, val: Option<&V>, path: &[u8]| {
+        debug_assert_eq!(jump_len, 0);
+        alg_f(mask, children, val, path)
+    }, NoMemo).run(seed)
+}
+
+/// Unfolds a trie from `seed` using `coalg_f`, and simultaneously folds it with a "jumping" `alg_f`
+///
+/// The arguments to `alg_f` are the same as for [`into_cata_jumping_side_effect`](Catamorphism::into_cata_jumping_side_effect),
+/// and the results will be the same as running it on the trie created by [`PathMap::new_from_ana`].
+/// However, `alg_f` may be called in a different order, because whether a path can be jumped over isn't
+/// known until all of the branches beside it have been unfolded.
+///
+/// See [hylo] for more details.
+pub fn hylo_jumping<S, V, W, CoAlgF, AlgF>(seed: S, coalg_f: CoAlgF, alg_f: AlgF) -> W
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, GlobalAlloc>, &[u8]),
+    AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
+{
+    HyloState::<V, S, W, _, _, _, _, true>::new(coalg_f, alg_f, NoMemo).run(seed)
+}
+
+/// A version of [hylo] that memoizes the results for repeated seeds
+///
+/// When a seed is encountered that is equal to a previously unfolded seed, the previous result is reused
+/// and neither `coalg_f` nor `alg_f` are run for the subtrie below it.  Therefore the subtrie generated by
+/// `coalg_f` must depend only on the seed and not on the `path` argument.
+///
+/// A seed pushed together with a value, with [TrieBuilder::push_with_val] or [TrieBuilder::set_child_vals],
+/// is never memoized, because the value passed to `coalg_f` and `alg_f` is part of the result.
+///
+/// The arguments to `alg_f` are the same as for [`into_cata_cached`](Catamorphism::into_cata_cached).
+pub fn hylo_cached<S, V, W, CoAlgF, AlgF>(seed: S, coalg_f: CoAlgF, alg_f: AlgF) -> W
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    S: Clone + core::hash::Hash + Eq,
+    W: Clone,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, GlobalAlloc>, &[u8]),
+    AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> W,
+{
+    HyloState::<V, S, W, _, _, _, _, false>::new(coalg_f, |mask: &ByteMask, children: &mut TakableSlice<W>, _jump_len, val: Option<&V>, _path: &[u8]| {
+        alg_f(mask, children, val)
+    }, DoMemo(HashMap::new())).run(seed)
+}
+
+/// A version of [hylo_jumping] that memoizes the results for repeated seeds
+///
+/// The arguments to `alg_f` are the same as for [`into_cata_jumping_cached`](Catamorphism::into_cata_jumping_cached).
+/// `alg_f` will be re-run for the upper-most feature in a reused subtrie, because its `sub_path` depends on
+/// where the subtrie is located.
+///
+/// See [hylo_cached] for more details.
+pub fn hylo_jumping_cached<S, V, W, CoAlgF, AlgF>(seed: S, coalg_f: CoAlgF, alg_f: AlgF) -> W
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    S: Clone + core::hash::Hash + Eq,
+    W: Clone,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, GlobalAlloc>, &[u8]),
+    AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
+{
+    HyloState::<V, S, W, _, _, _, _, true>::new(coalg_f, |mask: &ByteMask, children: &mut TakableSlice<W>, jump_len, val: Option<&V>, path: &[u8]| {
+        alg_f(mask, children, val, &path[path.len()-jump_len..])
+    }, DoMemo(HashMap::new())).run(seed)
+}
+
+/// Internal structure to represent a non-empty branch of the trie inside a hylomorphism
+#[derive(Clone)]
+enum HyloBranch<V, W> {
+    /// The `alg_f` has already been run for the top of the branch.  Used by stepping hylomorphisms
+    Folded(W),
+    /// The `alg_f` still needs to be run on the first feature of the branch, at `rel_path` below the top of
+    /// the branch.  Used by jumping hylomorphisms, because the number of jumped bytes isn't known yet
+    Pending {
+        rel_path: Vec<u8>,
+        mask: ByteMask,
+        children: Vec<W>,
+        val: Option<V>,
+    },
+}
+
+/// Internal trait to dictate if and how the branches unfolded from a seed are memoized
+///
+/// This serves the same purpose as [CacheStrategy], for seeds instead of nodes.
+trait SeedMemo<S, T> {
+    /// Returns the memoized result for `seed`, or the key under which to record the result if the
+    /// seed hasn't been encountered before
+    fn lookup(&mut self, seed: &S) -> Result<T, Option<S>>;
+
+    /// Records the result associated with a key returned by [Self::lookup]
+    fn record(&mut self, key: Option<S>, result: &T);
+}
+
+/// Memoization is disabled
+struct NoMemo;
+
+impl<S, T> SeedMemo<S, T> for NoMemo {
+    #[inline(always)]
+    fn lookup(&mut self, _seed: &S) -> Result<T, Option<S>> { Err(None) }
+    #[inline(always)]
+    fn record(&mut self, _key: Option<S>, _result: &T) { }
+}
+
+/// Memoization is enabled for `S: Hash + Eq`
+struct DoMemo<S, T>(HashMap<S, T>);
+
+impl<S: Clone + core::hash::Hash + Eq, T: Clone> SeedMemo<S, T> for DoMemo<S, T> {
+    fn lookup(&mut self, seed: &S) -> Result<T, Option<S>> {
+        match self.0.get(seed) {
+            Some(result) => Ok(result.clone()),
+            None => Err(Some(seed.clone()))
+        }
+    }
+    fn record(&mut self, key: Option<S>, result: &T) {
+        if let Some(key) = key {
+            self.0.insert(key, result.clone());
+        }
+    }
+}
+
+/// Internal structure to hold the state of a hylomorphism
+struct HyloState<V: Clone + Send + Sync, S, W, CoAlgF, AlgF, Memo, A: Allocator, const JUMPING: bool> {
+    coalg_f: CoAlgF,
+    alg_f: AlgF,
+    memo: Memo,
+    path: Vec<u8>,
+    builders: Vec<TrieBuilder<V, S, A>>,
+    alloc: A,
+    _w: core::marker::PhantomData<W>,
+}
+
+impl<V, S, W, CoAlgF, AlgF, Memo, const JUMPING: bool> HyloState<V, S, W, CoAlgF, AlgF, Memo, GlobalAlloc, JUMPING>
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, GlobalAlloc>, &[u8]),
+    AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
+    Memo: SeedMemo<S, Option<HyloBranch<V, W>>>,
+{
+    fn new(coalg_f: CoAlgF, alg_f: AlgF, memo: Memo) -> Self {
+        Self {
+            coalg_f,
+            alg_f,
+            memo,
+            path: Vec::with_capacity(64),
+            builders: Vec::with_capacity(12),
+            alloc: global_alloc(),
+            _w: core::marker::PhantomData,
+        }
+    }
+}
+
+impl<V, S, W, CoAlgF, AlgF, Memo, A, const JUMPING: bool> HyloState<V, S, W, CoAlgF, AlgF, Memo, A, JUMPING>
+    where
+    V: 'static + Clone + Send + Sync + Unpin,
+    CoAlgF: FnMut(S, &mut Option<V>, &mut TrieBuilder<V, S, A>, &[u8]),
+    AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
+    Memo: SeedMemo<S, Option<HyloBranch<V, W>>>,
+    A: Allocator,
+{
+
+    fn run(mut self, seed: S) -> W {
+        match self.seed_branch(seed, None) {
+            None => (self.alg_f)(&ByteMask::EMPTY, &mut TakableSlice::default(), 0, None, &[]),
+            Some(HyloBranch::Folded(w)) => w,
+            //If the root isn't a feature, the first feature's bytes are all jumped, including the first
+            Some(HyloBranch::Pending { rel_path, mask, children, val }) => {
+                let mut children = TakableVec::from(children);
+                (self.alg_f)(&mask, &mut children.takable_slice(..), rel_path.len(), val.as_ref(), &rel_path)
+            }
+        }
+    }
+
+    /// Unfolds the subtrie for `seed` at the current path, and folds as much of it as possible
+    ///
+    /// `val` is the value that was pushed for the current path, before the closure runs.  The branch
+    /// depends on `val` as well as `seed`, so it is only memoized if no value was pushed
+    fn seed_branch(&mut self, seed: S, mut val: Option<V>) -> Option<HyloBranch<V, W>> {
+        let key = match val {
+            Some(_) => None,
+            None => match self.memo.lookup(&seed) {
+                Ok(branch) => return branch,
+                Err(key) => key
+            },
+        };
+
+        let mut builder = self.builders.pop().unwrap_or_else(|| TrieBuilder::new_in(self.alloc.clone()));
+        (self.coalg_f)(seed, &mut val, &mut builder, &self.path);
+        builder.finalize(&mut val);
 
-            if let Some(child_path_remains) = stack[frame_idx].0.taken_child_remaining_path(child_path_byte) {
-                z.descend_to(child_path_remains);
-                child_path_len += child_path_remains.len();
-            }
+        let base_len = self.path.len();
+        let mut mask = ByteMask::EMPTY;
+        let mut children = Vec::with_capacity(builder.len());
+        let mut val_bytes = builder.child_val_mask().iter().peekable();
+        while let Some((w_or_node, child_val)) = builder.take_next() {
+            let byte = builder.taken_child_byte();
 
-            match w_or_node {
-                // Recursive path with more Ws
-                WOrNode::W(w) => {
-                    debug_assert!(frame_idx < stack.len());
-                    frame_idx += 1;
-                    if frame_idx == stack.len() {
-                        stack.push((TrieBuilder::<V, W, A>::new_in(alloc.clone()), child_path_len));
-                    } else {
-                        stack[frame_idx].0.reset();
-                        stack[frame_idx].1 = child_path_len;
-                    }
+            //Values for single-byte paths that precede the child become leaf branches
+            while let Some(val_byte) = val_bytes.next_if(|val_byte| *val_byte < byte) {
+                self.leaf_branch(&mut builder, val_byte, &mut mask, &mut children);
+            }
+            val_bytes.next_if_eq(&byte);
 
-                    //Run the alg if we just descended
-                    alg_f(w, &mut val, &mut stack[frame_idx].0, z.path());
-                    stack[frame_idx].0.finalize();
-                    if let Some(val) = core::mem::take(&mut val) {
-                        z.set_val(val);
-                    }
+            self.path.push(byte);
+            if let Some(child_path_remains) = builder.taken_child_remaining_path(byte) {
+                self.path.extend_from_slice(child_path_remains);
+            }
+            let sole_byte = self.path.len() == base_len + 1;
+            let byte_val = builder.take_child_val(byte);
+            let (child_val, byte_val) = if sole_byte { (child_val.or(byte_val), None) } else { (child_val, byte_val) };
+            let child = match w_or_node {
+                WOrNode::W(seed) => self.seed_branch(seed, child_val),
+                WOrNode::Node(Some(node)) => {
+                    let mut z = ReadZipperUntracked::new_with_node_and_path_in(&node, &[], 0, 0, None, self.alloc.clone());
+                    self.zipper_branch(&mut z, child_val)
                 },
-                // Path from a graft, we shouldn't descend
-                WOrNode::Node(node) => {
-                    z.core().graft_internal(Some(node));
-                    z.ascend(child_path_len);
-                }
+                WOrNode::Node(None) => self.node_branch(ByteMask::EMPTY, vec![], child_val),
+            };
+            let child = match byte_val {
+                //A value at the child's first byte puts a feature in the middle of the child's path
+                Some(byte_val) => {
+                    let next_byte = self.path[base_len + 1];
+                    let child = child.map(|child| self.elevate_branch(child, base_len + 2));
+                    self.path.truncate(base_len + 1);
+                    let mut child_mask = ByteMask::EMPTY;
+                    let child_children = match child {
+                        Some(child) => {
+                            child_mask.set_bit(next_byte);
+                            vec![child]
+                        },
+                        None => vec![]
+                    };
+                    self.node_branch(child_mask, child_children, Some(byte_val))
+                },
+                None => child.map(|child| self.elevate_branch(child, base_len + 1)),
+            };
+            if let Some(child) = child {
+                mask.set_bit(byte);
+                children.push(child);
             }
-        } else {
-            //If not, we should ascend
-            if frame_idx == 0 {
-                break
+            self.path.truncate(base_len);
+        }
+        for val_byte in val_bytes {
+            self.leaf_branch(&mut builder, val_byte, &mut mask, &mut children);
+        }
+        builder.reset();
+        self.builders.push(builder);
+
+        let branch = self.node_branch(mask, children, val);
+        self.memo.record(key, &branch);
+        branch
+    }
+
+    /// Adds a branch for a value set with [TrieBuilder::set_child_vals] at a byte where no child was pushed
+    fn leaf_branch(&mut self, builder: &mut TrieBuilder<V, S, A>, byte: u8, mask: &mut ByteMask, children: &mut Vec<HyloBranch<V, W>>) {
+        let val = builder.take_child_val(byte);
+        self.path.push(byte);
+        if let Some(child) = self.node_branch(ByteMask::EMPTY, vec![], val) {
+            mask.set_bit(byte);
+            children.push(child);
+        }
+        self.path.pop();
+    }
+
+    /// Folds the subtrie below the zipper's focus, which is at the current path
+    ///
+    /// `val` is used in place of the value at the zipper's focus
+    fn zipper_branch<Z>(&mut self, z: &mut Z, val: Option<V>) -> Option<HyloBranch<V, W>>
+        where Z: ZipperMoving + ZipperValues<V>
+    {
+        let mut mask = ByteMask::EMPTY;
+        let mut children = Vec::with_capacity(z.child_count());
+        for idx in 0..z.child_count() {
+            z.descend_indexed_byte(idx);
+            let byte = *z.path().last().unwrap();
+            self.path.push(byte);
+            let child_val = z.val().cloned();
+            if let Some(child) = self.zipper_branch(z, child_val) {
+                mask.set_bit(byte);
+                children.push(child);
+            }
+            self.path.pop();
+            z.ascend_byte();
+        }
+        self.node_branch(mask, children, val)
+    }
+
+    /// Brings a branch located at the current path up to `top_len`, across path bytes without forks or values
+    fn elevate_branch(&mut self, branch: HyloBranch<V, W>, top_len: usize) -> HyloBranch<V, W> {
+        match branch {
+            HyloBranch::Folded(w) => {
+                let mut children = TakableVec::with_capacity(1);
+                children.push(w);
+                for len in (top_len..self.path.len()).rev() {
+                    let mut mask = ByteMask::EMPTY;
+                    mask.set_bit(self.path[len]);
+                    let w = (self.alg_f)(&mask, &mut children.takable_slice(..), 0, None, &self.path[..len]);
+                    children.clear();
+                    children.push(w);
+                }
+                HyloBranch::Folded(children.pop().unwrap())
+            },
+            HyloBranch::Pending { rel_path, mask, children, val } => {
+                let mut new_rel_path = Vec::with_capacity(self.path.len() - top_len + rel_path.len());
+                new_rel_path.extend_from_slice(&self.path[top_len..]);
+                new_rel_path.extend_from_slice(&rel_path);
+                HyloBranch::Pending { rel_path: new_rel_path, mask, children, val }
             }
-            z.ascend(stack[frame_idx].1);
-            stack[frame_idx].0.reset();
-            frame_idx -= 1;
         }
     }
-    drop(z);
-    new_map
+
+    /// Assembles the branch for the node at the current path, from the branches directly below it
+    fn node_branch(&mut self, mask: ByteMask, children: Vec<HyloBranch<V, W>>, val: Option<V>) -> Option<HyloBranch<V, W>> {
+        if children.is_empty() && val.is_none() {
+            return None
+        }
+        if !JUMPING {
+            let mut children: TakableVec<W> = children.into_iter().map(|child| match child {
+                HyloBranch::Folded(w) => w,
+                HyloBranch::Pending { .. } => unreachable!()
+            }).collect();
+            let w = (self.alg_f)(&mask, &mut children.takable_slice(..), 0, val.as_ref(), &self.path);
+            return Some(HyloBranch::Folded(w))
+        }
+
+        //A node without a value and with only one branch gets jumped over
+        if val.is_none() && children.len() == 1 {
+            let byte = mask.iter().next().unwrap();
+            let child = children.into_iter().next().unwrap();
+            let HyloBranch::Pending { rel_path, mask, children, val } = child else { unreachable!() };
+            let mut new_rel_path = Vec::with_capacity(rel_path.len() + 1);
+            new_rel_path.push(byte);
+            new_rel_path.extend_from_slice(&rel_path);
+            return Some(HyloBranch::Pending { rel_path: new_rel_path, mask, children, val })
+        }
+
+        //This node is a feature, so we can run the `alg_f` for the first feature of each branch
+        let base_len = self.path.len();
+        let mut child_ws = Vec::with_capacity(children.len());
+        for (byte, child) in mask.iter().zip(children) {
+            let HyloBranch::Pending { rel_path, mask: child_mask, children, val: child_val } = child else { unreachable!() };
+            let mut children = TakableVec::from(children);
+            self.path.push(byte);
+            self.path.extend_from_slice(&rel_path);
+            child_ws.push((self.alg_f)(&child_mask, &mut children.takable_slice(..), rel_path.len(), child_val.as_ref(), &self.path));
+            self.path.truncate(base_len);
+        }
+        Some(HyloBranch::Pending { rel_path: vec![], mask, children: child_ws, val })
+    }
 }
 
 /// A [Vec]-like struct for assembling all the downstream branches from a path in the trie
-//GOAT, Ideally I would skip the `val` argument to the anamorphism closure, and add a `set_val` method
-// to TrieBuilder.  I'm a little on the fence about it, however, because it increases the size of the
-// TrieBuilder by an `Option<V>`, which is stored for every stack frame, as opposed to just the one place
-// it's needed.  However, this change opens up the possibility to embed the WriteZipper into the TrieBuilder,
-// which can make some operations a bit more efficient.
-//
-//GOAT, If we exposed an interface that allowed values to be set in bulk, (e.g. with a mask), we could
-// plumb it straight through to a node interface
+//GOAT, Now that the TrieBuilder has a `set_val` method, we could skip the `val` argument to the anamorphism
+// closure.  This opens up the possibility to embed the WriteZipper into the TrieBuilder, which can make some
+// operations a bit more efficient.
 pub struct TrieBuilder<V: Clone + Send + Sync, W, A: Allocator> {
     child_mask: [u64; 4],
     cur_mask_word: usize,
     child_paths: ReusingQueue<Vec<u8>>,
-    child_structs: ReusingQueue<WOrNode<V, W, A>>,
-    _alloc: A,
+    child_structs: ReusingQueue<Option<BuilderChild<V, W, A>>>,
+    val: Option<V>,
+    child_val_mask: [u64; 4],
+    child_vals: Vec<Option<V>>,
+    alloc: A,
 }
 
 /// Internal structure 
 enum WOrNode<V: Clone + Send + Sync, W, A: Allocator> {
     W(W),
-    Node(TrieNodeODRc<V, A>)
+    Node(Option<TrieNodeODRc<V, A>>)
 }
 
-impl<V: Clone + Send + Sync, W: Default, A: Allocator> Default for WOrNode<V, W, A> {
-    fn default() -> Self {
-        //GOAT, the default impl here is mainly to facilitate core::mem::take, therefore, the default
-        // should be the cheapest thing to create.  At some point that will be a TrieNodeODRc pointing
-        // at a static empty node, but that's currently not implemented yet.
-        // Alternatively we could use a MaybeUninit.
-        Self::W(W::default())
-    }
-}
+/// Internal.  A child pushed to a [TrieBuilder], along with the value for the end of its path
+type BuilderChild<V, W, A> = (WOrNode<V, W, A>, Option<V>);
 
-impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
+impl<V: Clone + Send + Sync, W, A: Allocator> TrieBuilder<V, W, A> {
     /// Internal method to make a new empty `TrieBuilder`
     fn new_in(alloc: A) -> Self {
         Self {
@@ -1020,7 +2118,10 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
             cur_mask_word: 0,
             child_paths: ReusingQueue::new(),
             child_structs: ReusingQueue::new(),
-            _alloc: alloc,
+            val: None,
+            child_val_mask: [0u64; 4],
+            child_vals: Vec::new(),
+            alloc,
         }
     }
     /// Internal method.  Clears a builder without freeing its memory
@@ -1029,17 +2130,26 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
         self.cur_mask_word = 0;
         self.child_structs.clear();
         self.child_paths.clear();
+        self.val = None;
+        self.child_val_mask = [0u64; 4];
+        self.child_vals.clear();
     }
     /// Internal method.  Called after the user code has run to fill the builder, but before we start to empty it
-    fn finalize(&mut self) {
+    ///
+    /// A value set with [Self::set_val] replaces the value passed through the closure's `val` argument
+    fn finalize(&mut self, val: &mut Option<V>) {
         self.cur_mask_word = 0;
         while self.cur_mask_word < 4 && self.child_mask[self.cur_mask_word] == 0 {
             self.cur_mask_word += 1;
         }
+        if let Some(builder_val) = self.val.take() {
+            *val = Some(builder_val);
+        }
     }
-    /// Internal method to get the next child from the builder in the push order.  Used by the anamorphism
-    fn take_next(&mut self) -> Option<WOrNode<V, W, A>> {
-        self.child_structs.pop_front().map(|element| core::mem::take(element))
+    /// Internal method to get the next child from the builder in the push order, along with the value
+    /// pushed for the end of its path.  Used by the anamorphism
+    fn take_next(&mut self) -> Option<BuilderChild<V, W, A>> {
+        self.child_structs.pop_front().and_then(|element| element.take())
     }
     /// Internal method.  After [Self::take_next] returns `Some`, this method will return the first byte of the
     /// associated path.
@@ -1062,15 +2172,52 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
             self.child_paths.pop_front().map(|v| &v.as_slice()[1..])
         }
     }
+    /// Internal method.  Takes the value set by [Self::set_child_vals] for the single-byte path `[byte]`
+    fn take_child_val(&mut self, byte: u8) -> Option<V> {
+        let mask = ByteMask::from(self.child_val_mask);
+        if !mask.test_bit(byte) {
+            return None
+        }
+        self.child_vals[mask.index_of(byte) as usize].take()
+    }
+    /// Internal method.  Returns the mask of single-byte paths with values set by [Self::set_child_vals]
+    fn child_val_mask(&self) -> ByteMask {
+        ByteMask::from(self.child_val_mask)
+    }
+    /// Internal method.  Takes the values set by [Self::set_child_vals] at the bytes where no child was
+    /// pushed, as a node that holds only those values.  Must be called before the children are taken
+    fn take_leaf_vals_node(&mut self) -> Option<TrieNodeODRc<V, A>> {
+        let val_mask = ByteMask::from(self.child_val_mask);
+        let leaf_mask = val_mask.andn(&ByteMask::from(self.child_mask));
+        if leaf_mask.is_empty_mask() {
+            return None
+        }
+        let node = if leaf_mask == val_mask {
+            let node = DenseByteNode::from_vals_in(leaf_mask, self.child_vals.drain(..).map(|val| val.unwrap()), self.alloc.clone());
+            self.child_val_mask = [0u64; 4];
+            node
+        } else {
+            let child_vals = &mut self.child_vals;
+            DenseByteNode::from_vals_in(leaf_mask, leaf_mask.iter().map(|byte| child_vals[val_mask.index_of(byte) as usize].take().unwrap()), self.alloc.clone())
+        };
+        Some(TrieNodeODRc::new_in(node, self.alloc.clone()))
+    }
     /// Returns the number of children that have been pushed to the `TrieBuilder`, so far
     pub fn len(&self) -> usize {
         self.child_structs.len()
     }
+    /// Sets the value at the path where the `TrieBuilder` is being filled
+    ///
+    /// This is equivalent to assigning to the `val` argument of the anamorphism closure.  If both are used,
+    /// the value set with `set_val` takes precedence.
+    pub fn set_val(&mut self, val: V) {
+        self.val = Some(val);
+    }
     /// Simultaneously sets all child branches with single-byte path continuations
     ///
     /// Panics if existing children have already been set / pushed, or if the number of bits set in `mask`
     /// doesn't match `children.len()`.
-    pub fn set_child_mask<C: AsMut<[W]>>(&mut self, mask: [u64; 4], mut children: C) {
+    pub fn set_child_mask<C: AsMut<[W]>>(&mut self, mask: [u64; 4], mut children: C) where W: Default {
         if self.child_structs.len() != 0 {
             panic!("set_mask called over existing children")
         }
@@ -1081,7 +2228,7 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
         }
         self.child_structs.clear();
         for child in children {
-            self.child_structs.push_val(WOrNode::W(core::mem::take(child)));
+            self.child_structs.push_val(Some((WOrNode::W(core::mem::take(child)), None)));
         }
         debug_assert_eq!(self.cur_mask_word, 0);
         while mask[self.cur_mask_word] == 0 {
@@ -1089,10 +2236,31 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
         }
         self.child_mask = mask;
     }
-    /// Pushes a new child branch into the `TrieBuilder` with the specified `byte`
+    /// Simultaneously sets values at all of the single-byte paths specified by `mask`
     ///
-    /// Panics if `byte <=` the first byte of any previosuly pushed paths.
-    pub fn push_byte(&mut self, byte: u8, w: W) {
+    /// The values are stored in the order of the bits in `mask`.  When building a trie, the values at the
+    /// bytes where no child is pushed are moved into a single new node, in that same order, rather than
+    /// being inserted one path at a time.  A [hylomorphism](hylo) has no trie to fill, so it folds each
+    /// of those values as a separate leaf.
+    ///
+    /// This method can be combined with [push](Self::push), [set_child_mask](Self::set_child_mask) and
+    /// the other methods that add children.  If a child is also pushed to one of the single-byte paths,
+    /// the value will be passed to the closure through the `val` argument when it runs for the child.  If
+    /// a child with a longer path begins with one of the bytes, the value is set at the single-byte path
+    /// after the child has been built.
+    ///
+    /// Panics if values have already been set with this method, or if the number of bits set in `mask`
+    /// doesn't match the number of `vals`.
+    pub fn set_child_vals<I: IntoIterator<Item=V>>(&mut self, mask: [u64; 4], vals: I) {
+        if !self.child_vals.is_empty() {
+            panic!("set_child_vals called over existing values")
+        }
+        self.child_vals.extend(vals.into_iter().map(Some));
+        assert_eq!(mask.iter().fold(0, |sum, word| sum + word.count_ones() as usize), self.child_vals.len());
+        self.child_val_mask = mask;
+    }
+    /// Internal method to add `byte` to the child mask, enforcing the ordering rules for pushing children
+    fn push_child_byte(&mut self, byte: u8) {
         let mask_word = (byte / 64) as usize;
         if mask_word < self.cur_mask_word {
             panic!("children must be pushed in sorted order")
@@ -1103,9 +2271,29 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
             panic!("children must be pushed in sorted order and each initial byte must be unique")
         }
         self.child_mask[mask_word] |= mask_delta;
+    }
+    /// Internal method to push a child at `sub_path`, with an optional value for the end of the path
+    fn push_internal(&mut self, sub_path: &[u8], child: WOrNode<V, W, A>, val: Option<V>) {
+        assert!(sub_path.len() > 0);
+        self.push_child_byte(sub_path[0]);
+
+        //Push the remaining path
+        if sub_path.len() > 1 {
+            let child_path = self.child_paths.push_mut();
+            child_path.clear();
+            child_path.extend(sub_path);
+        }
+
+        self.child_structs.push_val(Some((child, val)));
+    }
+    /// Pushes a new child branch into the `TrieBuilder` with the specified `byte`
+    ///
+    /// Panics if `byte <=` the first byte of any previosuly pushed paths.
+    pub fn push_byte(&mut self, byte: u8, w: W) {
+        self.push_child_byte(byte);
 
         //Push the `W`
-        self.child_structs.push_val(WOrNode::W(w));
+        self.child_structs.push_val(Some((WOrNode::W(w), None)));
     }
     /// Pushes a new child branch into the `TrieBuilder` with the specified `sub_path`
     ///
@@ -1121,16 +2309,14 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
     // Not sure if it would make any difference.  Feels unlikely, but might be worth a try after we've implemented
     // the other speedup ideas
     pub fn push(&mut self, sub_path: &[u8], w: W) {
-        assert!(sub_path.len() > 0);
-
-        //Push the remaining path
-        if sub_path.len() > 1 {
-            let child_path = self.child_paths.push_mut();
-            child_path.clear();
-            child_path.extend(sub_path);
-        }
-
-        self.push_byte(sub_path[0], w);
+        self.push_internal(sub_path, WOrNode::W(w), None)
+    }
+    /// Behaves like [push](Self::push), but also sets a value at the end of `sub_path`
+    ///
+    /// The value will be passed to the closure through the `val` argument when it runs for the child, so
+    /// the closure may replace or remove it.
+    pub fn push_with_val(&mut self, sub_path: &[u8], val: V, w: W) {
+        self.push_internal(sub_path, WOrNode::W(w), Some(val))
     }
 //GOAT WIP
 //     /// Behaves like [push](Self::push), but will tolerate inputs in any order, and inputs and with
@@ -1201,25 +2387,43 @@ impl<V: Clone + Send + Sync, W: Default, A: Allocator> TrieBuilder<V, W, A> {
     pub fn child_mask(&self) -> [u64; 4] {
         self.child_mask
     }
-    /// Grafts the subtrie below the focus of the `read_zipper` at the specified `byte`
+    /// Grafts the subtrie below the focus of the `read_zipper` at the specified `sub_path`
+    ///
+    /// The grafted subtrie is shared with the source, rather than copied, and the closure will not run
+    /// for any paths within it.  A [TrieRef] may be passed to graft a subtrie without a zipper.  If the
+    /// `read_zipper` is at a nonexistent path, nothing is grafted.
+    ///
+    /// GOAT: This method's behavior is affected by the `graft_root_vals` feature, in the same way as
+    /// [ZipperWriting::graft]
     ///
     /// WARNING: This method is incompatible with [Self::set_child_mask] and must follow the same
-    /// rules as [Self::push_byte]
-    pub fn graft_at_byte<Z: ZipperSubtries<V, A>>(&mut self, byte: u8, read_zipper: &Z) {
-        let mask_word = (byte / 64) as usize;
-        if mask_word < self.cur_mask_word {
-            panic!("children must be pushed in sorted order")
+    /// rules as [Self::push]
+    pub fn graft<Z: ZipperSubtries<V, A>>(&mut self, sub_path: &[u8], read_zipper: &Z) {
+        let node = read_zipper.get_focus().into_option();
+        #[cfg(not(feature = "graft_root_vals"))]
+        let val = None;
+        #[cfg(feature = "graft_root_vals")]
+        let val = read_zipper.val().cloned();
+        if node.is_some() || val.is_some() {
+            self.push_internal(sub_path, WOrNode::Node(node), val)
         }
-        self.cur_mask_word = mask_word;
-        let mask_delta = 1u64 << (byte % 64);
-        if self.child_mask[mask_word] >= mask_delta {
-            panic!("children must be pushed in sorted order and each initial byte must be unique")
+    }
+    /// Grafts the contents of a [PathMap] at the specified `sub_path`, consuming the map
+    ///
+    /// If the `map` is empty, nothing is grafted.  See [graft](Self::graft) for more details.
+    pub fn graft_map(&mut self, sub_path: &[u8], map: PathMap<V, A>) where V: Unpin {
+        let (node, val) = map.into_root();
+        #[cfg(not(feature = "graft_root_vals"))]
+        let val = { drop(val); None };
+        if node.is_some() || val.is_some() {
+            self.push_internal(sub_path, WOrNode::Node(node), val)
         }
-        self.child_mask[mask_word] |= mask_delta;
-
-        //Clone the read_zipper's focus and push it
-        let node = read_zipper.get_focus().into_option();
-        self.child_structs.push_val(WOrNode::Node(node.unwrap())); //GOAT!! Currently we panic if the read_zipper is at an nonexistent path
+    }
+    /// Grafts the subtrie below the focus of the `read_zipper` at the specified `byte`
+    ///
+    /// Equivalent to calling [graft](Self::graft) with a 1-byte `sub_path`
+    pub fn graft_at_byte<Z: ZipperSubtries<V, A>>(&mut self, byte: u8, read_zipper: &Z) {
+        self.graft(&[byte], read_zipper)
     }
     //GOAT, feature removed.  See below
     // /// Returns an [`Iterator`] type to iterate over the `(sub_path, w)` pairs that have been pushed
@@ -1280,7 +2484,7 @@ mod tests {
         zipper: Z, mut f_side: AlgF, mut assert: Assert)
         where
             Z: Clone + Catamorphism<V>, W: Clone,
-            AlgF: FnMut(&ByteMask, &mut [W], usize, Option<&V>, &[u8]) -> W,
+            AlgF: FnMut(&ByteMask, &mut TakableSlice<W>, usize, Option<&V>, &[u8]) -> W,
             Assert: FnMut(W, &str),
     {
         let output = zipper.clone().into_cata_side_effect(
@@ -1289,13 +2493,19 @@ mod tests {
         let output = zipper.clone().into_cata_jumping_side_effect(
             |bm, ch, jmp, v, path| f_side(bm, ch, jmp, v, path));
         assert(output, "into_cata_jumping_side_effect");
+        let output = zipper.clone().into_cata_pruned_side_effect(|_path| PreVisit::Descend,
+            |bm, ch, v, path| f_side(bm, ch, 0, v, path));
+        assert(output, "into_cata_pruned_side_effect");
+        let output = zipper.clone().into_cata_jumping_pruned_side_effect(|_path| PreVisit::Descend,
+            |bm, ch, jmp, v, path| f_side(bm, ch, jmp, v, path));
+        assert(output, "into_cata_jumping_pruned_side_effect");
     }
 
     fn check_pure_catas<'a, W, V, Z, AlgFP, Assert>(
         zipper: Z, f_pure: AlgFP, mut assert: Assert)
         where
             Z: Clone + Catamorphism<V>, W: Clone,
-            AlgFP: Fn(&ByteMask, &mut [W], Option<&V>, &[u8]) -> W,
+            AlgFP: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> W,
             Assert: FnMut(W, &str),
     {
         let output = zipper.clone().into_cata_cached(
@@ -1310,7 +2520,7 @@ mod tests {
         zipper: Z, alg_f: AlgF, mut assert: Assert)
         where
             Z: Clone + Catamorphism<V>, W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>) -> W,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>) -> W,
             Assert: FnMut(W, &str),
     {
         check_side_effect_catas(zipper.clone(), |mask, children, _jmp, val, _path| {
@@ -1343,7 +2553,7 @@ mod tests {
             //Test both flavors of the side-effect catas, since they fundamentally work the same
             // for this algorithm.  They just add the path byte each time there is a value, and
             // sum all the downstream branches.
-            let alg = |_child_mask: &ByteMask, children: &mut [u32], _jump_len: usize, val: Option<&()>, path: &[u8]| {
+            let alg = |_child_mask: &ByteMask, children: &mut TakableSlice<u32>, _jump_len: usize, val: Option<&()>, path: &[u8]| {
                 let this_digit = if val.is_some() {
                     (*path.last().unwrap() as char).to_digit(10).unwrap()
                 } else {
@@ -1357,7 +2567,7 @@ mod tests {
             //The pure stepping cata alg is similar, but works a little differently
             // Here, we pass whether there was a val, so we can decide to add the path byte
             // at the next level up.
-            let pure_alg_stepping = |child_mask: &ByteMask, children: &mut [(bool, u32)], val: Option<&()>| {
+            let pure_alg_stepping = |child_mask: &ByteMask, children: &mut TakableSlice<(bool, u32)>, val: Option<&()>| {
                 let mut sum = 0;
                 for (child_byte, (child_val, downstream_sum)) in child_mask.iter().zip(children.into_iter()) {
                     if *child_val {
@@ -1376,7 +2586,7 @@ mod tests {
             //
             //This code works fine for both stepping and jumping, but is a little more complicated
             // than the stepping-only version
-            let pure_alg = |child_mask: &ByteMask, children: &mut [(bool, u32)], val: Option<&()>, sub_path: &[u8]| {
+            let pure_alg = |child_mask: &ByteMask, children: &mut TakableSlice<(bool, u32)>, val: Option<&()>, sub_path: &[u8]| {
                 let mut sum = 0;
                 if val.is_some() {
                     if let Some(path_byte) = sub_path.last() {
@@ -1397,6 +2607,58 @@ mod tests {
         }
     }
 
+    #[test]
+    fn cata_pruned_test1() {
+        let keys = ["arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
+        let map: PathMap<()> = keys.iter().map(|k| (k, ())).collect();
+
+        let count_alg = |_mask: &ByteMask, children: &mut TakableSlice<usize>, val: Option<&()>, _path: &[u8]| {
+            children.iter().sum::<usize>() + val.is_some() as usize
+        };
+        let jumping_count_alg = |mask: &ByteMask, children: &mut TakableSlice<usize>, _jump_len: usize, val: Option<&()>, path: &[u8]| {
+            count_alg(mask, children, val, path)
+        };
+
+        //Skip everything below "rom", substituting a made-up count
+        let mut pre_calls = vec![];
+        let cnt = map.clone().into_cata_pruned_side_effect(|path| {
+            pre_calls.push(path.to_vec());
+            if path == b"rom" { PreVisit::Skip(100) } else { PreVisit::Descend }
+        }, count_alg);
+        assert_eq!(cnt, 107);
+        assert!(!pre_calls.iter().any(|path| path.len() > 3 && path.starts_with(b"rom")));
+        assert_eq!(pre_calls.iter().filter(|path| path.as_slice() == b"").count(), 1);
+
+        //In jumping mode, "rom" isn't the start of a branch, but "ro" is
+        let mut pre_calls = vec![];
+        let cnt = map.clone().into_cata_jumping_pruned_side_effect(|path| {
+            pre_calls.push(path.to_vec());
+            if path.starts_with(b"ro") { PreVisit::Skip(100) } else { PreVisit::Descend }
+        }, jumping_count_alg);
+        assert_eq!(cnt, 107);
+        let expected_calls = ["", "a", "b", "c", "r", "ro", "ru", "rube", "ruben", "ruber", "rubi", "rubico", "rubicu"];
+        assert_eq!(pre_calls, expected_calls.iter().map(|p| p.as_bytes().to_vec()).collect::<Vec<_>>());
+
+        //A depth bound, counting the paths that reach the bound
+        for depth in 0..12 {
+            let bounded = map.clone().into_cata_pruned_side_effect(|path| {
+                if path.len() == depth { PreVisit::Skip(1) } else { PreVisit::Descend }
+            }, count_alg);
+            let mut expected = PathMap::<()>::new();
+            for key in keys.iter() {
+                if key.len() >= depth {
+                    expected.set_val_at(&key.as_bytes()[..depth], ());
+                }
+            }
+            let shallower = keys.iter().filter(|key| key.len() < depth).count();
+            assert_eq!(bounded, expected.val_count() + shallower);
+        }
+
+        //Skipping at the root doesn't visit anything
+        let cnt = map.into_cata_jumping_pruned_side_effect(|_path| PreVisit::Skip(7), |_mask, _children: &mut TakableSlice<usize>, _jump_len, _val, _path| -> usize { unreachable!() });
+        assert_eq!(cnt, 7);
+    }
+
     #[test]
     fn cata_test2() {
         let mut btm = PathMap::new();
@@ -1415,21 +2677,21 @@ mod tests {
                 1 //leaf node
             }
         }
-        let alg = |_mask: &ByteMask, children: &mut [usize], val: Option<&usize>| {
+        let alg = |_mask: &ByteMask, children: &mut TakableSlice<usize>, val: Option<&usize>| {
             leaf_cnt(children, val)
         };
         check_all_catas(btm.read_zipper(), alg, |cnt, _| assert_eq!(cnt, 11));
 
         //=================================================================================
         // LongestPath - Finds the longest path in the trie, by just looking at the path.  Side-effect catas have that luxury
-        fn longest_path(children: &mut[Vec<u8>], path: &[u8]) -> Vec<u8> {
+        fn longest_path(children: &mut TakableSlice<Vec<u8>>, path: &[u8]) -> Vec<u8> {
             if children.len() == 0 {
                 path.to_vec()
             } else {
                 children.iter_mut().max_by_key(|p| p.len()).map_or(vec![], std::mem::take)
             }
         }
-        let alg = |_mask: &ByteMask, children: &mut [Vec<u8>], _jmp: usize, _val: Option<&usize>, path: &[u8]| {
+        let alg = |_mask: &ByteMask, children: &mut TakableSlice<Vec<u8>>, _jmp: usize, _val: Option<&usize>, path: &[u8]| {
             longest_path(children, path)
         };
         check_side_effect_catas(btm.read_zipper(), alg, |longest, _|
@@ -1438,7 +2700,7 @@ mod tests {
         //=================================================================================
         // PureLongestPath - Finds the longest path in the trie by concatenating sub-paths;
         //  This is necessary for pure catas because the same subtrie may share multiple base paths
-        fn longest_partial_path(child_mask: &ByteMask, children: &mut[Vec<u8>], sub_path: &[u8]) -> Vec<u8> {
+        fn longest_partial_path(child_mask: &ByteMask, children: &mut TakableSlice<Vec<u8>>, sub_path: &[u8]) -> Vec<u8> {
             if children.len() == 0 {
                 sub_path.to_vec()
             } else {
@@ -1454,7 +2716,7 @@ mod tests {
                 path
             }
         }
-        let alg = |mask: &ByteMask, children: &mut [Vec<u8>], _val: Option<&usize>, path: &[u8]| {
+        let alg = |mask: &ByteMask, children: &mut TakableSlice<Vec<u8>>, _val: Option<&usize>, path: &[u8]| {
             longest_partial_path(mask, children, path)
         };
         check_pure_catas(btm.read_zipper(), alg, |longest, _|
@@ -1476,7 +2738,7 @@ mod tests {
                 vec![]
             }
         }
-        let alg = |_mask: &ByteMask, children: &mut [Vec<usize>], val: Option<&usize>| {
+        let alg = |_mask: &ByteMask, children: &mut TakableSlice<Vec<usize>>, val: Option<&usize>| {
             vals_at_branches(children, val)
         };
         check_all_catas(btm.read_zipper(), alg, |at_truncated, _|
@@ -1499,7 +2761,7 @@ mod tests {
             let zip = map.read_zipper();
 
             //Test both the jumping and non-jumping versions
-            let (node_sum, leaf_sum) = zip.clone().into_cata_side_effect(|_child_mask: &ByteMask, children: &mut [(u32, u32)], _val, path: &[u8]| {
+            let (node_sum, leaf_sum) = zip.clone().into_cata_side_effect(|_child_mask: &ByteMask, children: &mut TakableSlice<(u32, u32)>, _val, path: &[u8]| {
                 // println!("aggregate path=\"{}\", children={children:?}", String::from_utf8_lossy(path));
                 let (mut node_sum, mut leaf_sum) = children.into_iter().fold((0, 0), |(node_sum, leaf_sum), (child_node, child_leaf)| (node_sum + *child_node, leaf_sum + *child_leaf));
                 if path.len() > 0 {
@@ -1513,7 +2775,7 @@ mod tests {
             assert_eq!(node_sum, byte_cnt);
             assert_eq!(leaf_sum, leaf_cnt);
 
-            let (node_sum, leaf_sum) = zip.into_cata_jumping_side_effect(|_child_mask: &ByteMask, children: &mut [(u32, u32)], jump, _val, path: &[u8]| {
+            let (node_sum, leaf_sum) = zip.into_cata_jumping_side_effect(|_child_mask: &ByteMask, children: &mut TakableSlice<(u32, u32)>, jump, _val, path: &[u8]| {
                 // println!("aggregate path=\"{}\", children={children:?}, jump={jump}", String::from_utf8_lossy(path));
                 let (mut node_sum, mut leaf_sum) = children.into_iter().fold((0, 0), |(node_sum, leaf_sum), (child_node, child_leaf)| (node_sum + *child_node, leaf_sum + *child_leaf));
                 if children.len() != 1 && path.len() > 0 { //Don't count the root as a leaf
@@ -1588,11 +2850,11 @@ mod tests {
         let rs = ["arr", "arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
         rs.iter().enumerate().for_each(|(i, r)| { btm.set_val_at(r.as_bytes(), i); });
 
-        let s: Option<Trie<usize>> = btm.read_zipper().into_cata_jumping_side_effect(|bm, ws: &mut [Option<Trie<usize>>], jump, mv, path| {
+        let s: Option<Trie<usize>> = btm.read_zipper().into_cata_jumping_side_effect(|bm, ws: &mut TakableSlice<Option<Trie<usize>>>, jump, mv, path| {
             Some(Trie{
                 prefix: String::from_utf8(path[path.len()-jump..].to_vec()).unwrap(),
                 value: mv.cloned(),
-                children: bm.iter().zip(ws).map(|(b, t)| (b as char, std::mem::take(t).unwrap())).collect()
+                children: bm.iter().enumerate().map(|(i, b)| (b as char, ws.take(i).unwrap())).collect()
             })
         });
 
@@ -1619,11 +2881,11 @@ mod tests {
         let keys = [vec![b'a', b'b', b'c'], vec![b'a', b'b', b'c', b'x', b'y']];
         let btm: PathMap<usize> = keys.into_iter().enumerate().map(|(i, k)| (k, i)).collect();
 
-        let s: Option<Trie<usize>> = btm.read_zipper().into_cata_jumping_side_effect(|bm, ws: &mut [Option<Trie<usize>>], jump, mv, path| {
+        let s: Option<Trie<usize>> = btm.read_zipper().into_cata_jumping_side_effect(|bm, ws: &mut TakableSlice<Option<Trie<usize>>>, jump, mv, path| {
             Some(Trie{
                 prefix: String::from_utf8(path[path.len()-jump..].to_vec()).unwrap(),
                 value: mv.cloned(),
-                children: bm.iter().zip(ws).map(|(b, t)| (b as char, std::mem::take(t).unwrap())).collect()
+                children: bm.iter().enumerate().map(|(i, b)| (b as char, ws.take(i).unwrap())).collect()
             })
         });
 
@@ -1681,7 +2943,7 @@ mod tests {
     #[test]
     fn cata_test5() {
         let empty = PathMap::<u64>::new();
-        let result = empty.into_cata_side_effect(|_mask, children: &mut [usize], val, _path| {
+        let result = empty.into_cata_side_effect(|_mask, children: &mut TakableSlice<usize>, val, _path| {
             let mut val_count = children.into_iter().fold(0, |sum, cnt| sum + *cnt);
             if val.is_some() {
                 val_count += 1
@@ -1692,7 +2954,7 @@ mod tests {
 
         let mut nonempty = PathMap::<u64>::new();
         nonempty.set_val_at(&[1, 2, 3], !0);
-        let result = nonempty.into_cata_side_effect(|_mask, children: &mut [usize], val, _path| {
+        let result = nonempty.into_cata_side_effect(|_mask, children: &mut TakableSlice<usize>, val, _path| {
             let mut val_count = children.into_iter().fold(0, |sum, cnt| sum + *cnt);
             if val.is_some() {
                 val_count += 1
@@ -1951,6 +3213,313 @@ mod tests {
         eprintln!("calls_cached: {calls_cached}\ncalls_side: {calls_side}");
     }
 
+    /// Checks that accumulators without a [Default] impl can be moved out of `children`
+    #[test]
+    fn cata_test_non_default() {
+        use core::num::NonZeroUsize;
+        /// A stand-in for a resource with no sensible empty value
+        #[derive(Clone, Debug, PartialEq)]
+        struct Chain(NonZeroUsize, Vec<u8>);
+
+        let keys = ["arr", "arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus"];
+        let btm: PathMap<()> = keys.iter().map(|k| (k.as_bytes(), ())).collect();
+
+        let alg = |mask: &ByteMask, children: &mut TakableSlice<Chain>, _val: Option<&()>| {
+            //Keep the longest child chain, moving it out rather than cloning it
+            let mut best: Option<(u8, Chain)> = None;
+            for (idx, byte) in mask.iter().enumerate() {
+                let child = children.take(idx);
+                if best.as_ref().map(|(_, b)| child.0 > b.0).unwrap_or(true) {
+                    best = Some((byte, child));
+                }
+            }
+            match best {
+                Some((byte, Chain(len, mut bytes))) => {
+                    bytes.insert(0, byte);
+                    Chain(len.checked_add(1).unwrap(), bytes)
+                },
+                None => Chain(NonZeroUsize::MIN, vec![])
+            }
+        };
+        let expected = Chain(NonZeroUsize::new(8).unwrap(), b"romanus".to_vec());
+        assert_eq!(btm.read_zipper().into_cata_cached(alg), expected);
+        assert_eq!(btm.read_zipper().into_cata_side_effect(|mask, children, val, _path| alg(mask, children, val)), expected);
+    }
+
+    /// Checks that an incremental cata only reruns the `alg_f` along the modified paths
+    #[test]
+    fn cata_incremental_test1() {
+        use core::cell::Cell;
+        let mut map: PathMap<u64> = (0..10000u64).map(|i| (format!("{i:04}"), i)).collect();
+        let calls = Cell::new(0);
+        let alg = |_mask: &ByteMask, children: &mut TakableSlice<u64>, val: Option<&u64>| {
+            calls.set(calls.get() + 1);
+            children.iter().sum::<u64>() + val.copied().unwrap_or(0)
+        };
+        let expected_sum = |map: &PathMap<u64>| map.iter().map(|(_path, val)| *val).sum::<u64>();
+
+        let mut cache = CataCache::new();
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut cache, alg), expected_sum(&map));
+        let full_calls = calls.replace(0);
+        assert!(!cache.is_empty());
+
+        //Nothing changed, so only the positions inside the root node are recomputed
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut cache, alg), expected_sum(&map));
+        assert!(calls.replace(0) <= 4);
+
+        //A few modifications
+        map.insert("0500", 1_000_000);
+        map.insert("12345", 12345);
+        map.remove("0007");
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut cache, alg), expected_sum(&map));
+        assert!(calls.get() < full_calls / 50, "{} calls, out of {full_calls}", calls.get());
+        assert_eq!(map.clone().into_cata_cached(alg), expected_sum(&map));
+
+        //Evicting releases the replaced nodes, but keeps the results for the live ones
+        let len_before = cache.len();
+        cache.evict_stale();
+        assert!(cache.len() < len_before);
+        calls.set(0);
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut cache, alg), expected_sum(&map));
+        assert!(calls.get() <= 4);
+
+        //Dropping the map makes all the results stale
+        drop(map);
+        cache.evict_stale();
+        assert!(cache.is_empty());
+    }
+
+    /// Checks the jumping incremental cata matches the non-incremental version after modifications
+    #[test]
+    fn cata_incremental_test2() {
+        use core::cell::Cell;
+
+        let keys = ["arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
+        let mut map: PathMap<()> = keys.iter().map(|k| (k.as_bytes(), ())).collect();
+        let alg = |mask: &ByteMask, children: &mut TakableSlice<String>, val: Option<&()>, sub_path: &[u8]| {
+            format!("{mask:?}{children:?}{val:?}{}", String::from_utf8_lossy(sub_path))
+        };
+        let mut cache = CataCache::new();
+        for key in ["rubicundo", "bowling", "arr", "roma", "x"] {
+            let incremental = map.clone().into_cata_jumping_incremental(&mut cache, alg);
+            assert_eq!(incremental, map.clone().into_cata_jumping_cached(alg));
+            map.insert(key, ());
+        }
+        assert!(!cache.is_empty());
+
+        //Switching the same cache to the non-jumping mode must discard the jumping results, so every node
+        // is recomputed, just as it would be with a new cache
+        let calls = Cell::new(0);
+        let stepping_alg = |mask: &ByteMask, children: &mut TakableSlice<String>, val: Option<&()>| {
+            calls.set(calls.get() + 1);
+            format!("{mask:?}{children:?}{val:?}")
+        };
+        let expected = map.clone().into_cata_cached(stepping_alg);
+        let mut new_cache = CataCache::new();
+        calls.set(0);
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut new_cache, stepping_alg), expected);
+        let new_cache_calls = calls.replace(0);
+        assert_eq!(map.read_zipper().into_cata_incremental(&mut cache, stepping_alg), expected);
+        assert_eq!(calls.get(), new_cache_calls);
+        assert_eq!(cache.len(), new_cache.len());
+
+        //And switching back discards the non-jumping results
+        assert_eq!(map.clone().into_cata_jumping_incremental(&mut cache, alg), map.clone().into_cata_jumping_cached(alg));
+    }
+
+    /// Checks that the subtries passed to the paramorphism `alg_f` match the `W` computed for each child
+    #[test]
+    fn para_test1() {
+        let keys = ["arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
+        let btm: PathMap<usize> = keys.iter().enumerate().map(|(i, k)| (k.as_bytes(), i)).collect();
+        let check_counts = |children: &[usize], subtries: &[TrieRef<'_, usize>]| {
+            assert_eq!(children.len(), subtries.len());
+            for (w, subtrie) in children.iter().zip(subtries) {
+                assert_eq!(*w, subtrie.val_count());
+            }
+        };
+
+        let total = btm.clone().into_para_side_effect(|_mask, children: &mut TakableSlice<usize>, subtries, val, _path| {
+            check_counts(children, subtries);
+            children.iter().sum::<usize>() + val.is_some() as usize
+        });
+        assert_eq!(total, keys.len());
+
+        let total = btm.clone().into_para_jumping_side_effect(|_mask, children: &mut TakableSlice<usize>, subtries, _jump_len, val, _path| {
+            check_counts(children, subtries);
+            children.iter().sum::<usize>() + val.is_some() as usize
+        });
+        assert_eq!(total, keys.len());
+
+        let total = btm.clone().into_para_cached(|_mask, children: &mut TakableSlice<usize>, subtries, val| {
+            check_counts(children, subtries);
+            children.iter().sum::<usize>() + val.is_some() as usize
+        });
+        assert_eq!(total, keys.len());
+
+        let total = btm.read_zipper_at_path(b"rub").into_para_jumping_cached(|_mask, children: &mut TakableSlice<usize>, subtries, val, _sub_path| {
+            check_counts(children, subtries);
+            children.iter().sum::<usize>() + val.is_some() as usize
+        });
+        assert_eq!(total, 4);
+    }
+
+    /// Rebuilds a trie by grafting the original subtries, with and without caching
+    #[test]
+    fn para_test2() {
+        let rebuild = |mask: &ByteMask, subtries: &[TrieRef<'_, u8>], val: Option<&u8>, sub_path: &[u8]| {
+            let mut map = PathMap::new();
+            let mut wz = map.write_zipper_at_path(sub_path);
+            if let Some(v) = val {
+                wz.set_val(*v);
+            }
+            for (byte, subtrie) in mask.iter().zip(subtries) {
+                wz.descend_to_byte(byte);
+                wz.graft(subtrie);
+                wz.ascend_byte();
+            }
+            drop(wz);
+            map
+        };
+
+        let keys = [vec![0, 128, 1], vec![0, 128, 1, 255, 2], vec![0, 129], vec![3, 4, 5, 6]];
+        let btm: PathMap<u8> = keys.iter().enumerate().map(|(i, k)| (k, i as u8)).collect();
+        let rebuilt = btm.clone().into_para_jumping_cached(|mask, _children: &mut TakableSlice<PathMap<u8>>, subtries, val, sub_path| {
+            rebuild(mask, subtries, val, sub_path)
+        });
+        assert_eq!(rebuilt.iter().collect::<Vec<_>>(), btm.iter().collect::<Vec<_>>());
+
+        let shared = crate::utils::ints::gen_int_range::<u8, 2, u16>(0x0, 0x301, 0x1, 0);
+        let rebuilt = shared.clone().into_para_cached(|mask, _children: &mut TakableSlice<PathMap<u8>>, subtries, val| {
+            rebuild(mask, subtries, val, &[])
+        });
+        assert_eq!(rebuilt.val_count(), 0x301);
+        assert_eq!(rebuilt.iter().collect::<Vec<_>>(), shared.iter().collect::<Vec<_>>());
+    }
+
+    /// Compares hylomorphisms against the equivalent anamorphism followed by a catamorphism
+    #[test]
+    fn hylo_test1() {
+        let graft_src = PathMap::from_iter([(&b"zz"[..], 100), (b"zzy", 101), (b"zx", 102)]);
+        let graft_z = graft_src.read_zipper_at_path(b"z");
+        let coalg = |n: u32, val: &mut Option<u32>, children: &mut TrieBuilder<u32, u32, GlobalAlloc>, _path: &[u8]| {
+            if n & 1 == 0 {
+                *val = Some(n)
+            }
+            if n < 40 {
+                children.push_byte(b'a', n*2+1);
+                children.push(b"bcd", n*2+2);
+                if n == 5 {
+                    children.graft_at_byte(b'g', &graft_z);
+                }
+            }
+        };
+        let map = PathMap::new_from_ana(0, coalg);
+
+        //Stepping, in exactly the same order
+        let mut cata_calls = vec![];
+        let cata_w = map.clone().into_cata_side_effect(|mask, children: &mut TakableSlice<String>, val, path| {
+            cata_calls.push(path.to_vec());
+            format!("{mask:?}{children:?}{val:?}{path:?}")
+        });
+        let mut hylo_calls = vec![];
+        let hylo_w = hylo(0, coalg, |mask, children: &mut TakableSlice<String>, val, path| {
+            hylo_calls.push(path.to_vec());
+            format!("{mask:?}{children:?}{val:?}{path:?}")
+        });
+        assert_eq!(hylo_w, cata_w);
+        assert_eq!(hylo_calls, cata_calls);
+
+        //Jumping, in any order
+        let mut cata_calls = vec![];
+        let cata_w = map.clone().into_cata_jumping_side_effect(|mask, children: &mut TakableSlice<String>, jump_len, val, path| {
+            cata_calls.push(path.to_vec());
+            format!("{mask:?}{children:?}{jump_len}{val:?}{path:?}")
+        });
+        let mut hylo_calls = vec![];
+        let hylo_w = hylo_jumping(0, coalg, |mask, children: &mut TakableSlice<String>, jump_len, val, path| {
+            hylo_calls.push(path.to_vec());
+            format!("{mask:?}{children:?}{jump_len}{val:?}{path:?}")
+        });
+        assert_eq!(hylo_w, cata_w);
+        cata_calls.sort();
+        hylo_calls.sort();
+        assert_eq!(hylo_calls, cata_calls);
+
+        //Cached variants
+        let cata_w = map.clone().into_cata_cached(|mask, children: &mut TakableSlice<String>, val| format!("{mask:?}{children:?}{val:?}"));
+        let hylo_w = hylo_cached(0, coalg, |mask, children: &mut TakableSlice<String>, val| format!("{mask:?}{children:?}{val:?}"));
+        assert_eq!(hylo_w, cata_w);
+        let cata_w = map.into_cata_jumping_cached(|mask, children: &mut TakableSlice<String>, val, sub_path| format!("{mask:?}{children:?}{val:?}{sub_path:?}"));
+        let hylo_w = hylo_jumping_cached(0, coalg, |mask, children: &mut TakableSlice<String>, val, sub_path| format!("{mask:?}{children:?}{val:?}{sub_path:?}"));
+        assert_eq!(hylo_w, cata_w);
+    }
+
+    /// Tests the degenerate cases and memoization of repeated seeds
+    #[test]
+    fn hylo_test2() {
+        //An empty trie
+        let w = hylo(0, |_n: u8, _val: &mut Option<()>, _children, _path| {}, |mask, children: &mut TakableSlice<usize>, val, path| {
+            assert_eq!(*mask, ByteMask::EMPTY);
+            assert_eq!(children.len(), 0);
+            assert!(val.is_none());
+            assert_eq!(path.len(), 0);
+            7
+        });
+        assert_eq!(w, 7);
+
+        //A single path with no forks gets jumped all the way to the root
+        let w = hylo_jumping(0, |n: u8, val, children, _path| {
+            if n < 4 { children.push_byte(n, n+1) } else { *val = Some(()) }
+        }, |_mask, _children: &mut TakableSlice<Vec<u8>>, jump_len, _val, path| {
+            assert_eq!(jump_len, 4);
+            path.to_vec()
+        });
+        assert_eq!(w, vec![0, 1, 2, 3]);
+
+        //Generate all 10-long 'L' | 'R' permutations
+        let mut invocations = 0;
+        let count = hylo_cached(10, |idx: usize, val, children, _path| {
+            if idx > 0 {
+                children.push_byte(b'L', idx - 1);
+                children.push_byte(b'R', idx - 1);
+            } else {
+                *val = Some(());
+            }
+            invocations += 1;
+        }, |_mask, children: &mut TakableSlice<usize>, val| children.iter().sum::<usize>() + val.is_some() as usize);
+        assert_eq!(count, 1024);
+        assert_eq!(invocations, 11);
+
+        let mut invocations = 0;
+        let count = hylo_jumping_cached(10, |idx: usize, val, children, _path| {
+            if idx > 0 {
+                children.push(b"LL", idx - 1);
+                children.push(b"RR", idx - 1);
+            } else {
+                *val = Some(());
+            }
+            invocations += 1;
+        }, |_mask, children: &mut TakableSlice<usize>, val, _sub_path| children.iter().sum::<usize>() + val.is_some() as usize);
+        assert_eq!(count, 1024);
+        assert_eq!(invocations, 11);
+
+        //The same seed pushed with different values isn't reused
+        let sum = hylo_cached(2u8, |n: u8, val: &mut Option<u64>, children, _path| {
+            match n {
+                2 => {
+                    children.push_with_val(b"a", 10, 0);
+                    children.push_with_val(b"b", 20, 0);
+                    children.push(b"c", 0);
+                    children.push(b"d", 0);
+                },
+                1 => *val = Some(1),
+                _ => children.push_byte(b'x', 1),
+            }
+        }, |_mask, children: &mut TakableSlice<u64>, val| children.iter().sum::<u64>() + val.copied().unwrap_or(0));
+        assert_eq!(sum, (10 + 1) + (20 + 1) + 1 + 1);
+    }
+
     /// Generate some basic tries using the [TrieBuilder::push_byte] API
     #[test]
     fn ana_test1() {
@@ -2066,6 +3635,73 @@ mod tests {
         assert_eq!(map.get_val_at(b"RightLeftRightLeftRightLeftRight"), Some(&()));
     }
 
+    /// Test setting values and grafting through the [`TrieBuilder`], rather than the closure's `val` argument
+    #[test]
+    fn ana_test5() {
+        let graft_src = PathMap::from_iter([(&b"zz"[..], 100), (b"zzy", 101), (b"zx", 102)]);
+        let coalg = |n: u32, val: &mut Option<u32>, children: &mut TrieBuilder<u32, u32, GlobalAlloc>, _path: &[u8]| {
+            match n {
+                0 => {
+                    children.set_val(1);
+                    //Values at 'a' and 'c' for pushed children, and at 'b' and 'e' without them
+                    let mut mask = ByteMask::EMPTY;
+                    for b in b"abce" { mask.set_bit(*b) }
+                    children.set_child_vals(mask.into_inner(), [10, 11, 12, 13]);
+                    children.push_byte(b'a', 1);
+                    children.push(b"cd", 2);
+                    children.push_with_val(b"fg", 14, 3);
+                    children.graft(b"ghost", &graft_src.read_zipper_at_path(b"nothing"));
+                    children.graft(b"x", &graft_src.trie_ref_at_path(b"z"));
+                    children.graft_map(b"yy", graft_src.clone());
+                },
+                //The closure sees the value set for it by the parent
+                1 => assert_eq!(*val, Some(10)),
+                2 => assert_eq!(*val, None),
+                3 => {
+                    assert_eq!(*val, Some(14));
+                    *val = Some(15);
+                },
+                _ => unreachable!()
+            }
+        };
+        let map = PathMap::new_from_ana(0, coalg);
+        let expected = [(&b""[..], 1), (b"a", 10), (b"b", 11), (b"c", 12), (b"e", 13), (b"fg", 15),
+            (b"xx", 102), (b"xz", 100), (b"xzy", 101), (b"yyzx", 102), (b"yyzz", 100), (b"yyzzy", 101)];
+        let entries: Vec<(Vec<u8>, u32)> = map.iter().map(|(path, val)| (path, *val)).collect();
+        assert_eq!(entries, expected.iter().map(|(path, val)| (path.to_vec(), *val)).collect::<Vec<_>>());
+
+        //The hylomorphism must see the same trie
+        let alg = |mask: &ByteMask, children: &mut TakableSlice<String>, val: Option<&u32>, path: &[u8]| {
+            format!("{mask:?}{children:?}{val:?}{path:?}")
+        };
+        assert_eq!(hylo(0, coalg, alg), map.clone().into_cata_side_effect(alg));
+        let jumping_alg = |mask: &ByteMask, children: &mut TakableSlice<String>, jump_len: usize, val: Option<&u32>, path: &[u8]| {
+            format!("{mask:?}{children:?}{jump_len}{val:?}{path:?}")
+        };
+        assert_eq!(hylo_jumping(0, coalg, jumping_alg), map.into_cata_jumping_side_effect(jumping_alg));
+    }
+
+    /// Test [`TrieBuilder::set_child_vals`] at every level of a trie, where children are also pushed
+    /// beside the values, and below them
+    #[test]
+    fn ana_test6() {
+        let map = PathMap::new_from_ana(0u32, |depth, _val: &mut Option<u32>, children: &mut TrieBuilder<u32, u32, GlobalAlloc>, _path: &[u8]| {
+            let mut mask = ByteMask::EMPTY;
+            for b in b"0123" { mask.set_bit(*b) }
+            children.set_child_vals(mask.into_inner(), (0..4).map(|i| depth * 10 + i));
+            if depth < 3 {
+                children.push(b"2y", depth + 1);
+                children.push_byte(b'x', depth + 1);
+            }
+        });
+        assert_eq!(map.val_count(), 4 * 15);
+        assert_eq!(map.get_val_at(b"3"), Some(&3));
+        assert_eq!(map.get_val_at(b"x2"), Some(&12));
+        assert_eq!(map.get_val_at(b"2yx1"), Some(&21));
+        assert_eq!(map.get_val_at(b"2y2y2y0"), Some(&30));
+        assert_eq!(map.get_val_at(b"xxxx"), None);
+    }
+
     const GREETINGS: &[&str] = &["Hallo,Afrikaans", "Përshëndetje,Albanian", "እው ሰላም ነው,Amharic", "مرحبًا,Arabic",
         "Barev,Armenian", "Kamisaki,Aymara", "Salam,Azerbaijani", "Kaixo,Basque", "Вітаю,Belarusian", "হ্যালো,Bengali",
         "Zdravo,Bosnian", "Здравейте,Bulgarian", "ဟယ်လို,Burmese", "你好,Cantonese", "Hola,Catalan", "Kamusta,Cebuano",
@@ -2230,4 +3866,80 @@ mod tests {
             println!("v: {}, p: {}", v, std::str::from_utf8(rz.path()).unwrap());
         }
     }
+
+    /// Summarizes from a mid-trie focus, checking the zipper isn't moved and the paths are focus-relative
+    #[test]
+    fn summary_test1() {
+        let keys = ["arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
+        let btm: PathMap<()> = keys.iter().map(|k| (k.as_bytes(), ())).collect();
+        let mut zipper = btm.read_zipper();
+        zipper.descend_to(b"rom");
+
+        //Reassemble every path below the focus from the jumped sub_paths
+        let paths = zipper.summarize(|mask, children: &mut TakableSlice<Vec<Vec<u8>>>, val, sub_path| {
+            let mut paths = vec![];
+            if val.is_some() {
+                paths.push(vec![]);
+            }
+            for (idx, byte) in mask.iter().enumerate() {
+                for path in children.take(idx) {
+                    paths.push([&[byte][..], &path].concat());
+                }
+            }
+            for path in paths.iter_mut() {
+                path.splice(0..0, sub_path.iter().copied());
+            }
+            paths
+        });
+        let mut paths: Vec<String> = paths.into_iter().map(|path| String::from_utf8(path).unwrap()).collect();
+        paths.sort();
+        assert_eq!(paths, vec!["'i", "an", "ane", "anus", "ulus"]);
+        assert_eq!(zipper.path(), b"rom");
+        assert_eq!(zipper.val_count(), 5);
+
+        zipper.descend_to(b"x");
+        assert_eq!(zipper.val_count(), 0);
+        assert_eq!(zipper.summarize(|_mask, _children: &mut TakableSlice<usize>, _val, _sub_path| 7), 7);
+    }
+
+    /// Compares `val_count` on the virtual zippers against the materialized tries
+    #[test]
+    fn summary_test2() {
+        let a: PathMap<()> = ["ab", "abc", "x"].iter().map(|k| (k.as_bytes(), ())).collect();
+        let b: PathMap<()> = ["", "1", "22"].iter().map(|k| (k.as_bytes(), ())).collect();
+        let c: PathMap<()> = ["ab", "y"].iter().map(|k| (k.as_bytes(), ())).collect();
+
+        //ProductZipper, from the root and from a focus inside the second factor
+        let mut pz = crate::zipper::ProductZipper::new(a.read_zipper(), [b.read_zipper()]);
+        let mut iter_z = crate::zipper::ProductZipper::new(a.read_zipper(), [b.read_zipper()]);
+        let mut iter_cnt = iter_z.is_val() as usize;
+        while iter_z.to_next_val() {
+            iter_cnt += 1;
+        }
+        assert_eq!(pz.val_count(), iter_cnt);
+        assert_eq!(pz.val_count(), 7);
+        pz.descend_to(b"x");
+        assert_eq!(pz.val_count(), 3);
+        pz.descend_to(b"2");
+        assert_eq!(pz.val_count(), 1);
+        assert_eq!(pz.path(), b"x2");
+
+        //PrefixZipper, from the root and from within the prefix
+        let mut prz = crate::zipper::PrefixZipper::new(b"pre", a.read_zipper());
+        assert_eq!(prz.val_count(), 3);
+        prz.descend_to(b"pr");
+        assert_eq!(prz.val_count(), 3);
+        let depth = prz.summarize(|_mask, children: &mut TakableSlice<usize>, _val, sub_path| {
+            children.iter().copied().max().map(|d| d + 1).unwrap_or(0) + sub_path.len()
+        });
+        assert_eq!(depth, 4);
+        prz.descend_to(b"q");
+        assert_eq!(prz.val_count(), 0);
+
+        //OverlayZipper
+        let mut oz = crate::zipper::OverlayZipper::new(a.read_zipper(), c.read_zipper());
+        assert_eq!(oz.val_count(), 4);
+        oz.descend_to(b"a");
+        assert_eq!(oz.val_count(), 2);
+    }
 }
@@ -15,10 +15,15 @@
 // e.g. `Fn(Option<&VBase>, Option<&VOverlay>) -> VOverlay`  Although this also breaks the trait contract
 // with ZipperValues, etc. because we don't have a place to store the newly created value
 //
+//UPDATE: The merging direction now lives in [crate::zipper::JoinZipper], which caches the merged value
+// at the focus so it can hand out references, and accepts any number of sources.  This zipper stays as the
+// cheap two-source view for mappings that only need to select a reference.
+//
 
 use fast_slice_utils::find_prefix_overlap;
-use crate::utils::{BitMask, ByteMask};
-use crate::zipper::{Zipper, ZipperMoving, ZipperIteration, ZipperValues};
+use crate::utils::{BitMask, ByteMask, TakableSlice};
+use crate::morphisms::summarize_from_focus;
+use crate::zipper::{Zipper, ZipperMoving, ZipperIteration, ZipperValues, ZipperForking, TrieSummary};
 
 /// Zipper that traverses a virtual trie formed by fusing the tries of two other zippers
 pub struct OverlayZipper<AV, BV, OutV, AZipper, BZipper, Mapping>
@@ -149,10 +154,6 @@ impl<AV, BV, OutV, AZipper, BZipper, Mapping> ZipperMoving
         self.a.path()
     }
 
-    fn val_count(&self) -> usize {
-        todo!()
-    }
-
     fn descend_to<P: AsRef<[u8]>>(&mut self, path: P) {
         let path = path.as_ref();
         self.a.descend_to(path);
@@ -315,6 +316,23 @@ impl<AV, BV, OutV, AZipper, BZipper, Mapping> ZipperIteration
         Mapping: for<'a> Fn(Option<&'a AV>, Option<&'a BV>) -> Option<&'a OutV>,
 { }
 
+impl<AV, BV, OutV, AZipper, BZipper, Mapping> TrieSummary<OutV>
+    for OverlayZipper<AV, BV, OutV, AZipper, BZipper, Mapping>
+    where
+        AZipper: ZipperMoving + ZipperValues<AV> + ZipperForking<AV>,
+        BZipper: ZipperMoving + ZipperValues<BV> + ZipperForking<BV>,
+        Mapping: for<'a> Fn(Option<&'a AV>, Option<&'a BV>) -> Option<&'a OutV>,
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&OutV>, &[u8]) -> Result<W, E>
+    {
+        let mut z = OverlayZipper::with_mapping(self.a.fork_read_zipper(), self.b.fork_read_zipper(), &self.mapping);
+        summarize_from_focus(&mut z, self.val(), alg_f)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::alloc::GlobalAlloc;
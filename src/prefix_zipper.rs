@@ -1,7 +1,8 @@
 use std::borrow::Cow;
 use fast_slice_utils::{find_prefix_overlap, starts_with};
 use crate::alloc::Allocator;
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
+use crate::morphisms::summarize_from_focus;
 use crate::PathMap;
 use crate::trie_node::{AbstractNodeRef, TrieNodeODRc, TaggedNodeRef};
 use crate::zipper::*;
@@ -328,10 +329,6 @@ impl<'prefix, Z> ZipperMoving for PrefixZipper<'prefix, Z>
         &self.path[self.origin_depth..]
     }
 
-    fn val_count(&self) -> usize {
-        self.source.val_count()
-    }
-
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, patho: K) -> usize {
         if self.position.is_invalid() {
             return 0;
@@ -466,6 +463,27 @@ impl<'prefix, Z> ZipperMoving for PrefixZipper<'prefix, Z>
 }
 
 /// An interface for a [Zipper] to support accessing the full path buffer used to create the zipper
+impl<'prefix, Z, V> TrieSummary<V> for PrefixZipper<'prefix, Z>
+    where
+        Z: ZipperMoving + ZipperForking<V> + TrieSummary<V>
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        match self.position {
+            PrefixPos::Source => self.source.summarize_fallible(alg_f),
+            PrefixPos::PrefixOff {..} => alg_f(&ByteMask::EMPTY, &mut TakableSlice::default(), None, &[]),
+            PrefixPos::Prefix { valid } => {
+                //The source zipper sits at its root while the focus is inside the prefix
+                let mut z = PrefixZipper::new(&self.prefix[self.origin_depth + valid..], self.source.fork_read_zipper());
+                summarize_from_focus(&mut z, None, alg_f)
+            }
+        }
+    }
+}
+
 impl<'prefix, Z> ZipperAbsolutePath for PrefixZipper<'prefix, Z>
     where Z: ZipperAbsolutePath
 {
@@ -1,6 +1,7 @@
 
 use crate::alloc::{Allocator, GlobalAlloc};
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
+use crate::morphisms::summarize_from_focus;
 use crate::trie_node::*;
 use crate::zipper::*;
 use zipper_priv::*;
@@ -200,10 +201,6 @@ impl<'trie, V: Clone + Send + Sync + Unpin + 'trie, A: Allocator + 'trie> Zipper
     fn path(&self) -> &[u8] {
         self.z.path()
     }
-    fn val_count(&self) -> usize {
-        assert!(self.focus_factor() == self.factor_count() - 1);
-        self.z.val_count()
-    }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize {
         let k = k.as_ref();
         let mut descended = 0;
@@ -337,6 +334,36 @@ impl<'trie, V: Clone + Send + Sync + Unpin + 'trie, A: Allocator + 'trie> Zipper
     }
 }
 
+impl<'trie, V: Clone + Send + Sync + Unpin + 'trie, A: Allocator + 'trie> TrieSummary<V> for ProductZipper<'_, 'trie, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        if !self.path_exists() {
+            return alg_f(&ByteMask::EMPTY, &mut TakableSlice::default(), None, &[])
+        }
+        //The product below the focus is the rest of the focus factor, followed by the factors that
+        // haven't been entered yet
+        let remaining_factors = self.secondaries[self.factor_paths.len()..].iter().map(|factor| factor.fork_read_zipper());
+        match self.factor_paths.last() {
+            None => {
+                let mut z = ProductZipperG::new(self.z.fork_read_zipper(), remaining_factors);
+                summarize_from_focus(&mut z, self.val(), alg_f)
+            },
+            Some(&factor_start) => {
+                //Once a secondary factor has been entered, the inner zipper's node stack can't be
+                // forked directly, so we re-locate the focus within the factor.  At a junction, the
+                // focus value comes from the earlier factor, so it's passed in explicitly
+                let factor = &self.secondaries[self.factor_paths.len() - 1];
+                let focus_ref = factor.trie_ref_at_path(&self.path()[factor_start..]);
+                let mut z = ProductZipperG::new(focus_ref.fork_read_zipper(), remaining_factors);
+                summarize_from_focus(&mut z, self.val(), alg_f)
+            }
+        }
+    }
+}
+
 /// A [`witness`](ZipperReadOnlyConditionalValues::witness) type used by [`ProductZipper`]
 pub struct ProductZipperWitness<V: Clone + Send + Sync, A: Allocator>((ReadZipperWitness<V, A>, Vec<TrieRefOwned<V, A>>));
 
@@ -623,6 +650,35 @@ impl<'trie, PrimaryZ, SecondaryZ, V> ZipperValues<V>
     }
 }
 
+impl<'trie, PrimaryZ, SecondaryZ, V> TrieSummary<V>
+    for ProductZipperG<'trie, PrimaryZ, SecondaryZ, V>
+    where
+        V: Clone + Send + Sync,
+        PrimaryZ: ZipperMoving + ZipperValues<V> + ZipperForking<V>,
+        SecondaryZ: ZipperMoving + ZipperValues<V> + ZipperForking<V>,
+{
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        //The product below the focus is the rest of the focus factor, followed by the factors that
+        // haven't been entered yet
+        match self.factor_idx(false) {
+            None => {
+                let remaining_factors = self.secondary.iter().map(|factor| factor.fork_read_zipper());
+                let mut z = ProductZipperG::new(self.primary.fork_read_zipper(), remaining_factors);
+                summarize_from_focus(&mut z, self.val(), alg_f)
+            },
+            Some(idx) => {
+                let remaining_factors = self.secondary[idx+1..].iter().map(|factor| factor.fork_read_zipper());
+                let mut z = ProductZipperG::new(self.secondary[idx].fork_read_zipper(), remaining_factors);
+                summarize_from_focus(&mut z, self.val(), alg_f)
+            }
+        }
+    }
+}
+
 impl<'trie, PrimaryZ, SecondaryZ, V> ZipperReadOnlyValues<'trie, V>
     for ProductZipperG<'trie, PrimaryZ, SecondaryZ, V>
     where
@@ -717,9 +773,6 @@ impl<'trie, PrimaryZ, SecondaryZ, V> ZipperMoving for ProductZipperG<'trie, Prim
     fn path(&self) -> &[u8] {
         self.primary.path()
     }
-    fn val_count(&self) -> usize {
-        unimplemented!("method will probably get removed")
-    }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, path: K) -> usize {
         let mut path = path.as_ref();
         let mut descended = 0;
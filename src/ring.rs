@@ -1,6 +1,8 @@
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::hash::Hash;
+use std::sync::Arc;
+use smallvec::SmallVec;
 
 /// The result of an algebraic operation on elements in a partial lattice
 ///
@@ -606,6 +608,58 @@ pub trait DistributiveLattice {
     //GOAT, We want a psubtract_from (subtract_into??) that operates on a `&mut self`
 }
 
+/// Derive macro to implement [Lattice] on a struct, field-by-field
+///
+/// The generated `pjoin` and `pmeet` apply the operation to each field, and combine the per-field results
+/// with [AlgebraicResult::merge].  Therefore the result is an [Identity](AlgebraicResult::Identity) of an
+/// argument when every field's result is an identity of that argument, and no fields are cloned in that case.
+/// If the operation on any field results in [AlgebraicResult::None], the result for the whole struct is `None`.
+///
+/// Each field type must implement `Lattice` and [Clone].
+///
+/// ```
+/// use std::collections::HashSet;
+/// use pathmap::ring::{AlgebraicResult, Lattice, SELF_IDENT};
+///
+/// #[derive(Clone, Lattice)]
+/// struct Record {
+///     seen: bool,
+///     tags: HashSet<&'static str>,
+/// }
+///
+/// let a = Record{ seen: true, tags: ["x", "y"].into_iter().collect() };
+/// let b = Record{ seen: false, tags: ["y"].into_iter().collect() };
+/// assert_eq!(a.pjoin(&b).identity_mask(), Some(SELF_IDENT));
+/// ```
+pub use pathmap_derive::Lattice;
+
+/// Derive macro to implement [DistributiveLattice] on a struct, field-by-field
+///
+/// The generated `psubtract` subtracts each field, and combines the per-field results in the same way as
+/// the [Lattice](macro@Lattice) derive macro.  Each field type must implement `DistributiveLattice`, [Clone]
+/// and [Default].
+///
+/// A field that is completely subtracted away is set to its [Default] value, which should be the empty
+/// element of the field type, and the struct is only subtracted away if every field is.  This is unlike
+/// `pmeet`, where a single empty field annihilates the whole struct.
+///
+/// ```
+/// use std::collections::HashSet;
+/// use pathmap::ring::{AlgebraicResult, DistributiveLattice, Lattice};
+///
+/// #[derive(Clone, Debug, PartialEq, Lattice, DistributiveLattice)]
+/// struct Record {
+///     count: u64,
+///     tags: HashSet<&'static str>,
+/// }
+///
+/// let a = Record{ count: 1, tags: ["x", "y"].into_iter().collect() };
+/// let b = Record{ count: 1, tags: ["x"].into_iter().collect() };
+/// assert_eq!(a.psubtract(&b), AlgebraicResult::Element(Record{ count: 0, tags: ["y"].into_iter().collect() }));
+/// assert_eq!(a.psubtract(&a), AlgebraicResult::None);
+/// ```
+pub use pathmap_derive::DistributiveLattice;
+
 /// Implements subtract behavior on a reference to a [DistributiveLattice] type
 pub trait DistributiveLatticeRef {
     /// The type that is referenced
@@ -616,6 +670,181 @@ pub trait DistributiveLatticeRef {
     fn psubtract(&self, other: &Self) -> AlgebraicResult<Self::T>;
 }
 
+/// A caller-supplied rule for combining the values found at the same path in two tries
+///
+/// Policies are passed to methods such as [PathMap::join_with](crate::PathMap::join_with) and
+/// [ZipperWriting::join_into_with](crate::zipper::ZipperWriting::join_into_with), and they replace the
+/// [Lattice] or [DistributiveLattice] behavior of `V` for a single operation.  The operation determines which
+/// paths appear in the result, and the policy is only consulted where both tries have a value.
+///
+/// A policy should return [AlgebraicResult::Identity] whenever possible, because that allows the operation to
+/// leave the corresponding part of the trie untouched.  Returning [AlgebraicResult::None] removes the value.
+///
+/// Any `Fn(&V, &V) -> AlgebraicResult<V>` closure can be used as a policy, and `dyn MergePolicy<V>` can be
+/// used to choose a policy at runtime.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::ring::{AlgebraicResult, TakeOther};
+///
+/// let a: PathMap<u64> = [("x", 1), ("y", 2)].into_iter().collect();
+/// let b: PathMap<u64> = [("y", 40), ("z", 3)].into_iter().collect();
+///
+/// let sum = a.join_with(&b, &|a: &u64, b: &u64| AlgebraicResult::Element(a + b));
+/// assert_eq!(sum.get("y"), Some(&42));
+///
+/// let last_writer_wins = a.join_with(&b, &TakeOther);
+/// assert_eq!(last_writer_wins.get("y"), Some(&40));
+/// ```
+pub trait MergePolicy<V> {
+    /// Combines `self_val`, from the trie being operated on, with `other_val`, from the argument trie
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V>;
+}
+
+impl<V, F> MergePolicy<V> for F where F: Fn(&V, &V) -> AlgebraicResult<V> {
+    #[inline]
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V> {
+        self(self_val, other_val)
+    }
+}
+
+/// A [MergePolicy] that combines values with [Lattice::pjoin]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct JoinPolicy;
+
+impl<V: Lattice> MergePolicy<V> for JoinPolicy {
+    #[inline]
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V> {
+        self_val.pjoin(other_val)
+    }
+}
+
+/// A [MergePolicy] that combines values with [Lattice::pmeet]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct MeetPolicy;
+
+impl<V: Lattice> MergePolicy<V> for MeetPolicy {
+    #[inline]
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V> {
+        self_val.pmeet(other_val)
+    }
+}
+
+/// A [MergePolicy] that combines values with [DistributiveLattice::psubtract]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SubtractPolicy;
+
+impl<V: DistributiveLattice> MergePolicy<V> for SubtractPolicy {
+    #[inline]
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V> {
+        self_val.psubtract(other_val)
+    }
+}
+
+/// A [MergePolicy] that always keeps the value from `self`, aka "first-writer-wins"
+#[derive(Clone, Copy, Debug, Default)]
+pub struct KeepSelf;
+
+impl<V> MergePolicy<V> for KeepSelf {
+    #[inline]
+    fn merge(&self, _self_val: &V, _other_val: &V) -> AlgebraicResult<V> {
+        AlgebraicResult::Identity(SELF_IDENT)
+    }
+}
+
+/// A [MergePolicy] that always takes the value from `other`, aka "last-writer-wins"
+#[derive(Clone, Copy, Debug, Default)]
+pub struct TakeOther;
+
+impl<V> MergePolicy<V> for TakeOther {
+    #[inline]
+    fn merge(&self, _self_val: &V, _other_val: &V) -> AlgebraicResult<V> {
+        AlgebraicResult::Identity(COUNTER_IDENT)
+    }
+}
+
+/// A [MergePolicy] that chooses one of the two values using a comparator, keeping whichever value compares
+/// greater, or the value from `self` if they compare equal
+///
+/// This is useful for value types that are combined by choosing between them, such as [String], when the
+/// ordering used by their [Lattice] impl isn't the one that's wanted.  Reverse the comparator to choose the
+/// lesser value instead.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::ring::ChooseBy;
+///
+/// let a: PathMap<String> = [("x", "pear".to_string())].into_iter().collect();
+/// let b: PathMap<String> = [("x", "fig".to_string())].into_iter().collect();
+///
+/// let longest = a.join_with(&b, &ChooseBy(|a: &String, b: &String| a.len().cmp(&b.len())));
+/// assert_eq!(longest.get("x").map(String::as_str), Some("pear"));
+///
+/// let shortest = a.join_with(&b, &ChooseBy(|a: &String, b: &String| b.len().cmp(&a.len())));
+/// assert_eq!(shortest.get("x").map(String::as_str), Some("fig"));
+/// ```
+#[derive(Clone, Copy, Debug, Default)]
+pub struct ChooseBy<F>(pub F);
+
+impl<V, F> MergePolicy<V> for ChooseBy<F> where F: Fn(&V, &V) -> core::cmp::Ordering {
+    #[inline]
+    fn merge(&self, self_val: &V, other_val: &V) -> AlgebraicResult<V> {
+        match (self.0)(self_val, other_val) {
+            core::cmp::Ordering::Less => AlgebraicResult::Identity(COUNTER_IDENT),
+            _ => AlgebraicResult::Identity(SELF_IDENT),
+        }
+    }
+}
+
+/// Implements weighted algebraic behavior for a value type, where the values are weights combined under a
+/// [semiring](https://en.wikipedia.org/wiki/Semiring) rather than a lattice
+///
+/// In a weighted trie, [plus](Semiring::plus) is used to combine the weights of alternative paths that
+/// arrive at the same place, and [times](Semiring::times) is used to combine the weights of paths that are
+/// concatenated.  A value for which [is_zero](Semiring::is_zero) returns `true` is treated as an absent path
+/// and will not be stored.
+///
+/// Implementations must uphold the semiring laws: `plus` is associative and commutative with [zero](Semiring::zero)
+/// as its identity, `times` is associative with [one](Semiring::one) as its identity, `times` distributes over
+/// `plus`, and `zero` annihilates under `times`.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::ring::Tropical;
+///
+/// let a: PathMap<Tropical> = [("a", Tropical(1.0)), ("ab", Tropical(2.0))].into_iter().collect();
+/// let b: PathMap<Tropical> = [("bc", Tropical(5.0)), ("c", Tropical(0.5))].into_iter().collect();
+///
+/// //"abc" is reachable as "a"+"bc" and as "ab"+"c", and the cheaper of the two costs is kept
+/// let product = a.weighted_product(&b);
+/// assert_eq!(product.get("abc"), Some(&Tropical(2.5)));
+/// assert_eq!(product.best_weighted_path(), Some((b"ac".to_vec(), Tropical(1.5))));
+/// ```
+pub trait Semiring: Sized {
+    /// Returns the identity of [plus](Semiring::plus), which is also the annihilator of [times](Semiring::times)
+    fn zero() -> Self;
+
+    /// Returns the identity of [times](Semiring::times)
+    fn one() -> Self;
+
+    /// Returns `true` if `self` is equal to [zero](Semiring::zero)
+    fn is_zero(&self) -> bool;
+
+    /// Combines the weights of two alternative paths
+    fn plus(&self, other: &Self) -> Self;
+
+    /// Combines the weights of two concatenated paths, where `self` is the weight of the prefix
+    fn times(&self, other: &Self) -> Self;
+}
+
+/// A [Semiring] with an order that identifies the best of two weights, used to extract a best path
+///
+/// For a selective semiring such as [Tropical], the preferred weight is the one chosen by [Semiring::plus]
+pub trait OrderedSemiring: Semiring {
+    /// Returns `true` if `self` is strictly preferred to `other`
+    fn is_better(&self, other: &Self) -> bool;
+}
+
 // =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
 // Private traits
 // =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
@@ -793,6 +1022,35 @@ impl<V: DistributiveLattice> DistributiveLattice for Box<V> {
     }
 }
 
+// =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
+// =-*   `Arc<V>`                                                                                     *-=
+// NOTE: Two `Arc`s pointing to the same allocation are identities of each other, so the operation on the
+// inner type is skipped entirely in that case.
+
+impl<V: Lattice> Lattice for Arc<V> {
+    fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> {
+        if Arc::ptr_eq(self, other) {
+            return AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT)
+        }
+        self.as_ref().pjoin(other.as_ref()).map(|result| Arc::new(result))
+    }
+    fn pmeet(&self, other: &Self) -> AlgebraicResult<Self> {
+        if Arc::ptr_eq(self, other) {
+            return AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT)
+        }
+        self.as_ref().pmeet(other.as_ref()).map(|result| Arc::new(result))
+    }
+}
+
+impl<V: DistributiveLattice> DistributiveLattice for Arc<V> {
+    fn psubtract(&self, other: &Self) -> AlgebraicResult<Self> {
+        if Arc::ptr_eq(self, other) {
+            return AlgebraicResult::None
+        }
+        self.as_ref().psubtract(other.as_ref()).map(|result| Arc::new(result))
+    }
+}
+
 // =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
 // =-*   `&V`                                                                                         *-=
 
@@ -906,7 +1164,183 @@ impl Lattice for bool {
 }
 
 // =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
-// =-*   `SetLattice<K>`, including `HashMap<K, V>`, `HashSet<K>`, etc.                               *-=
+// =-*   `String`                                                                                     *-=
+// NOTE: Strings are treated as a chain, ordered lexicographically.  The join chooses the greater of the two
+// arguments and the meet chooses the lesser, so the result is always an identity of one of the arguments.
+// To choose between strings by some other rule, use a `ChooseBy` policy with the `_with` operations.
+
+impl Lattice for String {
+    fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> {
+        match self.cmp(other) {
+            core::cmp::Ordering::Equal => AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT),
+            core::cmp::Ordering::Greater => AlgebraicResult::Identity(SELF_IDENT),
+            core::cmp::Ordering::Less => AlgebraicResult::Identity(COUNTER_IDENT),
+        }
+    }
+    fn pmeet(&self, other: &Self) -> AlgebraicResult<Self> {
+        match self.cmp(other) {
+            core::cmp::Ordering::Equal => AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT),
+            core::cmp::Ordering::Less => AlgebraicResult::Identity(SELF_IDENT),
+            core::cmp::Ordering::Greater => AlgebraicResult::Identity(COUNTER_IDENT),
+        }
+    }
+}
+
+impl DistributiveLattice for String {
+    fn psubtract(&self, other: &Self) -> AlgebraicResult<Self> {
+        if self == other {
+            AlgebraicResult::None
+        } else {
+            AlgebraicResult::Identity(SELF_IDENT)
+        }
+    }
+}
+
+// =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
+// =-*   Tuples                                                                                       *-=
+// NOTE: Tuples are joined and met component-wise.  If any component annihilates, the whole tuple does, in
+// the same way the product of two sets is empty if either set is empty.  There is no `DistributiveLattice`
+// impl because the difference between two products generally can't be expressed as a single product.
+
+/// Internal function to combine the component results of an operation on a tuple.  Returns the combined
+/// identity mask, or `None` if a new element needs to be constructed
+#[inline]
+fn tuple_ident_mask(masks: &[Option<u64>]) -> Option<u64> {
+    let mut combined = SELF_IDENT | COUNTER_IDENT;
+    for mask in masks {
+        combined &= (*mask)?;
+    }
+    if combined > 0 { Some(combined) } else { None }
+}
+
+macro_rules! tuple_lattice {
+    ( $( $t:ident $idx:tt ),+ ) => {
+        impl<$( $t: Lattice + Clone ),+> Lattice for ( $( $t, )+ ) {
+            fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> {
+                let results = ( $( self.$idx.pjoin(&other.$idx), )+ );
+                tuple_lattice!(@integrate self, other, results, $( $idx ),+)
+            }
+            fn pmeet(&self, other: &Self) -> AlgebraicResult<Self> {
+                let results = ( $( self.$idx.pmeet(&other.$idx), )+ );
+                tuple_lattice!(@integrate self, other, results, $( $idx ),+)
+            }
+        }
+    };
+    (@integrate $self:ident, $other:ident, $results:ident, $( $idx:tt ),+) => {{
+        if $( $results.$idx.is_none() )||+ {
+            return AlgebraicResult::None
+        }
+        match tuple_ident_mask(&[ $( $results.$idx.identity_mask(), )+ ]) {
+            Some(mask) => AlgebraicResult::Identity(mask),
+            None => AlgebraicResult::Element(( $( $results.$idx.unwrap([&$self.$idx, &$other.$idx]), )+ )),
+        }
+    }};
+}
+
+tuple_lattice!(A 0, B 1);
+tuple_lattice!(A 0, B 1, C 2);
+tuple_lattice!(A 0, B 1, C 2, D 3);
+tuple_lattice!(A 0, B 1, C 2, D 3, E 4);
+tuple_lattice!(A 0, B 1, C 2, D 3, E 4, F 5);
+
+// =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
+// =-*   Semirings                                                                                    *-=
+
+/// The tropical (min, +) [Semiring], where each weight is a cost and the best path is the cheapest one
+///
+/// `plus` takes the minimum, `times` adds, [zero](Semiring::zero) is `+inf` and [one](Semiring::one) is `0.0`
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct Tropical(pub f64);
+
+impl Semiring for Tropical {
+    #[inline]
+    fn zero() -> Self { Self(f64::INFINITY) }
+    #[inline]
+    fn one() -> Self { Self(0.0) }
+    #[inline]
+    fn is_zero(&self) -> bool { self.0 == f64::INFINITY }
+    #[inline]
+    fn plus(&self, other: &Self) -> Self { Self(self.0.min(other.0)) }
+    #[inline]
+    fn times(&self, other: &Self) -> Self { Self(self.0 + other.0) }
+}
+
+impl OrderedSemiring for Tropical {
+    #[inline]
+    fn is_better(&self, other: &Self) -> bool { self.0 < other.0 }
+}
+
+/// The log-probability [Semiring], where each weight is the natural log of a probability
+///
+/// `plus` adds the probabilities (computed with log-sum-exp), `times` multiplies them by adding the logs,
+/// [zero](Semiring::zero) is `-inf` and [one](Semiring::one) is `0.0`.  The best path is the most probable one.
+#[derive(Clone, Copy, Debug, PartialEq, PartialOrd)]
+pub struct LogProb(pub f64);
+
+impl LogProb {
+    /// Makes a `LogProb` from a probability
+    #[inline]
+    pub fn from_prob(p: f64) -> Self { Self(p.ln()) }
+
+    /// Returns the probability represented by the `LogProb`
+    #[inline]
+    pub fn prob(&self) -> f64 { self.0.exp() }
+}
+
+impl Semiring for LogProb {
+    #[inline]
+    fn zero() -> Self { Self(f64::NEG_INFINITY) }
+    #[inline]
+    fn one() -> Self { Self(0.0) }
+    #[inline]
+    fn is_zero(&self) -> bool { self.0 == f64::NEG_INFINITY }
+    #[inline]
+    fn plus(&self, other: &Self) -> Self {
+        if self.is_zero() {
+            return *other
+        }
+        if other.is_zero() {
+            return *self
+        }
+        let max = self.0.max(other.0);
+        Self(max + (-(self.0 - other.0).abs()).exp().ln_1p())
+    }
+    #[inline]
+    fn times(&self, other: &Self) -> Self { Self(self.0 + other.0) }
+}
+
+impl OrderedSemiring for LogProb {
+    #[inline]
+    fn is_better(&self, other: &Self) -> bool { self.0 > other.0 }
+}
+
+/// The counting (+, *) [Semiring] over the natural numbers, e.g. to count the number of ways a path can be
+/// constructed
+///
+/// Arithmetic saturates at `u64::MAX`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Counting(pub u64);
+
+impl Semiring for Counting {
+    #[inline]
+    fn zero() -> Self { Self(0) }
+    #[inline]
+    fn one() -> Self { Self(1) }
+    #[inline]
+    fn is_zero(&self) -> bool { self.0 == 0 }
+    #[inline]
+    fn plus(&self, other: &Self) -> Self { Self(self.0.saturating_add(other.0)) }
+    #[inline]
+    fn times(&self, other: &Self) -> Self { Self(self.0.saturating_mul(other.0)) }
+}
+
+impl OrderedSemiring for Counting {
+    #[inline]
+    fn is_better(&self, other: &Self) -> bool { self.0 > other.0 }
+}
+
+// =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
+// =-*   `SetLattice<K>`, including `HashMap<K, V>`, `HashSet<K>`, `BTreeMap<K, V>`, etc.              *-=
 
 /// Implemented on an unordered set type, (e.g. [HashMap], [HashSet], etc.) to get automatic implementations
 /// of the [Lattice] and [DistributiveLattice] traits on the set type with the [set_lattice](crate::set_lattice) and
@@ -1191,11 +1625,238 @@ impl<'a, K> Iterator for HashSetIterWrapper<'a, K> {
 set_lattice!(HashSet<K>);
 set_dist_lattice!(HashSet<K>);
 
+impl<K: Clone + Ord, V: Clone + Lattice> SetLattice for BTreeMap<K, V> {
+    type K = K;
+    type V = V;
+    type Iter<'a> = std::collections::btree_map::Iter<'a, K, V> where K: 'a, V: 'a;
+    fn with_capacity(_capacity: usize) -> Self { Self::new() }
+    fn len(&self) -> usize { self.len() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn contains_key(&self, key: &Self::K) -> bool { self.contains_key(key) }
+    fn insert(&mut self, key: Self::K, val: Self::V) { self.insert(key, val); }
+    fn get(&self, key: &Self::K) -> Option<&Self::V> { self.get(key) }
+    fn replace(&mut self, key: &Self::K, val: Self::V) { *self.get_mut(key).unwrap() = val }
+    fn remove(&mut self, key: &Self::K) { self.remove(key); }
+    fn iter<'a>(&'a self) -> Self::Iter<'a> { self.iter() }
+    fn shrink_to_fit(&mut self) { }
+}
+
+set_lattice!(BTreeMap<K, V>);
+set_dist_lattice!(BTreeMap<K, V>);
+
+impl<K: Clone + Ord> SetLattice for BTreeSet<K> {
+    type K = K;
+    type V = ();
+    type Iter<'a> = BTreeSetIterWrapper<'a, K> where K: 'a;
+    fn with_capacity(_capacity: usize) -> Self { Self::new() }
+    fn len(&self) -> usize { self.len() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn contains_key(&self, key: &Self::K) -> bool { self.contains(key) }
+    fn insert(&mut self, key: Self::K, _val: Self::V) { self.insert(key); }
+    fn get(&self, key: &Self::K) -> Option<&Self::V> { self.get(key).map(|_| &()) }
+    fn replace(&mut self, key: &Self::K, _val: Self::V) { debug_assert!(self.contains(key)); /* a noop since we can assume the key already exists */ }
+    fn remove(&mut self, key: &Self::K) { self.remove(key); }
+    fn iter<'a>(&'a self) -> Self::Iter<'a> { BTreeSetIterWrapper(self.iter()) }
+    fn shrink_to_fit(&mut self) { }
+}
+
+/// The [SetLattice::Iter] type for [BTreeSet], which pairs each element with the unit value
+pub struct BTreeSetIterWrapper<'a, K> (std::collections::btree_set::Iter<'a, K>);
+
+impl<'a, K> Iterator for BTreeSetIterWrapper<'a, K> {
+    type Item = (&'a K, &'a());
+    fn next(&mut self) -> Option<(&'a K, &'a())> {
+        self.0.next().map(|key| (key, &()))
+    }
+}
+
+set_lattice!(BTreeSet<K>);
+set_dist_lattice!(BTreeSet<K>);
+
+// =-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-==-**-=
+// =-*   Sorted `Vec<K>` and `SmallVec<[K; N]>`                                                       *-=
+// NOTE: A `Vec` or `SmallVec` is treated as a set of elements, and it must be sorted in ascending order
+// with no duplicates.  The algebraic operations are implemented with a linear merge rather than with the
+// `set_lattice` macros, to avoid repeated insertions into the middle of the vec.
+// Since the operations are linear anyway, they check that the arguments are sorted sets in release builds
+// as well, and panic rather than silently produce a corrupt set from a vec that was never meant to be one.
+
+/// Internal function to panic if `set` isn't sorted in ascending order with no duplicates
+#[inline]
+fn check_sorted_set<K: Ord>(set: &[K]) {
+    assert!(set.windows(2).all(|pair| pair[0] < pair[1]),
+        "a Vec or SmallVec used as a set must be sorted in ascending order, with no duplicates");
+}
+
+/// Internal function to count the elements that are only in `a`, only in `b`, and in both sorted sets
+fn sorted_set_overlap<K: Ord>(a: &[K], b: &[K]) -> (usize, usize, usize) {
+    check_sorted_set(a);
+    check_sorted_set(b);
+    let (mut a_idx, mut b_idx, mut common) = (0, 0, 0);
+    while a_idx < a.len() && b_idx < b.len() {
+        match a[a_idx].cmp(&b[b_idx]) {
+            core::cmp::Ordering::Less => a_idx += 1,
+            core::cmp::Ordering::Greater => b_idx += 1,
+            core::cmp::Ordering::Equal => {
+                a_idx += 1;
+                b_idx += 1;
+                common += 1;
+            }
+        }
+    }
+    (a.len() - common, b.len() - common, common)
+}
+
+/// Internal function to merge two sorted sets, keeping the elements selected by the flags
+fn sorted_set_merge<K: Ord + Clone, S: Default + Extend<K>>(a: &[K], b: &[K], keep_a_only: bool, keep_b_only: bool, keep_common: bool) -> S {
+    let mut result = S::default();
+    let (mut a_idx, mut b_idx) = (0, 0);
+    while a_idx < a.len() && b_idx < b.len() {
+        match a[a_idx].cmp(&b[b_idx]) {
+            core::cmp::Ordering::Less => {
+                if keep_a_only { result.extend(core::iter::once(a[a_idx].clone())) }
+                a_idx += 1;
+            },
+            core::cmp::Ordering::Greater => {
+                if keep_b_only { result.extend(core::iter::once(b[b_idx].clone())) }
+                b_idx += 1;
+            },
+            core::cmp::Ordering::Equal => {
+                if keep_common { result.extend(core::iter::once(a[a_idx].clone())) }
+                a_idx += 1;
+                b_idx += 1;
+            }
+        }
+    }
+    if keep_a_only { result.extend(a[a_idx..].iter().cloned()) }
+    if keep_b_only { result.extend(b[b_idx..].iter().cloned()) }
+    result
+}
+
+/// Internal function to implement `pjoin` on sorted sets
+fn sorted_set_pjoin<K: Ord + Clone, S: Default + Extend<K>>(a: &[K], b: &[K]) -> AlgebraicResult<S> {
+    let (a_only, b_only, common) = sorted_set_overlap(a, b);
+    if a_only + b_only + common == 0 {
+        return AlgebraicResult::None
+    }
+    let mask = if b_only == 0 { SELF_IDENT } else { 0 } | if a_only == 0 { COUNTER_IDENT } else { 0 };
+    if mask > 0 {
+        AlgebraicResult::Identity(mask)
+    } else {
+        AlgebraicResult::Element(sorted_set_merge(a, b, true, true, true))
+    }
+}
+
+/// Internal function to implement `pmeet` on sorted sets
+fn sorted_set_pmeet<K: Ord + Clone, S: Default + Extend<K>>(a: &[K], b: &[K]) -> AlgebraicResult<S> {
+    let (a_only, b_only, common) = sorted_set_overlap(a, b);
+    if common == 0 {
+        return AlgebraicResult::None
+    }
+    let mask = if a_only == 0 { SELF_IDENT } else { 0 } | if b_only == 0 { COUNTER_IDENT } else { 0 };
+    if mask > 0 {
+        AlgebraicResult::Identity(mask)
+    } else {
+        AlgebraicResult::Element(sorted_set_merge(a, b, false, false, true))
+    }
+}
+
+/// Internal function to implement `psubtract` on sorted sets
+fn sorted_set_psubtract<K: Ord + Clone, S: Default + Extend<K>>(a: &[K], b: &[K]) -> AlgebraicResult<S> {
+    let (a_only, _b_only, common) = sorted_set_overlap(a, b);
+    if a_only == 0 {
+        AlgebraicResult::None
+    } else if common == 0 {
+        AlgebraicResult::Identity(SELF_IDENT)
+    } else {
+        AlgebraicResult::Element(sorted_set_merge(a, b, true, false, false))
+    }
+}
+
+impl<K: Clone + Ord> SetLattice for Vec<K> {
+    type K = K;
+    type V = ();
+    type Iter<'a> = SortedSetIterWrapper<'a, K> where K: 'a;
+    fn with_capacity(capacity: usize) -> Self { Self::with_capacity(capacity) }
+    fn len(&self) -> usize { self.len() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn contains_key(&self, key: &Self::K) -> bool { self.binary_search(key).is_ok() }
+    fn insert(&mut self, key: Self::K, _val: Self::V) {
+        if let Err(idx) = self.binary_search(&key) {
+            Vec::insert(self, idx, key);
+        }
+    }
+    fn get(&self, key: &Self::K) -> Option<&Self::V> { self.binary_search(key).ok().map(|_| &()) }
+    fn replace(&mut self, key: &Self::K, _val: Self::V) { debug_assert!(self.binary_search(key).is_ok()); /* a noop since we can assume the key already exists */ }
+    fn remove(&mut self, key: &Self::K) {
+        if let Ok(idx) = self.binary_search(key) {
+            Vec::remove(self, idx);
+        }
+    }
+    fn iter<'a>(&'a self) -> Self::Iter<'a> { SortedSetIterWrapper(self[..].iter()) }
+    fn shrink_to_fit(&mut self) { Vec::shrink_to_fit(self); }
+}
+
+impl<K: Clone + Ord> Lattice for Vec<K> {
+    fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_pjoin(self, other) }
+    fn pmeet(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_pmeet(self, other) }
+}
+
+impl<K: Clone + Ord> DistributiveLattice for Vec<K> {
+    fn psubtract(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_psubtract(self, other) }
+}
+
+impl<Arr: smallvec::Array> SetLattice for SmallVec<Arr> where Arr::Item: Clone + Ord {
+    type K = Arr::Item;
+    type V = ();
+    type Iter<'a> = SortedSetIterWrapper<'a, Arr::Item> where Arr: 'a;
+    fn with_capacity(capacity: usize) -> Self { Self::with_capacity(capacity) }
+    fn len(&self) -> usize { self.len() }
+    fn is_empty(&self) -> bool { self.is_empty() }
+    fn contains_key(&self, key: &Self::K) -> bool { self.binary_search(key).is_ok() }
+    fn insert(&mut self, key: Self::K, _val: Self::V) {
+        if let Err(idx) = self.binary_search(&key) {
+            SmallVec::insert(self, idx, key);
+        }
+    }
+    fn get(&self, key: &Self::K) -> Option<&Self::V> { self.binary_search(key).ok().map(|_| &()) }
+    fn replace(&mut self, key: &Self::K, _val: Self::V) { debug_assert!(self.binary_search(key).is_ok()); /* a noop since we can assume the key already exists */ }
+    fn remove(&mut self, key: &Self::K) {
+        if let Ok(idx) = self.binary_search(key) {
+            SmallVec::remove(self, idx);
+        }
+    }
+    fn iter<'a>(&'a self) -> Self::Iter<'a> { SortedSetIterWrapper(self[..].iter()) }
+    fn shrink_to_fit(&mut self) { SmallVec::shrink_to_fit(self); }
+}
+
+impl<Arr: smallvec::Array> Lattice for SmallVec<Arr> where Arr::Item: Clone + Ord {
+    fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_pjoin(self, other) }
+    fn pmeet(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_pmeet(self, other) }
+}
+
+impl<Arr: smallvec::Array> DistributiveLattice for SmallVec<Arr> where Arr::Item: Clone + Ord {
+    fn psubtract(&self, other: &Self) -> AlgebraicResult<Self> { sorted_set_psubtract(self, other) }
+}
+
+/// The [SetLattice::Iter] type for sorted [Vec]s and [SmallVec]s, which pairs each element with the unit value
+pub struct SortedSetIterWrapper<'a, K> (core::slice::Iter<'a, K>);
+
+impl<'a, K> Iterator for SortedSetIterWrapper<'a, K> {
+    type Item = (&'a K, &'a());
+    fn next(&mut self) -> Option<(&'a K, &'a())> {
+        self.0.next().map(|key| (key, &()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use std::collections::{HashSet, HashMap};
-    use crate::ring::Lattice;
-    use super::{AlgebraicResult, SetLattice, SELF_IDENT, COUNTER_IDENT};
+    use std::collections::{BTreeMap, BTreeSet, HashSet, HashMap};
+    use std::sync::Arc;
+    use smallvec::SmallVec;
+    use crate as pathmap;
+    use crate::ring::{DistributiveLattice, Lattice};
+    use super::{AlgebraicResult, SetLattice, SELF_IDENT, COUNTER_IDENT, Semiring, OrderedSemiring, Tropical, LogProb, Counting, ChooseBy, MergePolicy};
 
     #[test]
     fn set_lattice_join_test1() {
@@ -1418,10 +2079,219 @@ mod tests {
         let meet_result = b.pmeet(&meet);
         assert_eq!(meet_result.identity_mask().unwrap(), COUNTER_IDENT);
     }
+
+    #[derive(Clone, Debug, PartialEq, Lattice, DistributiveLattice)]
+    struct DerivedRecord {
+        flag: bool,
+        tags: Vec<u8>,
+        name: String,
+    }
+
+    #[derive(Clone, Debug, Default, PartialEq, Lattice, DistributiveLattice)]
+    struct DerivedTuple(bool, Vec<u8>);
+
+    #[derive(Clone, Debug, PartialEq, Lattice, DistributiveLattice)]
+    struct DerivedUnit;
+
+    #[derive(Clone, Debug, Default, PartialEq, Lattice, DistributiveLattice)]
+    struct DerivedNested {
+        flag: bool,
+        inner: DerivedTuple,
+    }
+
+    #[test]
+    fn derived_lattice_test1() {
+        let a = DerivedRecord{ flag: true, tags: vec![1, 2], name: "b".to_string() };
+        let b = DerivedRecord{ flag: false, tags: vec![2, 3], name: "a".to_string() };
+
+        //Mixed field results produce a new element
+        assert_eq!(a.pjoin(&b), AlgebraicResult::Element(DerivedRecord{ flag: true, tags: vec![1, 2, 3], name: "b".to_string() }));
+        assert_eq!(a.pmeet(&b), AlgebraicResult::Element(DerivedRecord{ flag: false, tags: vec![2], name: "a".to_string() }));
+
+        //Identities when every field is an identity of the same argument
+        let bigger = DerivedRecord{ flag: true, tags: vec![1, 2, 3], name: "c".to_string() };
+        assert_eq!(bigger.pjoin(&a), AlgebraicResult::Identity(SELF_IDENT));
+        let smaller = DerivedRecord{ flag: false, tags: vec![1], name: "a".to_string() };
+        assert_eq!(smaller.pjoin(&bigger), AlgebraicResult::Identity(COUNTER_IDENT));
+        assert_eq!(a.pmeet(&bigger), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(a.pjoin(&a.clone()).identity_mask().map(|mask| mask & SELF_IDENT), Some(SELF_IDENT));
+
+        //Fields that are identities of different args need a new element
+        let mixed = DerivedRecord{ flag: false, tags: vec![1, 2, 3], name: "b".to_string() };
+        assert_eq!(a.pjoin(&mixed), AlgebraicResult::Element(DerivedRecord{ flag: true, tags: vec![1, 2, 3], name: "b".to_string() }));
+
+        //A field that annihilates makes the whole struct annihilate
+        let disjoint = DerivedRecord{ flag: true, tags: vec![9], name: "b".to_string() };
+        assert_eq!(a.pmeet(&disjoint), AlgebraicResult::None);
+
+        //Subtract
+        let sub = DerivedRecord{ flag: false, tags: vec![2], name: "z".to_string() };
+        assert_eq!(a.psubtract(&sub), AlgebraicResult::Element(DerivedRecord{ flag: true, tags: vec![1], name: "b".to_string() }));
+        let unrelated = DerivedRecord{ flag: false, tags: vec![7], name: "z".to_string() };
+        assert_eq!(a.psubtract(&unrelated), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(a.psubtract(&a.clone()), AlgebraicResult::None);
+
+        //A field that is subtracted away takes its default value, unless every field is subtracted away
+        let same_flag = DerivedRecord{ flag: true, tags: vec![2], name: "z".to_string() };
+        assert_eq!(a.psubtract(&same_flag), AlgebraicResult::Element(DerivedRecord{ flag: false, tags: vec![1], name: "b".to_string() }));
+        let n_a = DerivedNested{ flag: true, inner: DerivedTuple(true, vec![1, 2]) };
+        let n_b = DerivedNested{ flag: true, inner: DerivedTuple(true, vec![1]) };
+        assert_eq!(n_a.psubtract(&n_b), AlgebraicResult::Element(DerivedNested{ flag: false, inner: DerivedTuple(false, vec![2]) }));
+        assert_eq!(n_b.psubtract(&n_a), AlgebraicResult::None);
+
+        //Tuple and unit structs
+        let t_a = DerivedTuple(false, vec![1]);
+        let t_b = DerivedTuple(true, vec![2]);
+        assert_eq!(t_a.pjoin(&t_b), AlgebraicResult::Element(DerivedTuple(true, vec![1, 2])));
+        assert_eq!(t_a.psubtract(&t_b), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(DerivedUnit.pjoin(&DerivedUnit), AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT));
+        assert_eq!(DerivedUnit.psubtract(&DerivedUnit), AlgebraicResult::None);
+    }
+
+    #[test]
+    fn semiring_test1() {
+        //Tropical
+        assert_eq!(Tropical(3.0).plus(&Tropical(1.5)), Tropical(1.5));
+        assert_eq!(Tropical(3.0).times(&Tropical(1.5)), Tropical(4.5));
+        assert_eq!(Tropical(3.0).plus(&Tropical::zero()), Tropical(3.0));
+        assert_eq!(Tropical(3.0).times(&Tropical::one()), Tropical(3.0));
+        assert!(Tropical(3.0).times(&Tropical::zero()).is_zero());
+        assert!(Tropical(1.0).is_better(&Tropical(2.0)));
+
+        //LogProb
+        let sum = LogProb::from_prob(0.25).plus(&LogProb::from_prob(0.5));
+        assert!((sum.prob() - 0.75).abs() < 1e-12);
+        let product = LogProb::from_prob(0.25).times(&LogProb::from_prob(0.5));
+        assert!((product.prob() - 0.125).abs() < 1e-12);
+        assert_eq!(LogProb::from_prob(0.5).plus(&LogProb::zero()), LogProb::from_prob(0.5));
+        assert!(LogProb::from_prob(0.0).is_zero());
+        assert!(LogProb::zero().plus(&LogProb::zero()).is_zero());
+        assert!(LogProb::from_prob(0.5).is_better(&LogProb::from_prob(0.25)));
+
+        //Counting
+        assert_eq!(Counting(3).plus(&Counting(4)), Counting(7));
+        assert_eq!(Counting(3).times(&Counting(4)), Counting(12));
+        assert_eq!(Counting(u64::MAX).plus(&Counting::one()), Counting(u64::MAX));
+        assert!(Counting(3).times(&Counting::zero()).is_zero());
+    }
+
+    #[test]
+    fn btree_lattice_test1() {
+        let a: BTreeSet<&str> = ["A", "B", "C"].into_iter().collect();
+        let b: BTreeSet<&str> = ["B", "C", "D"].into_iter().collect();
+        let joined = a.pjoin(&b).unwrap([&a, &b]);
+        assert_eq!(joined.iter().copied().collect::<Vec<_>>(), vec!["A", "B", "C", "D"]);
+        assert_eq!(joined.pjoin(&a), AlgebraicResult::Identity(SELF_IDENT));
+        let met = a.pmeet(&b).unwrap([&a, &b]);
+        assert_eq!(met.iter().copied().collect::<Vec<_>>(), vec!["B", "C"]);
+        assert_eq!(met.pmeet(&joined), AlgebraicResult::Identity(SELF_IDENT));
+        let diff = a.psubtract(&b).unwrap([&a, &b]);
+        assert_eq!(diff.iter().copied().collect::<Vec<_>>(), vec!["A"]);
+        assert_eq!(met.psubtract(&joined), AlgebraicResult::None);
+
+        let mut a = BTreeMap::new();
+        let mut b = BTreeMap::new();
+        a.insert("1", true);
+        a.insert("2", false);
+        b.insert("2", true);
+        b.insert("3", false);
+        let joined = a.pjoin(&b).unwrap([&a, &b]);
+        assert_eq!(joined.len(), 3);
+        assert_eq!(joined.get("2"), Some(&true));
+        let met = a.pmeet(&b).unwrap([&a, &b]);
+        assert_eq!(met.len(), 1);
+        assert_eq!(met.get("2"), Some(&false));
+        assert!(a.pmeet(&a.clone()).is_identity());
+    }
+
+    #[test]
+    fn sorted_vec_lattice_test1() {
+        let a = vec![1, 3, 5, 7];
+        let b = vec![3, 4, 5];
+        assert_eq!(a.pjoin(&b), AlgebraicResult::Element(vec![1, 3, 4, 5, 7]));
+        assert_eq!(a.pmeet(&b), AlgebraicResult::Element(vec![3, 5]));
+        assert_eq!(a.psubtract(&b), AlgebraicResult::Element(vec![1, 7]));
+        assert_eq!(b.psubtract(&a), AlgebraicResult::Element(vec![4]));
+
+        //Identities
+        let sub = vec![3, 5];
+        assert_eq!(a.pjoin(&sub), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(sub.pjoin(&a), AlgebraicResult::Identity(COUNTER_IDENT));
+        assert_eq!(a.pmeet(&sub), AlgebraicResult::Identity(COUNTER_IDENT));
+        assert_eq!(a.pmeet(&a.clone()), AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT));
+        assert_eq!(a.psubtract(&vec![2, 4]), AlgebraicResult::Identity(SELF_IDENT));
+
+        //Empty results
+        assert_eq!(a.pmeet(&vec![2, 4]), AlgebraicResult::None);
+        assert_eq!(sub.psubtract(&a), AlgebraicResult::None);
+        assert_eq!(Vec::<u8>::new().pjoin(&vec![]), AlgebraicResult::None);
+
+        //The same operations through SmallVec
+        let a: SmallVec<[u8; 4]> = SmallVec::from_slice(&[1, 3, 5, 7]);
+        let b: SmallVec<[u8; 4]> = SmallVec::from_slice(&[3, 4, 5]);
+        assert_eq!(a.pjoin(&b).unwrap([&a, &b]).as_slice(), &[1, 3, 4, 5, 7]);
+        assert_eq!(a.pmeet(&b).unwrap([&a, &b]).as_slice(), &[3, 5]);
+        assert_eq!(a.psubtract(&b).unwrap([&a, &b]).as_slice(), &[1, 7]);
+
+        //SetLattice keeps the vec sorted
+        let mut set = <Vec<u8> as SetLattice>::with_capacity(4);
+        SetLattice::insert(&mut set, 5, ());
+        SetLattice::insert(&mut set, 1, ());
+        SetLattice::insert(&mut set, 3, ());
+        SetLattice::insert(&mut set, 1, ());
+        assert_eq!(set, vec![1, 3, 5]);
+        SetLattice::remove(&mut set, &3);
+        assert_eq!(set, vec![1, 5]);
+        assert!(SetLattice::contains_key(&set, &5));
+    }
+
+    #[test]
+    #[should_panic]
+    fn sorted_vec_lattice_test2() {
+        //An unsorted vec isn't a set, even in release builds
+        let _ = vec![3u8, 1].pjoin(&vec![2]);
+    }
+
+    #[test]
+    fn string_tuple_arc_lattice_test1() {
+        let apple = "apple".to_string();
+        let banana = "banana".to_string();
+        assert_eq!(apple.pjoin(&banana), AlgebraicResult::Identity(COUNTER_IDENT));
+        assert_eq!(apple.pmeet(&banana), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(apple.pjoin(&apple.clone()), AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT));
+        assert_eq!(apple.psubtract(&banana), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(apple.psubtract(&apple.clone()), AlgebraicResult::None);
+
+        //A ChooseBy policy replaces the lexicographic order
+        let longer = ChooseBy(|a: &String, b: &String| a.len().cmp(&b.len()));
+        assert_eq!(longer.merge(&apple, &banana), AlgebraicResult::Identity(COUNTER_IDENT));
+        assert_eq!(longer.merge(&banana, &apple), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(longer.merge(&apple, &"grape".to_string()), AlgebraicResult::Identity(SELF_IDENT));
+
+        //Tuples combine the components
+        let a = (true, apple.clone());
+        let b = (false, banana.clone());
+        assert_eq!(a.pjoin(&b), AlgebraicResult::Element((true, banana.clone())));
+        assert_eq!(a.pmeet(&b), AlgebraicResult::Element((false, apple.clone())));
+        let c = (true, banana.clone());
+        assert_eq!(c.pjoin(&b), AlgebraicResult::Identity(SELF_IDENT));
+        assert_eq!(b.pjoin(&c), AlgebraicResult::Identity(COUNTER_IDENT));
+        let sets = (vec![1, 2], vec![3]);
+        assert_eq!(sets.pmeet(&(vec![2], vec![4])), AlgebraicResult::None);
+        assert_eq!(sets.pjoin(&(vec![2], vec![3])), AlgebraicResult::Identity(SELF_IDENT));
+
+        //Arcs to the same allocation are identities without looking inside
+        let arc_a = Arc::new(vec![1, 2, 3]);
+        let arc_b = Arc::new(vec![3, 4]);
+        assert_eq!(arc_a.pjoin(&arc_a.clone()), AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT));
+        assert_eq!(arc_a.psubtract(&arc_a.clone()), AlgebraicResult::None);
+        let joined = arc_a.pjoin(&arc_b).unwrap([&arc_a, &arc_b]);
+        assert_eq!(*joined, vec![1, 2, 3, 4]);
+        assert_eq!(arc_a.pmeet(&Arc::new(vec![1, 2, 3, 4])), AlgebraicResult::Identity(SELF_IDENT));
+    }
 }
 
 //GOAT, do a test for the HashMap impl of psubtract
-//GOAT, do an impl of SetLattice for Vec as an indexed set
 
 
 //GOAT, LatticeCounter and LatticeBitfield should be traits.
@@ -126,6 +126,7 @@ pub fn write_trie<C :Catamorphism<V> ,V: TrieValue>(
   cata.into_cata_jumping_side_effect::<Result<Accumulator, std::io::Error>,_>(
     |bytemask, accumulators, jump_length, maybe_val, origin_path| {
       core::debug_assert!(bytemask.iter().count() == accumulators.len());
+      let accumulators: &mut [_] = accumulators;
       let acc0 = match accumulators { 
         // create nil or branching
         []| [ _, _, ..] => {
@@ -0,0 +1,370 @@
+use crate::meet_zipper::VirtualVal;
+use crate::ring::{AlgebraicResult, MergePolicy, SubtractPolicy, SELF_IDENT};
+use crate::utils::{BitMask, ByteMask};
+use crate::virtual_zipper::{ascend_virtual, step_to_sibling, SyncedSources};
+use crate::zipper::*;
+
+/// Zipper that traverses a virtual trie formed by subtracting the trie of one zipper from another
+///
+/// The zipper follows the paths of `a`, and a value in `a` is hidden where `b` has a value at the same
+/// path, unless the [MergePolicy] returns a remainder.  The remainder is computed the first time it is
+/// requested at each focus, so nothing is allocated unless the policy creates a new value.
+///
+/// Like [PathMap::subtract](crate::PathMap::subtract), the branches whose values were all subtracted are
+/// pruned, so they are missing from the [child_mask](Zipper::child_mask) and [path_exists](Zipper::path_exists)
+/// returns `false` inside them.  Pruning requires visiting the subtries below the focus, in step with `b`,
+/// to find a value that remains.
+///
+/// ```
+/// use pathmap::{PathMap, utils::ByteMask, zipper::*};
+///
+/// let a: PathMap<()> = ["apple", "banana", "cherry"].into_iter().map(|k| (k, ())).collect();
+/// let b: PathMap<()> = ["banana", "durian"].into_iter().map(|k| (k, ())).collect();
+///
+/// let mut z = SubtractZipper::new(a.read_zipper(), b.read_zipper());
+/// let mut contents = vec![];
+/// while z.to_next_val() {
+///     contents.push(z.path().to_vec());
+/// }
+/// assert_eq!(contents, vec![b"apple".to_vec(), b"cherry".to_vec()]);
+///
+/// z.reset();
+/// assert_eq!(z.child_mask(), ByteMask::from_iter([b'a', b'c']));
+/// z.descend_to(b"banana");
+/// assert!(!z.path_exists());
+/// ```
+pub struct SubtractZipper<V, AZipper, BZipper, P> {
+    sources: SyncedSources<(AZipper, BZipper), VirtualVal<V>>,
+    policy: P,
+    /// The visible child masks of the focus and its ancestors, keyed by the length of the path
+    masks: Vec<(usize, ByteMask)>,
+    /// `true` if the focus is inside a pruned branch
+    hidden: bool,
+}
+
+impl<V, AZipper, BZipper> SubtractZipper<V, AZipper, BZipper, SubtractPolicy>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        SubtractPolicy: MergePolicy<V>,
+{
+    /// Creates a new `SubtractZipper` for `a` minus `b`, which subtracts the values with their
+    /// [DistributiveLattice](crate::ring::DistributiveLattice) implementation
+    pub fn new(a: AZipper, b: BZipper) -> Self {
+        Self::with_policy(a, b, SubtractPolicy)
+    }
+}
+
+impl<V, AZipper, BZipper, P> SubtractZipper<V, AZipper, BZipper, P>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    /// Creates a new `SubtractZipper` for `a` minus `b`, using `policy` to compute the remainder where both
+    /// zippers have a value
+    ///
+    /// Both source zippers are reset to their roots.
+    pub fn with_policy(a: AZipper, b: BZipper, policy: P) -> Self {
+        let mut z = Self {
+            sources: SyncedSources::new((a, b)),
+            policy,
+            masks: vec![],
+            hidden: false,
+        };
+        z.moved();
+        z
+    }
+
+    /// Consumes the `SubtractZipper`, and returns the source zippers
+    pub fn into_sources(self) -> (AZipper, BZipper) {
+        self.sources.into_sources()
+    }
+
+    /// Internal method to compute the remainder of the `a` value
+    fn subtract_vals(&self, (a, b): &(AZipper, BZipper)) -> VirtualVal<V> {
+        let Some(a_val) = a.val() else {
+            return VirtualVal::None
+        };
+        let Some(b_val) = b.val() else {
+            return VirtualVal::Source(0)
+        };
+        match self.policy.merge(a_val, b_val) {
+            AlgebraicResult::None => VirtualVal::None,
+            AlgebraicResult::Identity(mask) => {
+                if mask & SELF_IDENT != 0 {
+                    VirtualVal::Source(0)
+                } else {
+                    VirtualVal::Source(1)
+                }
+            },
+            AlgebraicResult::Element(val) => VirtualVal::Owned(val),
+        }
+    }
+
+    /// Internal method to check whether a value of `a` remains at the focus of the sources
+    fn remains_val(&self) -> bool {
+        !matches!(self.subtract_vals(self.sources.sources()), VirtualVal::None)
+    }
+
+    /// Internal method to recompute the pruned state of the focus, which must be called whenever the
+    /// focus moves
+    fn moved(&mut self) {
+        let depth = self.sources.path().len();
+        while self.masks.last().is_some_and(|(mask_depth, _)| *mask_depth > depth) {
+            self.masks.pop();
+        }
+        if self.masks.last().is_none_or(|(mask_depth, _)| *mask_depth < depth) {
+            let mask = self.visible_children(self.sources.sources().0.child_mask());
+            self.masks.push((depth, mask));
+        }
+        self.hidden = depth > 0 && !(self.sources.sources().0.path_exists() && (self.masks.last().unwrap().1 != ByteMask::EMPTY || self.remains_val()));
+    }
+
+    /// Internal method to find the bytes in `candidates` that lead from the focus to a remaining value
+    fn visible_children(&mut self, candidates: ByteMask) -> ByteMask {
+        let mut mask = ByteMask::EMPTY;
+        for byte in candidates.iter() {
+            self.sources.descend_to_byte(byte);
+            if self.subtrie_remains() {
+                mask.set_bit(byte);
+            }
+            self.sources.ascend(1);
+        }
+        mask
+    }
+
+    /// Internal method to search for a remaining value at or below the focus, returning the sources to
+    /// the original focus before returning
+    fn subtrie_remains(&mut self) -> bool {
+        let base_depth = self.sources.path().len();
+        let found = 'search: loop {
+            if self.remains_val() {
+                break true
+            }
+            if let Some(byte) = self.sources.sources().0.child_mask().indexed_bit::<true>(0) {
+                self.sources.descend_to_byte(byte);
+                continue
+            }
+            loop {
+                if self.sources.path().len() == base_depth {
+                    break 'search false
+                }
+                let cur_byte = *self.sources.path().last().unwrap();
+                self.sources.ascend(1);
+                if let Some(byte) = self.sources.sources().0.child_mask().next_bit(cur_byte) {
+                    self.sources.descend_to_byte(byte);
+                    break
+                }
+            }
+        };
+        let depth = self.sources.path().len();
+        self.sources.ascend(depth - base_depth);
+        found
+    }
+}
+
+impl<V, AZipper, BZipper, P> ZipperValues<V> for SubtractZipper<V, AZipper, BZipper, P>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn val(&self) -> Option<&V> {
+        let (a, b) = self.sources.sources();
+        match self.sources.cached(|sources| self.subtract_vals(sources)) {
+            VirtualVal::None => None,
+            VirtualVal::Source(0) => a.val(),
+            VirtualVal::Source(_) => b.val(),
+            VirtualVal::Owned(val) => Some(val),
+        }
+    }
+}
+
+impl<V, AZipper, BZipper, P> Zipper for SubtractZipper<V, AZipper, BZipper, P>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn path_exists(&self) -> bool {
+        !self.hidden && self.sources.sources().0.path_exists()
+    }
+    fn is_val(&self) -> bool {
+        self.val().is_some()
+    }
+    fn child_count(&self) -> usize {
+        self.child_mask().count_bits()
+    }
+    fn child_mask(&self) -> ByteMask {
+        self.masks.last().unwrap().1
+    }
+}
+
+impl<V, AZipper, BZipper, P> ZipperMoving for SubtractZipper<V, AZipper, BZipper, P>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{
+    fn at_root(&self) -> bool {
+        self.sources.at_root()
+    }
+
+    fn reset(&mut self) {
+        self.sources.reset();
+        self.moved();
+    }
+
+    fn path(&self) -> &[u8] {
+        self.sources.path()
+    }
+
+    fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
+        let path = k.as_ref();
+        if path.is_empty() {
+            return
+        }
+        self.sources.descend_to(path);
+        self.moved();
+    }
+
+    fn descend_to_byte(&mut self, k: u8) {
+        self.sources.descend_to_byte(k);
+        self.moved();
+    }
+
+    fn ascend(&mut self, steps: usize) -> bool {
+        let ascended = self.sources.ascend(steps);
+        self.moved();
+        ascended
+    }
+
+    fn ascend_until(&mut self) -> bool {
+        ascend_virtual(self, false, |z, branch_only| { z.sources.jump_up(branch_only); z.moved() })
+    }
+
+    fn ascend_until_branch(&mut self) -> bool {
+        ascend_virtual(self, true, |z, branch_only| { z.sources.jump_up(branch_only); z.moved() })
+    }
+
+    fn to_next_sibling_byte(&mut self) -> bool {
+        step_to_sibling(self, true)
+    }
+
+    fn to_prev_sibling_byte(&mut self) -> bool {
+        step_to_sibling(self, false)
+    }
+}
+
+impl<V, AZipper, BZipper, P> ZipperIteration for SubtractZipper<V, AZipper, BZipper, P>
+    where
+        AZipper: ZipperMoving + ZipperValues<V>,
+        BZipper: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V>,
+{ }
+
+#[cfg(test)]
+mod tests {
+    use crate::PathMap;
+    use crate::alloc::GlobalAlloc;
+    use crate::ring::{AlgebraicResult, SubtractPolicy};
+    use crate::utils::ByteMask;
+    use crate::zipper::*;
+
+    #[test]
+    fn subtract_zipper_test1() {
+        let a: PathMap<u64> = [("a", 5), ("b", 2), ("c", 3), ("c:1", 1), ("c:2", 2)].into_iter().collect();
+        let b: PathMap<u64> = [("a", 1), ("b", 2), ("d", 4)].into_iter().collect();
+
+        //Subtract the numbers, and hide the values that reach zero
+        let policy = |a: &u64, b: &u64| {
+            match a.saturating_sub(*b) {
+                0 => AlgebraicResult::None,
+                rem if rem == *a => AlgebraicResult::Identity(crate::ring::SELF_IDENT),
+                rem => AlgebraicResult::Element(rem),
+            }
+        };
+        let mut z = SubtractZipper::with_policy(a.read_zipper(), b.read_zipper(), policy);
+        let mut contents = vec![];
+        while z.to_next_val() {
+            contents.push((z.path().to_vec(), *z.val().unwrap()));
+        }
+        assert_eq!(contents, vec![(b"a".to_vec(), 4), (b"c".to_vec(), 3), (b"c:1".to_vec(), 1), (b"c:2".to_vec(), 2)]);
+
+        //The branches whose values were all subtracted are pruned, and the paths only in `b` don't exist
+        z.reset();
+        assert_eq!(z.child_mask(), ByteMask::from_iter([b'a', b'c']));
+        z.descend_to(b"b");
+        assert!(!z.path_exists());
+        assert!(!z.is_val());
+        z.reset();
+        z.descend_to(b"d");
+        assert!(!z.path_exists());
+        assert!(!z.is_val());
+
+        //The view follows `a`, even where `b` doesn't exist
+        z.reset();
+        z.descend_to(b"c:x");
+        assert!(!z.path_exists());
+        assert!(z.ascend_until());
+        assert_eq!(z.path(), b"c:");
+    }
+
+    /// Walks every path of the pruned view, and compares it to the map made by [PathMap::subtract]
+    #[test]
+    fn subtract_zipper_test2() {
+        let a: PathMap<()> = ["a:1", "a:2", "b:1", "b:2", "b:3:x", "c", "c:1:y"].into_iter().map(|k| (k, ())).collect();
+        let b: PathMap<()> = ["a:1", "a:2", "b:1", "b:3:x", "c:1:y", "d"].into_iter().map(|k| (k, ())).collect();
+        let expected = a.subtract(&b);
+
+        let mut z = SubtractZipper::new(a.read_zipper(), b.read_zipper());
+        let mut expected_z = expected.read_zipper();
+        assert_eq!(z.child_mask(), ByteMask::from_iter([b'b', b'c']));
+        loop {
+            assert_eq!(z.path(), expected_z.path());
+            assert_eq!(z.child_mask(), expected_z.child_mask());
+            assert_eq!(z.is_val(), expected_z.is_val());
+            let moved = z.to_next_step();
+            assert_eq!(moved, expected_z.to_next_step());
+            if !moved {
+                break
+            }
+        }
+
+        z.descend_to(b"b:");
+        assert_eq!(z.child_mask(), ByteMask::from(b'2'));
+        assert!(!z.to_next_sibling_byte());
+        z.descend_to(b"3");
+        assert!(!z.path_exists());
+        assert!(z.ascend_until_branch());
+        assert_eq!(z.path(), b"");
+        z.descend_to(b"c:");
+        assert!(!z.path_exists());
+        assert!(z.ascend_until());
+        assert_eq!(z.path(), b"c");
+    }
+
+    type SZ<'a, V, A=GlobalAlloc> = SubtractZipper<V, ReadZipperUntracked<'a, 'static, V, A>, ReadZipperUntracked<'a, 'static, V, A>, SubtractPolicy>;
+
+    /// Creates an `a` trie with all the keys, and a `b` trie whose values are all beside the keys
+    fn disjoint_keys(keys: &[&[u8]]) -> (PathMap<()>, PathMap<()>) {
+        let a = keys.iter().map(|key| (key, ())).collect();
+        let b = keys.iter().flat_map(|key| [[key, &[0xF0][..]].concat(), [&key[..key.len() / 2], &[0xF1]].concat()]).map(|key| (key, ())).collect();
+        (a, b)
+    }
+
+    zipper_moving_tests::zipper_moving_tests!(subtract_zipper,
+        disjoint_keys,
+        |tries: &mut (PathMap<()>, PathMap<()>), path: &[u8]| -> SZ<'_, ()> {
+            SubtractZipper::new(tries.0.read_zipper_at_path(path), tries.1.read_zipper_at_path(path))
+        }
+    );
+
+    zipper_iteration_tests::zipper_iteration_tests!(subtract_zipper,
+        disjoint_keys,
+        |tries: &mut (PathMap<()>, PathMap<()>), path: &[u8]| -> SZ<'_, ()> {
+            SubtractZipper::new(tries.0.read_zipper_at_path(path), tries.1.read_zipper_at_path(path))
+        }
+    );
+}
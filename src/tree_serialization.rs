@@ -4,7 +4,7 @@ use std::ptr::slice_from_raw_parts;
 use crate::alloc::Allocator;
 use crate::{zipper, TrieValue};
 use crate::morphisms::{Catamorphism, new_map_from_ana_jumping};
-use crate::utils::{BitMask, ByteMask};
+use crate::utils::{BitMask, ByteMask, TakableSlice};
 use crate::write_zipper::ZipperWriting;
 
 /// WIP
@@ -14,7 +14,7 @@ pub fn serialize_fork<V : TrieValue, RZ : Catamorphism<V>, F: FnMut(usize, &[u8]
         static WRITTEN: UnsafeCell<usize> = UnsafeCell::new(0)
     }
     WRITTEN.with(|w| {
-        rz.into_cata_jumping_side_effect_fallible(|bm: &ByteMask, ws: &mut [usize], jump, _ov: Option<&V>, path: &[u8]| {
+        rz.into_cata_jumping_side_effect_fallible(|bm: &ByteMask, ws: &mut TakableSlice<usize>, jump, _ov: Option<&V>, path: &[u8]| {
             // let bs = bm.iter().collect::<Vec<u8>>();
             // println!("at {path:?} #{} jump {} {:?}", bm.count_bits(), jump, &path[path.len()-jump..]);
 
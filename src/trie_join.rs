@@ -0,0 +1,372 @@
+use crate::utils::{BitMask, ByteMask};
+use crate::zipper::*;
+
+/// Enumerates the bindings of a conjunctive query over multiple tries, using a worst-case-optimal multiway
+/// join, aka a "leapfrog triejoin"
+///
+/// Each factor is a zipper over a trie whose paths are the concatenation of the values of some variables,
+/// and each variable is a fixed number of bytes wide.  For example, the triangle query
+/// `Q(x, y, z) :- R(x, y), S(y, z), T(x, z)` has 3 factors that each bind 2 of the 3 variables.
+///
+/// The variables are bound one byte at a time, in the order specified by `var_order`.  At each byte,
+/// the candidate bytes are the intersection of the [child_mask](Zipper::child_mask)s of every factor
+/// that binds the current variable, and those factors descend in lockstep.  A binding is produced when
+/// every variable is bound, and every factor has arrived at a value.
+///
+/// The variables of each factor must appear in its paths in the same relative order as they appear in
+/// `var_order`.
+///
+/// ```
+/// use pathmap::PathMap;
+/// use pathmap::zipper::TrieJoin;
+///
+/// //Directed edges, where each vertex is one byte
+/// let edges: PathMap<()> = ["ab", "bc", "ac", "cd", "bd"].into_iter().map(|k| (k, ())).collect();
+///
+/// //Triangles: edge(x, y), edge(y, z), edge(x, z)
+/// let factors = [edges.read_zipper(), edges.read_zipper(), edges.read_zipper()];
+/// let mut join = TrieJoin::new(factors, &[&[0, 1], &[1, 2], &[0, 2]], &[1, 1, 1], &[0, 1, 2]);
+/// let mut triangles = vec![];
+/// while join.to_next_binding() {
+///     triangles.push(join.binding().to_vec());
+/// }
+/// assert_eq!(triangles, vec![b"abc".to_vec(), b"bcd".to_vec()]);
+/// ```
+pub struct TrieJoin<Z> {
+    factors: Vec<Z>,
+    /// One entry per byte of the binding, in the order the bytes are bound
+    levels: Vec<JoinLevel>,
+    /// The offset of each variable's bytes within `binding`, indexed by variable
+    var_offsets: Vec<usize>,
+    /// The width of each variable, indexed by variable
+    var_widths: Vec<usize>,
+    /// The bytes that have been bound so far, in the order of `var_order`
+    binding: Vec<u8>,
+    /// The candidate bytes for each bound level, and the index of the next candidate to try
+    stack: Vec<(ByteMask, usize)>,
+    state: JoinState,
+}
+
+/// The factors involved at one byte of the binding
+struct JoinLevel {
+    /// The factors that bind the variable the byte belongs to
+    participants: Vec<usize>,
+    /// The factors whose last byte is bound at this level, and therefore must be at a value
+    completing: Vec<usize>,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum JoinState {
+    Unstarted,
+    AtBinding,
+    Finished,
+}
+
+impl<Z: ZipperMoving> TrieJoin<Z> {
+    /// Creates a new `TrieJoin`
+    ///
+    /// - `factors`: The zippers to join.  Each zipper's focus is the root of its factor
+    /// - `factor_vars`: The variables bound by each factor, in the order they appear in the factor's paths
+    /// - `var_widths`: The width, in bytes, of each variable
+    /// - `var_order`: The order in which to bind the variables
+    ///
+    /// Panics if a variable index is out of range, if a variable isn't bound by any factor, or if a factor's
+    /// variables are not in the same relative order as `var_order`.
+    pub fn new<Factors>(factors: Factors, factor_vars: &[&[usize]], var_widths: &[usize], var_order: &[usize]) -> Self
+        where Factors: IntoIterator<Item=Z>
+    {
+        let factors: Vec<Z> = factors.into_iter().collect();
+        assert_eq!(factors.len(), factor_vars.len(), "factor_vars must have one entry for each factor");
+        let var_cnt = var_widths.len();
+        assert_eq!(var_order.len(), var_cnt, "var_order must contain each variable once");
+
+        //The position of each variable in `var_order`
+        let mut var_rank = vec![usize::MAX; var_cnt];
+        for (rank, &var) in var_order.iter().enumerate() {
+            assert!(var < var_cnt, "variable {var} in var_order is out of range");
+            assert!(var_rank[var] == usize::MAX, "variable {var} appears in var_order more than once");
+            var_rank[var] = rank;
+        }
+
+        //The factors that bind each variable, and the last variable of each factor
+        let mut var_factors = vec![vec![]; var_cnt];
+        let mut factor_last_vars = Vec::with_capacity(factors.len());
+        for (factor_idx, vars) in factor_vars.iter().enumerate() {
+            let mut prev_rank = None;
+            for &var in vars.iter() {
+                assert!(var < var_cnt, "variable {var} bound by factor {factor_idx} is out of range");
+                assert!(prev_rank.map(|prev| prev < var_rank[var]).unwrap_or(true),
+                    "the variables of factor {factor_idx} are not in the same order as var_order");
+                prev_rank = Some(var_rank[var]);
+                var_factors[var].push(factor_idx);
+            }
+            factor_last_vars.push(vars.last().copied());
+        }
+
+        let mut levels = vec![];
+        let mut var_offsets = vec![0; var_cnt];
+        for &var in var_order {
+            assert!(!var_factors[var].is_empty(), "variable {var} isn't bound by any factor");
+            var_offsets[var] = levels.len();
+            for byte_idx in 0..var_widths[var] {
+                let completing = if byte_idx + 1 == var_widths[var] {
+                    var_factors[var].iter().copied().filter(|&factor_idx| factor_last_vars[factor_idx] == Some(var)).collect()
+                } else {
+                    vec![]
+                };
+                levels.push(JoinLevel { participants: var_factors[var].clone(), completing });
+            }
+        }
+
+        Self {
+            factors,
+            levels,
+            var_offsets,
+            var_widths: var_widths.to_vec(),
+            binding: vec![],
+            stack: vec![],
+            state: JoinState::Unstarted,
+        }
+    }
+
+    /// Advances to the next binding that satisfies every factor, returning `true`, or returns `false` if
+    /// there are no more bindings
+    ///
+    /// Bindings are produced in lexicographic order of [binding](Self::binding).
+    pub fn to_next_binding(&mut self) -> bool {
+        match self.state {
+            JoinState::Finished => return false,
+            JoinState::Unstarted => {
+                //Factors that bind no variables must still contain the empty tuple
+                if !self.factors_complete(self.factors_without_vars()) {
+                    self.state = JoinState::Finished;
+                    return false
+                }
+                if self.levels.is_empty() {
+                    self.state = JoinState::AtBinding;
+                    return true
+                }
+                let mask = self.candidate_mask(0);
+                self.stack.push((mask, 0));
+            },
+            JoinState::AtBinding => {
+                if self.levels.is_empty() {
+                    self.state = JoinState::Finished;
+                    return false
+                }
+                self.ascend_level();
+            },
+        }
+
+        loop {
+            let level_idx = self.stack.len() - 1;
+            let (mask, idx) = self.stack.last_mut().unwrap();
+            match mask.indexed_bit::<true>(*idx) {
+                Some(byte) => {
+                    *idx += 1;
+                    for &factor_idx in self.levels[level_idx].participants.iter() {
+                        self.factors[factor_idx].descend_to_byte(byte);
+                    }
+                    self.binding.push(byte);
+                    if !self.factors_complete(self.levels[level_idx].completing.iter().copied()) {
+                        self.ascend_level();
+                        continue
+                    }
+                    if level_idx + 1 == self.levels.len() {
+                        self.state = JoinState::AtBinding;
+                        return true
+                    }
+                    let mask = self.candidate_mask(level_idx + 1);
+                    self.stack.push((mask, 0));
+                },
+                None => {
+                    self.stack.pop();
+                    if self.stack.is_empty() {
+                        self.state = JoinState::Finished;
+                        return false
+                    }
+                    self.ascend_level();
+                }
+            }
+        }
+    }
+
+    /// Returns the bytes of every variable in the current binding, in the order the variables are bound
+    pub fn binding(&self) -> &[u8] {
+        &self.binding
+    }
+
+    /// Returns the bytes bound to the variable at `var_idx` in the current binding
+    pub fn var(&self, var_idx: usize) -> &[u8] {
+        let offset = self.var_offsets[var_idx];
+        &self.binding[offset..offset + self.var_widths[var_idx]]
+    }
+
+    /// Returns a reference to the factor zipper at `factor_idx`
+    ///
+    /// When the join is at a binding, each factor's focus is at the value for the binding.
+    pub fn factor(&self, factor_idx: usize) -> &Z {
+        &self.factors[factor_idx]
+    }
+
+    /// Returns the number of factors in the join
+    pub fn factor_count(&self) -> usize {
+        self.factors.len()
+    }
+
+    /// Consumes the `TrieJoin`, and returns the factor zippers
+    pub fn into_factors(self) -> Vec<Z> {
+        self.factors
+    }
+
+    /// Internal method to compute the candidate bytes at a level
+    fn candidate_mask(&self, level_idx: usize) -> ByteMask {
+        let mut participants = self.levels[level_idx].participants.iter();
+        let mut mask = self.factors[*participants.next().unwrap()].child_mask();
+        for &factor_idx in participants {
+            if mask.is_empty_mask() {
+                break
+            }
+            mask = mask.and(&self.factors[factor_idx].child_mask());
+        }
+        mask
+    }
+
+    /// Internal method to undo the most recent byte of the binding
+    fn ascend_level(&mut self) {
+        let level_idx = self.binding.len() - 1;
+        for &factor_idx in self.levels[level_idx].participants.iter() {
+            self.factors[factor_idx].ascend_byte();
+        }
+        self.binding.pop();
+    }
+
+    /// Internal method to find the factors that don't bind any variables
+    fn factors_without_vars(&self) -> Vec<usize> {
+        let mut bound = vec![false; self.factors.len()];
+        for level in self.levels.iter() {
+            for &factor_idx in level.participants.iter() {
+                bound[factor_idx] = true;
+            }
+        }
+        bound.into_iter().enumerate().filter(|(_, bound)| !bound).map(|(factor_idx, _)| factor_idx).collect()
+    }
+
+    /// Internal method to check that every specified factor is at a value
+    fn factors_complete<I: IntoIterator<Item=usize>>(&self, factor_idxs: I) -> bool {
+        factor_idxs.into_iter().all(|factor_idx| self.factors[factor_idx].is_val())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::PathMap;
+    use crate::zipper::*;
+
+    fn naive_triangles(r: &PathMap<u64>, s: &PathMap<u64>, t: &PathMap<u64>) -> Vec<Vec<u8>> {
+        let mut result = vec![];
+        for (rk, _) in r.iter() {
+            for (sk, _) in s.iter() {
+                if rk[2..4] != sk[0..2] {
+                    continue
+                }
+                let (x, y, z) = (&rk[0..2], &rk[2..4], &sk[2..4]);
+                if t.contains([x, z].concat()) {
+                    result.push([x, y, z].concat());
+                }
+            }
+        }
+        result.sort();
+        result
+    }
+
+    #[test]
+    fn trie_join_test1() {
+        //Three relations over 2-byte variables
+        let mut r = PathMap::<u64>::new();
+        let mut s = PathMap::<u64>::new();
+        let mut t = PathMap::<u64>::new();
+        for i in 0u64..300 {
+            let a = [(i % 5) as u8, (i % 3) as u8];
+            let b = [(i * 7 % 4) as u8, (i % 2) as u8];
+            let c = [(i * 11 % 6) as u8, 0];
+            r.set_val_at([a, b].concat(), i);
+            s.set_val_at([b, c].concat(), i);
+            t.set_val_at([a, c].concat(), i);
+            s.set_val_at([a, [(i % 4) as u8, 1]].concat(), i);
+        }
+        let expected = naive_triangles(&r, &s, &t);
+        assert!(!expected.is_empty());
+
+        //x = 0, y = 1, z = 2
+        let factor_vars: &[&[usize]] = &[&[0, 1], &[1, 2], &[0, 2]];
+        let mut join = TrieJoin::new([r.read_zipper(), s.read_zipper(), t.read_zipper()], factor_vars, &[2, 2, 2], &[0, 1, 2]);
+        let mut bindings = vec![];
+        while join.to_next_binding() {
+            bindings.push(join.binding().to_vec());
+            let (x, y, z) = (join.var(0), join.var(1), join.var(2));
+            assert_eq!(join.factor(0).val(), r.get([x, y].concat()));
+            assert_eq!(join.factor(1).val(), s.get([y, z].concat()));
+            assert_eq!(join.factor(2).val(), t.get([x, z].concat()));
+        }
+        assert_eq!(bindings, expected);
+        assert!(!join.to_next_binding());
+
+        //A different variable ordering requires factors with the variables in that order: z, x, y
+        let t_zx: PathMap<u64> = t.iter().map(|(k, v)| ([&k[2..4], &k[0..2]].concat(), *v)).collect();
+        let s_zy: PathMap<u64> = s.iter().map(|(k, v)| ([&k[2..4], &k[0..2]].concat(), *v)).collect();
+        let factor_vars: &[&[usize]] = &[&[0, 1], &[2, 1], &[2, 0]];
+        let mut join = TrieJoin::new([r.read_zipper(), s_zy.read_zipper(), t_zx.read_zipper()], factor_vars, &[2, 2, 2], &[2, 0, 1]);
+        let mut bindings = vec![];
+        while join.to_next_binding() {
+            assert_eq!(join.binding(), [join.var(2), join.var(0), join.var(1)].concat());
+            bindings.push([join.var(0), join.var(1), join.var(2)].concat());
+        }
+        bindings.sort();
+        assert_eq!(bindings, expected);
+    }
+
+    #[test]
+    fn trie_join_test2() {
+        //A factor must be at a value to satisfy the join, not just on a path
+        let a: PathMap<()> = ["ab", "abc", "b"].into_iter().map(|k| (k, ())).collect();
+        let b: PathMap<()> = ["a", "b", "c"].into_iter().map(|k| (k, ())).collect();
+        let mut join = TrieJoin::new([a.read_zipper(), b.read_zipper()], &[&[0, 1], &[1]], &[1, 1], &[0, 1]);
+        let mut bindings = vec![];
+        while join.to_next_binding() {
+            bindings.push(join.binding().to_vec());
+        }
+        assert_eq!(bindings, vec![b"ab".to_vec()]);
+
+        //A single-factor join enumerates the factor's paths of the right length
+        let mut join = TrieJoin::new([a.read_zipper()], &[&[0]], &[2], &[0]);
+        assert!(join.to_next_binding());
+        assert_eq!(join.binding(), b"ab");
+        assert!(!join.to_next_binding());
+
+        //A factor without variables acts as a filter on the empty tuple
+        let mut unit = PathMap::<()>::new();
+        let mut join = TrieJoin::new([b.read_zipper(), unit.read_zipper()], &[&[0], &[]], &[1], &[0]);
+        assert!(!join.to_next_binding());
+        unit.set_val_at([], ());
+        let mut join = TrieJoin::new([b.read_zipper(), unit.read_zipper()], &[&[0], &[]], &[1], &[0]);
+        let mut cnt = 0;
+        while join.to_next_binding() {
+            cnt += 1;
+        }
+        assert_eq!(cnt, 3);
+
+        //An empty join has one binding if all factors contain the empty tuple
+        let mut join = TrieJoin::new([unit.read_zipper()], &[&[]], &[], &[]);
+        assert!(join.to_next_binding());
+        assert_eq!(join.binding(), b"");
+        assert!(!join.to_next_binding());
+    }
+
+    #[test]
+    #[should_panic]
+    fn trie_join_bad_order_test() {
+        let a: PathMap<()> = ["ab"].into_iter().map(|k| (k, ())).collect();
+        let _join = TrieJoin::new([a.read_zipper()], &[&[1, 0]], &[1, 1], &[0, 1]);
+    }
+}
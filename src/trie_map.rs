@@ -5,7 +5,8 @@ use crate::morphisms::{new_map_from_ana_in, Catamorphism, TrieBuilder};
 use crate::trie_node::*;
 use crate::zipper::*;
 use crate::merkleization::{MerkleizeResult, merkleize_impl};
-use crate::ring::{AlgebraicResult, AlgebraicStatus, COUNTER_IDENT, SELF_IDENT, Lattice, LatticeRef, DistributiveLattice, DistributiveLatticeRef, Quantale};
+use crate::ring::{AlgebraicResult, AlgebraicStatus, FatAlgebraicResult, COUNTER_IDENT, SELF_IDENT, Lattice, LatticeRef, DistributiveLattice, DistributiveLatticeRef, MergePolicy, OrderedSemiring, Quantale, Semiring};
+use crate::utils::{BitMask, ByteMask};
 
 use crate::gxhash::{self, gxhash128};
 
@@ -93,6 +94,63 @@ impl<V: Clone + Send + Sync + Unpin> PathMap<V, GlobalAlloc> {
         Self::from((path, val))
     }
 
+    /// Returns a new `PathMap` containing the concatenation of all `factors`, in order
+    ///
+    /// This is the materialized equivalent of traversing the factors with a [ProductZipper].  See
+    /// [concat](Self::concat) for a discussion of how the values and the structure of the result are shared.
+    /// Returns an empty map if `factors` is empty.
+    pub fn product(factors: &[&Self]) -> Self where V: Lattice {
+        let Some((last, rest)) = factors.split_last() else {
+            return Self::new()
+        };
+        //NOTE: We fold from the right so each factor is grafted into the one before it exactly once.
+        // Folding from the left would require copying the nodes of every factor after the first
+        rest.iter().rev().fold((*last).clone(), |product, factor| factor.concat(&product))
+    }
+
+    /// Returns a new `PathMap` containing the union of all `maps`
+    ///
+    /// All the maps are merged together in a single descent, rather than folding [join](Lattice::pjoin)
+    /// pairwise.  Branches found in only one map, or shared in memory by all the maps that have them, are
+    /// reused in the result without being visited.  Returns an empty map if `maps` is empty.
+    ///
+    /// ```
+    /// use pathmap::PathMap;
+    ///
+    /// let a = PathMap::from_iter([("apple", ()), ("banana", ())]);
+    /// let b = PathMap::from_iter([("apricot", ())]);
+    /// let c = PathMap::from_iter([("banana", ()), ("cherry", ())]);
+    /// let union = PathMap::join_all([&a, &b, &c]);
+    /// assert_eq!(union.val_count(), 4);
+    /// ```
+    pub fn join_all<'a, I>(maps: I) -> Self
+        where
+        I: IntoIterator<Item=&'a Self>,
+        V: Lattice + 'a,
+    {
+        let maps: Vec<&Self> = maps.into_iter().collect();
+        if maps.is_empty() {
+            return Self::new()
+        }
+        nary_result_into_map(nary_op(&maps, NaryOp::Join), &maps)
+    }
+
+    /// Returns a new `PathMap` containing the intersection of all `maps`
+    ///
+    /// All the maps are intersected in a single descent, rather than folding [meet](Lattice::pmeet)
+    /// pairwise.  Returns an empty map if `maps` is empty.
+    pub fn meet_all<'a, I>(maps: I) -> Self
+        where
+        I: IntoIterator<Item=&'a Self>,
+        V: Lattice + 'a,
+    {
+        let maps: Vec<&Self> = maps.into_iter().collect();
+        if maps.is_empty() {
+            return Self::new()
+        }
+        nary_result_into_map(nary_op(&maps, NaryOp::Meet), &maps)
+    }
+
     /// Creates a new `PathMap` by evaluating the specified anamorphism
     ///
     /// `alg_f`: `alg(w: W, val: &mut Option<V>, children: &mut ChildBuilder<W>, path: &[u8])`
@@ -116,7 +174,6 @@ impl<V: Clone + Send + Sync + Unpin> PathMap<V, GlobalAlloc> {
     pub fn new_from_ana<W, AlgF>(w: W, alg_f: AlgF) -> Self
         where
         V: 'static,
-        W: Default,
         AlgF: FnMut(W, &mut Option<V>, &mut TrieBuilder<V, W, GlobalAlloc>, &[u8])
     {
         Self::new_from_ana_in(w, alg_f, global_alloc())
@@ -181,7 +238,6 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> PathMap<V, A> {
     pub fn new_from_ana_in<W, AlgF>(w: W, alg_f: AlgF, alloc: A) -> Self
         where
         V: 'static,
-        W: Default,
         AlgF: FnMut(W, &mut Option<V>, &mut TrieBuilder<V, W, A>, &[u8])
     {
         new_map_from_ana_in(w, alg_f, alloc)
@@ -583,6 +639,456 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> PathMap<V, A> {
         Self::new_with_root_in(subtracted_root_node, subtracted_root_val, self.alloc.clone())
     }
 
+    /// Returns a new `PathMap` containing the union of the paths in `self` and the paths in `other`,
+    /// using `policy` to combine the values at paths that exist in both maps
+    ///
+    /// See [MergePolicy] for details.
+    pub fn join_with<P: MergePolicy<V> + ?Sized>(&self, other: &Self, policy: &P) -> Self {
+        result_into_map(policy_op(self, other, policy, PolicyOp::Join), self, other, self.alloc.clone())
+    }
+
+    /// Returns a new `PathMap` containing the intersection of the paths in `self` and the paths in `other`,
+    /// using `policy` to combine the values
+    ///
+    /// See [MergePolicy] for details.
+    pub fn meet_with<P: MergePolicy<V> + ?Sized>(&self, other: &Self, policy: &P) -> Self {
+        result_into_map(policy_op(self, other, policy, PolicyOp::Meet), self, other, self.alloc.clone())
+    }
+
+    /// Returns a new `PathMap` containing the contents from `self`, where `policy` is used to subtract the
+    /// values in `other` from the values at the same paths in `self`
+    ///
+    /// Paths in `self` without a corresponding value in `other` are unaffected.  See [MergePolicy] for details.
+    pub fn subtract_with<P: MergePolicy<V> + ?Sized>(&self, other: &Self, policy: &P) -> Self {
+        result_into_map(policy_op(self, other, policy, PolicyOp::Subtract), self, other, self.alloc.clone())
+    }
+
+    /// Returns a new `PathMap` containing the union of the paths in `self` and the paths in `other`, where
+    /// the weights at paths that exist in both maps are combined with [Semiring::plus]
+    ///
+    /// Paths whose combined weight is [zero](Semiring::zero) are removed.
+    pub fn weighted_join(&self, other: &Self) -> Self where V: Semiring {
+        self.join_with(other, &semiring_plus)
+    }
+
+    /// Returns a new `PathMap` containing the concatenation of every path in `self` with every path in
+    /// `other`, weighted by the [Semiring::times] of the two paths' weights
+    ///
+    /// When a concatenated path can be formed in more than one way, the weights are combined with [Semiring::plus].
+    /// Paths whose weight is [zero](Semiring::zero) are not stored.
+    pub fn weighted_product(&self, other: &Self) -> Self where V: Semiring {
+        self.concat_internal(other, |wz, prefix_w| {
+            let mut suffix = Self::new_in(other.alloc.clone());
+            let mut suffix_root_w = None;
+            for (path, suffix_w) in other.iter() {
+                let w = prefix_w.times(suffix_w);
+                if w.is_zero() {
+                    continue
+                }
+                if path.is_empty() {
+                    suffix_root_w = Some(w);
+                } else {
+                    suffix.set_val_at(path, w);
+                }
+            }
+            wz.join_into_with(&suffix.read_zipper(), &semiring_plus);
+            if let Some(w) = suffix_root_w {
+                wz.set_val(w);
+            }
+            //The prefix was only a path in `self`, so it's removed if none of its products were kept
+            wz.prune_path();
+        })
+    }
+
+    /// Returns the path with the best weight in the map, according to [OrderedSemiring::is_better], along
+    /// with its weight.  E.g. the shortest path for [Tropical](crate::ring::Tropical) weights, or the most
+    /// probable path for [LogProb](crate::ring::LogProb) weights
+    ///
+    /// Each value is taken as the weight of the whole path leading to it, as in the result of
+    /// [weighted_product](Self::weighted_product).  The values at the path's prefixes are not combined with
+    /// [Semiring::times], so a map whose values are the weights of individual path segments must be multiplied
+    /// out into whole-path weights first.
+    ///
+    /// If several paths share the best weight, the first one in the map's order is returned.  Returns `None` if
+    /// the map contains no non-[zero](Semiring::zero) weights.
+    pub fn best_weighted_path(&self) -> Option<(Vec<u8>, V)> where V: OrderedSemiring {
+        let mut best: Option<(Vec<u8>, &V)> = None;
+        for (path, w) in self.iter() {
+            if w.is_zero() {
+                continue
+            }
+            match &best {
+                Some((_, best_w)) if !w.is_better(best_w) => {},
+                _ => best = Some((path, w)),
+            }
+        }
+        best.map(|(path, w)| (path, w.clone()))
+    }
+
+    /// Returns a new `PathMap` containing the concatenation of every path in `self` with every path in `other`
+    ///
+    /// The result is built by grafting a shared reference to `other` at each value position in `self`, so the
+    /// cost is proportional to the sizes of the two maps rather than to their product.  Paths in `self` that
+    /// continue below a value position are joined with the grafted trie, and copying only occurs where they overlap.
+    ///
+    /// The values of the result come from `other`, and the values in `self` only mark the positions where
+    /// paths may be concatenated, in the same way that the values in the argument to [restrict](Self::restrict)
+    /// only validate paths.  When a path can be formed in more than one way, the values are joined.  Use
+    /// [concat_with](Self::concat_with) to combine the values from both maps.
+    ///
+    /// A root value in `other` represents the empty path, so a path from `self` will be kept in the result with
+    /// `other`'s root value.
+    ///
+    /// ```
+    /// # use pathmap::PathMap;
+    /// let a: PathMap<()> = ["a", "ab"].into_iter().map(|k| (k, ())).collect();
+    /// let b: PathMap<()> = ["c", "d"].into_iter().map(|k| (k, ())).collect();
+    /// let product = a.concat(&b);
+    /// let keys: Vec<Vec<u8>> = product.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![b"abc".to_vec(), b"abd".to_vec(), b"ac".to_vec(), b"ad".to_vec()]);
+    /// ```
+    pub fn concat(&self, other: &Self) -> Self where V: Lattice {
+        let suffix = other.read_zipper();
+        self.concat_internal(other, |wz, _prefix_val| {
+            wz.join_into(&suffix);
+            if let Some(suffix_val) = other.root_val() {
+                wz.set_val(suffix_val.clone());
+            }
+        })
+    }
+
+    /// Returns a new `PathMap` containing the concatenation of every path in `self` with every path in `other`,
+    /// where the value for each path is computed by `combine_f(prefix_val, suffix_val)`
+    ///
+    /// When a path can be formed in more than one way, the values are joined.
+    ///
+    /// NOTE: Unlike [concat](Self::concat), this method can't share the structure of `other` because the values
+    /// may be different at each value position in `self`, so the cost is proportional to the product of the
+    /// sizes of the two maps.
+    pub fn concat_with<F>(&self, other: &Self, mut combine_f: F) -> Self
+        where
+        V: Lattice,
+        F: FnMut(&V, &V) -> V,
+    {
+        self.concat_internal(other, |wz, prefix_val| {
+            let mut suffix = Self::new_in(other.alloc.clone());
+            let mut suffix_root_val = None;
+            for (path, suffix_val) in other.iter() {
+                let val = combine_f(&prefix_val, suffix_val);
+                if path.is_empty() {
+                    suffix_root_val = Some(val);
+                } else {
+                    suffix.set_val_at(path, val);
+                }
+            }
+            wz.join_map_into(suffix);
+            if let Some(val) = suffix_root_val {
+                wz.set_val(val);
+            }
+        })
+    }
+
+    /// Returns a new `PathMap` containing the suffixes of the paths in `self` that follow a path leading to a
+    /// value in `prefixes`, aka the left quotient of `self` by `prefixes`
+    ///
+    /// In other words, the result contains `s -> v` whenever `self` contains `p ++ s -> v` for some `p` in
+    /// `prefixes`.  When a suffix follows more than one prefix, the values are joined.  The values in `prefixes`
+    /// are ignored.  The subtries of `self` are shared with the result wherever they don't overlap.
+    ///
+    /// This is the inverse of [concat](Self::concat), in the sense that `a.concat(&b).left_quotient(&a)`
+    /// contains `b`.
+    ///
+    /// ```
+    /// # use pathmap::PathMap;
+    /// let map: PathMap<()> = ["GET /a", "GET /b", "PUT /a", "PUT /c", "POST /d"].into_iter().map(|k| (k, ())).collect();
+    /// let prefixes: PathMap<()> = ["GET ", "PUT "].into_iter().map(|k| (k, ())).collect();
+    /// let quotient = map.left_quotient(&prefixes);
+    /// let keys: Vec<Vec<u8>> = quotient.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![b"/a".to_vec(), b"/b".to_vec(), b"/c".to_vec()]);
+    /// ```
+    pub fn left_quotient(&self, prefixes: &Self) -> Self where V: Lattice {
+        self.left_quotient_internal(prefixes.read_zipper())
+    }
+
+    /// Returns a new `PathMap` containing the relational composition of `self` and `other`
+    ///
+    /// Treating the paths in `self` as `a ++ b`, where `a` is the first `left_len` bytes, and the paths in
+    /// `other` as `b ++ c`, the result contains `a ++ c` for every `a ++ b` in `self` and `b ++ c` in `other`.
+    /// The `b` segment is the remainder of each path in `self` after `a`, so it doesn't need to have a fixed width.
+    ///
+    /// The composition is computed by a zipper-driven join over the shared `b` segment: For each distinct `a`, the
+    /// `b` suffixes in `self` are traversed in lockstep with `other`, and the matching `c` subtries of `other` are
+    /// grafted into the result under `a`, as in [left_quotient](Self::left_quotient).  The values in the result
+    /// come from `other`, and they are joined when several `b` segments lead to the same `a ++ c`.  The values in
+    /// `self` are ignored.
+    ///
+    /// ```
+    /// # use pathmap::PathMap;
+    /// //Edges of a graph, where each node is one byte
+    /// let edges: PathMap<()> = ["ab", "bc", "cd", "ce"].into_iter().map(|k| (k, ())).collect();
+    ///
+    /// //Paths of length 2
+    /// let two_hops = edges.compose(&edges, 1);
+    /// let keys: Vec<Vec<u8>> = two_hops.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![b"ac".to_vec(), b"bd".to_vec(), b"be".to_vec()]);
+    /// ```
+    pub fn compose(&self, other: &Self, left_len: usize) -> Self where V: Lattice {
+        //NOTE: `to_next_k_path` with `k == 0` doesn't terminate, because every step lands on the same path
+        if left_len == 0 {
+            return other.left_quotient(self)
+        }
+        let mut result = Self::new_in(self.alloc.clone());
+        let mut wz = result.write_zipper();
+        let mut self_z = self.read_zipper();
+        let mut found = self_z.descend_first_k_path(left_len);
+        while found {
+            let (composed_node, composed_val) = other.left_quotient_internal(self_z.fork_read_zipper()).into_root();
+            if composed_node.is_some() || composed_val.is_some() {
+                wz.descend_to(self_z.path());
+                wz.graft_map(Self::new_with_root_in(composed_node, None, self.alloc.clone()));
+                if let Some(val) = composed_val {
+                    wz.set_val(val);
+                }
+                wz.reset();
+            }
+            found = self_z.to_next_k_path(left_len);
+        }
+        drop(wz);
+        result
+    }
+
+    /// Returns a new `PathMap` that keeps only some of the fields of paths made of fixed-width fields
+    ///
+    /// `field_layout` holds the width in bytes of each field, in order, and `keep` holds the indices of the
+    /// fields to retain, in increasing order.  The other fields are removed from every path, and the values of
+    /// paths that collide once the fields are removed are joined.  Any bytes after the last field are kept at the
+    /// end of the path, and values at paths shorter than the whole layout are dropped.
+    ///
+    /// ```
+    /// # use pathmap::PathMap;
+    /// //Paths are `user ++ day ++ item`, with widths of 3, 1 and 2 bytes
+    /// let map: PathMap<()> = ["bob1xy", "bob2xy", "bob2zz", "sue1ab"].into_iter().map(|k| (k, ())).collect();
+    ///
+    /// //Drop the `day` field
+    /// let projected = map.project(&[3, 1, 2], &[0, 2]);
+    /// let keys: Vec<Vec<u8>> = projected.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![b"bobxy".to_vec(), b"bobzz".to_vec(), b"sueab".to_vec()]);
+    /// ```
+    pub fn project(&self, field_layout: &[usize], keep: &[usize]) -> Self where V: Lattice + 'static {
+        assert!(keep.windows(2).all(|pair| pair[0] < pair[1]), "`keep` must be in increasing order");
+        self.rearrange_fields(field_layout, keep)
+    }
+
+    /// Returns a new `PathMap` with the fields of paths made of fixed-width fields reordered
+    ///
+    /// `field_layout` holds the width in bytes of each field, in order, and `order` lists every field index,
+    /// in the order the fields should appear in the result.  For example, `[0, 2, 1]` swaps the last two of
+    /// three fields, which is handy for building a secondary index.  Any bytes after the last field are kept
+    /// at the end of the path, and values at paths shorter than the whole layout are dropped.
+    ///
+    /// ```
+    /// # use pathmap::PathMap;
+    /// //Paths are `user ++ item`, with widths of 3 and 2 bytes
+    /// let map: PathMap<()> = ["bobxy", "bobzz", "suexy"].into_iter().map(|k| (k, ())).collect();
+    ///
+    /// //Index by `item`
+    /// let by_item = map.permute(&[3, 2], &[1, 0]);
+    /// let keys: Vec<Vec<u8>> = by_item.iter().map(|(k, _)| k).collect();
+    /// assert_eq!(keys, vec![b"xybob".to_vec(), b"xysue".to_vec(), b"zzbob".to_vec()]);
+    /// ```
+    pub fn permute(&self, field_layout: &[usize], order: &[usize]) -> Self where V: Lattice + 'static {
+        let mut sorted_order = order.to_vec();
+        sorted_order.sort_unstable();
+        assert!(sorted_order.into_iter().eq(0..field_layout.len()), "`order` must be a permutation of the fields");
+        self.rearrange_fields(field_layout, order)
+    }
+
+    /// Internal method to implement [project](Self::project) and [permute](Self::permute).  The fields that
+    /// aren't listed in `order` are removed
+    ///
+    /// The result is built by an anamorphism.  Each step carries the paths of `self` that agree with the
+    /// fields emitted so far, with those fields removed, and splits them on the next field in `order`.
+    fn rearrange_fields(&self, field_layout: &[usize], order: &[usize]) -> Self where V: Lattice + 'static {
+        assert!(order.iter().all(|&field| field < field_layout.len()), "field index out of range");
+        let dropped_width: usize = (0..field_layout.len()).filter(|field| !order.contains(field)).map(|field| field_layout[field]).sum();
+        let alloc = self.alloc.clone();
+        Self::new_from_ana_in(FieldsW::Fields(self.clone(), 0), |mut w, val, children, _path| {
+            loop {
+                match w {
+                    FieldsW::Fields(remaining, idx) if idx == order.len() => {
+                        if dropped_width == 0 {
+                            w = FieldsW::Tail(remaining);
+                            continue
+                        }
+                        let parts = remaining.split_field(0, dropped_width);
+                        let part_refs: Vec<&Self> = parts.iter().map(|(_, part)| part).collect();
+                        if part_refs.is_empty() {
+                            return
+                        }
+                        w = FieldsW::Tail(nary_result_into_map(nary_op(&part_refs, NaryOp::Join), &part_refs));
+                    },
+                    FieldsW::Fields(remaining, idx) => {
+                        //The offset of the field among the fields that haven't been emitted yet
+                        let field = order[idx];
+                        let offset = (0..field).filter(|earlier| !order[..idx].contains(earlier)).map(|earlier| field_layout[earlier]).sum();
+                        w = FieldsW::Pending(remaining.split_field(offset, field_layout[field]), 0, idx);
+                    },
+                    FieldsW::Pending(mut parts, pos, idx) => {
+                        if pos == field_layout[order[idx]] {
+                            debug_assert!(parts.len() <= 1);
+                            match parts.pop() {
+                                Some((_, remaining)) => w = FieldsW::Fields(remaining, idx + 1),
+                                None => return
+                            }
+                            continue
+                        }
+                        //The parts are sorted by field value, so the parts that share the next byte are adjacent
+                        let mut parts = parts.into_iter().peekable();
+                        while let Some((field_val, part)) = parts.next() {
+                            let byte = field_val[pos];
+                            let mut group = vec![(field_val, part)];
+                            while let Some(next) = parts.next_if(|(next_val, _)| next_val[pos] == byte) {
+                                group.push(next);
+                            }
+                            children.push_byte(byte, FieldsW::Pending(group, pos + 1, idx));
+                        }
+                        return
+                    },
+                    FieldsW::Tail(tail) => {
+                        let (tail_node, tail_val) = tail.into_root();
+                        if tail_val.is_some() {
+                            *val = tail_val;
+                        }
+                        //Branches are grafted unless they begin with a value, because grafting only carries the
+                        // root value when the `graft_root_vals` feature is enabled
+                        let tail = Self::new_with_root_in(tail_node, None, alloc.clone());
+                        let mut tail_z = tail.read_zipper();
+                        for byte in tail_z.child_mask().iter() {
+                            tail_z.descend_to_byte(byte);
+                            match tail_z.val() {
+                                Some(child_val) => {
+                                    let child_node = tail_z.make_map().and_then(|child| child.into_root().0);
+                                    let child = Self::new_with_root_in(child_node, Some(child_val.clone()), alloc.clone());
+                                    children.push_with_val(&[byte], child_val.clone(), FieldsW::Tail(child));
+                                },
+                                None => children.graft_at_byte(byte, &tail_z),
+                            }
+                            tail_z.ascend_byte();
+                        }
+                        return
+                    },
+                }
+            }
+        }, self.alloc.clone())
+    }
+
+    /// Internal method to split `self` on a field that starts `offset` bytes into each path and is `width` bytes
+    /// wide.  Returns the distinct values of the field, in order, each with the paths that contain it, with the
+    /// field removed
+    fn split_field(&self, offset: usize, width: usize) -> Vec<(Vec<u8>, Self)> {
+        //NOTE: `to_next_k_path` with `k == 0` doesn't terminate, because every step lands on the same path
+        if width == 0 {
+            return vec![(vec![], self.clone())]
+        }
+        let mut parts = std::collections::BTreeMap::<Vec<u8>, Self>::new();
+        let mut z = self.read_zipper();
+        let mut found = offset == 0 || z.descend_first_k_path(offset);
+        while found {
+            let prefix = z.path().to_vec();
+            let mut found_field = z.descend_first_k_path(width);
+            while found_field {
+                //Each combination of prefix and field value is only visited once, so there is nothing to join
+                let part = parts.entry(z.path()[offset..].to_vec()).or_insert_with(|| Self::new_in(self.alloc.clone()));
+                let mut wz = part.write_zipper_at_path(&prefix);
+                if let Some(suffixes) = z.make_map() {
+                    let (suffixes_root_node, _) = suffixes.into_root();
+                    wz.graft_map(Self::new_with_root_in(suffixes_root_node, None, self.alloc.clone()));
+                }
+                if let Some(suffix_val) = z.val() {
+                    wz.set_val(suffix_val.clone());
+                }
+                drop(wz);
+                found_field = z.to_next_k_path(width);
+            }
+            found = offset > 0 && z.to_next_k_path(offset);
+        }
+        parts.into_iter().filter(|(_, part)| !part.is_empty()).collect()
+    }
+
+    /// Internal function to implement [left_quotient](Self::left_quotient), traversing `self` in lockstep with
+    /// the prefixes below the focus of `prefix_z`
+    fn left_quotient_internal<Z: ZipperMoving>(&self, mut prefix_z: Z) -> Self where V: Lattice {
+        let mut result = Self::new_in(self.alloc.clone());
+        let mut wz = result.write_zipper();
+
+        let mut self_z = self.read_zipper();
+        //Each frame holds the mask of the branches common to both maps, and the index of the next one to visit
+        let mut stack = Vec::<(ByteMask, usize)>::with_capacity(12);
+        loop {
+            if prefix_z.is_val() {
+                //NOTE: The value is joined separately because `join_map_into` only joins the root value when
+                // the `graft_root_vals` feature is enabled
+                if let Some(suffixes) = self_z.make_map() {
+                    let (suffixes_root_node, _) = suffixes.into_root();
+                    wz.join_map_into(Self::new_with_root_in(suffixes_root_node, None, self.alloc.clone()));
+                }
+                if let Some(self_val) = self_z.val() {
+                    match wz.get_val_mut() {
+                        Some(existing) => { existing.join_into(self_val.clone()); },
+                        None => { wz.set_val(self_val.clone()); },
+                    }
+                }
+            }
+            stack.push((self_z.child_mask().and(&prefix_z.child_mask()), 0));
+
+            //Move to the next common path, ascending out of the branches we have finished
+            loop {
+                let (mask, idx) = stack.last_mut().unwrap();
+                if let Some(byte) = mask.indexed_bit::<true>(*idx) {
+                    *idx += 1;
+                    self_z.descend_to_byte(byte);
+                    prefix_z.descend_to_byte(byte);
+                    break
+                }
+                stack.pop();
+                if stack.is_empty() {
+                    break
+                }
+                self_z.ascend_byte();
+                prefix_z.ascend_byte();
+            }
+            if stack.is_empty() {
+                break
+            }
+        }
+        drop(wz);
+        result
+    }
+
+    /// Internal function to implement [concat](Self::concat) and [concat_with](Self::concat_with).  `graft_f`
+    /// is called with a zipper at each value position of `self`, after the value has been removed
+    ///
+    /// The value positions are visited in reverse order, so the subtrie below a value position has already
+    /// been processed when `graft_f` is called for that position.
+    fn concat_internal<F>(&self, other: &Self, mut graft_f: F) -> Self
+        where F: FnMut(&mut WriteZipperUntracked<'_, 'static, V, A>, V)
+    {
+        if other.is_empty() {
+            return Self::new_in(self.alloc.clone())
+        }
+        let prefixes: Vec<Vec<u8>> = self.iter().map(|(path, _)| path).collect();
+        let mut result = self.clone();
+        let mut wz = result.write_zipper();
+        for prefix in prefixes.iter().rev() {
+            wz.reset();
+            wz.descend_to(prefix);
+            let prefix_val = wz.remove_val(false).unwrap();
+            graft_f(&mut wz, prefix_val);
+        }
+        drop(wz);
+        result
+    }
+
     /// Optimize the `PathMap` by factoring shared subtries using a temporary [Merkle Tree](https://en.wikipedia.org/wiki/Merkle_tree)
     pub fn merkleize(&mut self) -> MerkleizeResult
         where V: core::hash::Hash
@@ -668,6 +1174,28 @@ impl<V: Clone + Send + Sync + Unpin + 'static, A: Allocator + 'static> std::iter
     }
 }
 
+/// The state of the anamorphism that builds the result of [PathMap::rearrange_fields]
+enum FieldsW<V: Clone + Send + Sync, A: Allocator> {
+    /// The remaining paths, and the index in `order` of the next field to emit
+    Fields(PathMap<V, A>, usize),
+    /// The remaining paths split by the value of the field being emitted, the number of bytes of the field
+    /// emitted so far, and the index of the field in `order`
+    Pending(Vec<(Vec<u8>, PathMap<V, A>)>, usize, usize),
+    /// The paths that follow the last field
+    Tail(PathMap<V, A>),
+}
+
+/// Internal [MergePolicy] to combine the weights of alternative paths with [Semiring::plus], which removes
+/// the paths whose combined weight is [zero](Semiring::zero)
+fn semiring_plus<V: Semiring>(self_w: &V, other_w: &V) -> AlgebraicResult<V> {
+    let sum = self_w.plus(other_w);
+    if sum.is_zero() {
+        AlgebraicResult::None
+    } else {
+        AlgebraicResult::Element(sum)
+    }
+}
+
 /// Internal function to convert an [AlgebraicResult] (partial lattice result) into a `PathMap`
 fn result_into_map<V: Clone + Send + Sync + Unpin, A: Allocator>(result: AlgebraicResult<PathMap<V, A>>, self_map: &PathMap<V, A>, other_map: &PathMap<V, A>, result_region: A) -> PathMap<V, A> {
     match result {
@@ -684,6 +1212,513 @@ fn result_into_map<V: Clone + Send + Sync + Unpin, A: Allocator>(result: Algebra
     }
 }
 
+/// The structural behavior of [policy_op], which determines the paths that appear in the result
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum PolicyOp {
+    Join,
+    Meet,
+    Subtract,
+}
+
+/// The change to the value at a node visited by [policy_op_at]
+enum PolicyValEdit<V> {
+    Keep,
+    TakeOther,
+    Set(V),
+    Remove,
+}
+
+/// A node being visited by [policy_op_at]
+struct PolicyFrame<V> {
+    /// The branches common to both tries
+    common_mask: ByteMask,
+    /// The index of the next branch in `common_mask` to visit
+    next_idx: usize,
+    /// Whether the result below this node is still equal to the subtrie of `self`
+    self_ident: bool,
+    /// Whether the result below this node is still equal to the subtrie of `other`
+    counter_ident: bool,
+    /// Whether the result has a value at this node
+    has_val: bool,
+    val_edit: PolicyValEdit<V>,
+    /// The branches of `other` to graft into the result, because they aren't in `self`, or because the
+    /// result is an identity of `other` below them
+    graft_mask: ByteMask,
+    /// The branches of `self` that aren't in the result
+    remove_mask: ByteMask,
+}
+
+impl<V: Clone> PolicyFrame<V> {
+    /// Combines the values at the focus of the zippers, and inspects the branches that are only in one trie
+    fn enter<Z, OZ, P>(self_z: &Z, other_z: &OZ, policy: &P, op: PolicyOp, with_vals: bool) -> Self
+        where
+        Z: ZipperMoving + ZipperValues<V>,
+        OZ: ZipperMoving + ZipperValues<V>,
+        P: MergePolicy<V> + ?Sized,
+    {
+        let mut frame = Self {
+            common_mask: ByteMask::new(),
+            next_idx: 0,
+            self_ident: true,
+            counter_ident: op != PolicyOp::Subtract,
+            has_val: false,
+            val_edit: PolicyValEdit::Keep,
+            graft_mask: ByteMask::new(),
+            remove_mask: ByteMask::new(),
+        };
+        let (self_val, other_val) = if with_vals { (self_z.val(), other_z.val()) } else { (None, None) };
+        frame.has_val = self_val.is_some();
+        match (self_val, other_val) {
+            (Some(self_val), Some(other_val)) => match policy.merge(self_val, other_val) {
+                AlgebraicResult::Identity(mask) => {
+                    if mask & SELF_IDENT == 0 {
+                        frame.val_edit = PolicyValEdit::TakeOther;
+                        frame.self_ident = false;
+                    }
+                    if mask & COUNTER_IDENT == 0 {
+                        frame.counter_ident = false;
+                    }
+                },
+                AlgebraicResult::Element(val) => {
+                    frame.val_edit = PolicyValEdit::Set(val);
+                    frame.self_ident = false;
+                    frame.counter_ident = false;
+                },
+                AlgebraicResult::None => {
+                    frame.val_edit = PolicyValEdit::Remove;
+                    frame.has_val = false;
+                    frame.self_ident = false;
+                    frame.counter_ident = false;
+                },
+            },
+            (Some(_), None) => match op {
+                PolicyOp::Join => frame.counter_ident = false,
+                PolicyOp::Meet => {
+                    frame.val_edit = PolicyValEdit::Remove;
+                    frame.has_val = false;
+                    frame.self_ident = false;
+                },
+                PolicyOp::Subtract => {},
+            },
+            (None, Some(_)) => match op {
+                PolicyOp::Join => {
+                    frame.val_edit = PolicyValEdit::TakeOther;
+                    frame.has_val = true;
+                    frame.self_ident = false;
+                },
+                PolicyOp::Meet => frame.counter_ident = false,
+                PolicyOp::Subtract => {},
+            },
+            (None, None) => {},
+        }
+
+        let self_mask = self_z.child_mask();
+        let other_mask = other_z.child_mask();
+        frame.common_mask = self_mask.and(&other_mask);
+        let self_only = self_mask.andn(&other_mask);
+        let other_only = other_mask.andn(&self_mask);
+        match op {
+            PolicyOp::Join => {
+                if !self_only.is_empty_mask() {
+                    frame.counter_ident = false;
+                }
+                if !other_only.is_empty_mask() {
+                    frame.self_ident = false;
+                    frame.graft_mask = other_only;
+                }
+            },
+            PolicyOp::Meet => {
+                if !other_only.is_empty_mask() {
+                    frame.counter_ident = false;
+                }
+                if !self_only.is_empty_mask() {
+                    frame.self_ident = false;
+                    frame.remove_mask = self_only;
+                }
+            },
+            PolicyOp::Subtract => {},
+        }
+        frame
+    }
+
+    /// Accounts for the result below the branch `byte`
+    fn child_result(&mut self, byte: u8, result: AlgebraicResult<()>) {
+        match result {
+            AlgebraicResult::Identity(mask) => {
+                if mask & SELF_IDENT == 0 {
+                    self.self_ident = false;
+                    self.graft_mask.set_bit(byte);
+                }
+                if mask & COUNTER_IDENT == 0 {
+                    self.counter_ident = false;
+                }
+            },
+            AlgebraicResult::Element(()) => {
+                self.self_ident = false;
+                self.counter_ident = false;
+            },
+            AlgebraicResult::None => {
+                self.self_ident = false;
+                self.counter_ident = false;
+                self.remove_mask.set_bit(byte);
+            },
+        }
+    }
+}
+
+/// Performs an algebraic operation between the subtrie below the focus of `wz` and the subtrie below the
+/// focus of `other_z`, combining the values with a [MergePolicy], and writing the result through `wz`
+///
+/// The tries are only traversed along the paths they have in common.  The result for each subtrie is found
+/// before anything is written, so a subtrie that is an identity of `self` is left untouched, and a subtrie
+/// that is an identity of `other` is grafted from `other` as a whole, as are the branches only in `other`.
+/// Nothing is written if the whole result is an identity or [None](AlgebraicResult::None), so the caller
+/// decides how to apply those results.  The values at the foci are only combined if `with_vals` is `true`.
+pub(crate) fn policy_op_at<V, A, WZ, Z, P>(wz: &mut WZ, other_z: &mut Z, policy: &P, op: PolicyOp, with_vals: bool) -> AlgebraicResult<()>
+    where
+    V: Clone + Send + Sync,
+    A: Allocator,
+    WZ: ZipperWriting<V, A> + ZipperMoving + ZipperValues<V>,
+    Z: ZipperSubtries<V, A> + ZipperMoving,
+    P: MergePolicy<V> + ?Sized,
+{
+    let mut stack = Vec::<PolicyFrame<V>>::with_capacity(12);
+    stack.push(PolicyFrame::enter(wz, other_z, policy, op, with_vals));
+    loop {
+        let frame = stack.last_mut().unwrap();
+        if let Some(byte) = frame.common_mask.indexed_bit::<true>(frame.next_idx) {
+            frame.next_idx += 1;
+            wz.descend_to_byte(byte);
+            other_z.descend_to_byte(byte);
+            stack.push(PolicyFrame::enter(wz, other_z, policy, op, true));
+            continue
+        }
+        let frame = stack.pop().unwrap();
+        let result = policy_frame_finish(wz, other_z, frame);
+        let Some(parent) = stack.last_mut() else {
+            return result
+        };
+        let byte = *other_z.path().last().unwrap();
+        wz.ascend_byte();
+        other_z.ascend_byte();
+        parent.child_result(byte, result);
+    }
+}
+
+/// Internal function to write the result at a node visited by [policy_op_at], once the result below each
+/// common branch is known
+fn policy_frame_finish<V, A, WZ, Z>(wz: &mut WZ, other_z: &mut Z, frame: PolicyFrame<V>) -> AlgebraicResult<()>
+    where
+    V: Clone + Send + Sync,
+    A: Allocator,
+    WZ: ZipperWriting<V, A> + ZipperMoving + ZipperValues<V>,
+    Z: ZipperSubtries<V, A> + ZipperMoving,
+{
+    if frame.self_ident {
+        return AlgebraicResult::Identity(if frame.counter_ident { SELF_IDENT | COUNTER_IDENT } else { SELF_IDENT })
+    }
+    if frame.counter_ident {
+        return AlgebraicResult::Identity(COUNTER_IDENT)
+    }
+    //If nothing remains, the branch will be removed above, so there is no point writing anything
+    let kept_mask = wz.child_mask().andn(&frame.remove_mask);
+    if !frame.has_val && kept_mask.is_empty_mask() && frame.graft_mask.is_empty_mask() {
+        return AlgebraicResult::None
+    }
+    match frame.val_edit {
+        PolicyValEdit::Keep => {},
+        PolicyValEdit::TakeOther => { wz.set_val(other_z.val().unwrap().clone()); },
+        PolicyValEdit::Set(val) => { wz.set_val(val); },
+        PolicyValEdit::Remove => { wz.remove_val(false); },
+    }
+    if !frame.remove_mask.is_empty_mask() {
+        wz.remove_unmasked_branches(kept_mask, false);
+    }
+    for byte in frame.graft_mask.iter() {
+        wz.descend_to_byte(byte);
+        other_z.descend_to_byte(byte);
+        wz.graft(other_z);
+        #[cfg(not(feature = "graft_root_vals"))]
+        match other_z.val() {
+            Some(val) => { wz.set_val(val.clone()); },
+            None => { wz.remove_val(false); },
+        }
+        wz.ascend_byte();
+        other_z.ascend_byte();
+    }
+    AlgebraicResult::Element(())
+}
+
+/// Performs an algebraic operation on two maps, combining the values with a [MergePolicy]
+///
+/// The result is written into a clone of `self`, so untouched subtries stay shared with `self`, and the
+/// subtries taken from `other` stay shared with `other`.  See [policy_op_at].
+pub(crate) fn policy_op<V, A, P>(self_map: &PathMap<V, A>, other_map: &PathMap<V, A>, policy: &P, op: PolicyOp) -> AlgebraicResult<PathMap<V, A>>
+    where
+    V: Clone + Send + Sync + Unpin,
+    A: Allocator,
+    P: MergePolicy<V> + ?Sized,
+{
+    let mut result = self_map.clone();
+    let mut wz = result.write_zipper();
+    let mut other_z = other_map.read_zipper();
+    let status = policy_op_at(&mut wz, &mut other_z, policy, op, true);
+    drop(wz);
+    match status {
+        AlgebraicResult::Identity(mask) => {
+            let ident_map = if mask & SELF_IDENT > 0 { self_map } else { other_map };
+            if ident_map.is_empty() {
+                AlgebraicResult::None
+            } else {
+                AlgebraicResult::Identity(mask)
+            }
+        },
+        AlgebraicResult::Element(()) => AlgebraicResult::Element(result),
+        AlgebraicResult::None => AlgebraicResult::None,
+    }
+}
+
+/// The structural behavior of [nary_op]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) enum NaryOp {
+    Join,
+    Meet,
+}
+
+/// The identity bit for the operand at `idx`.  Operands beyond the width of the mask never produce
+/// identity results, so the result is built from their contents instead
+#[inline]
+fn nary_ident_bit(idx: usize) -> u64 {
+    if idx < 64 { 1 << idx } else { 0 }
+}
+
+/// A node being merged by [nary_op]
+struct NaryFrame<V: Clone + Send + Sync, A: Allocator> {
+    /// The byte that led to this node from its parent
+    byte: u8,
+    /// The operands that have this node
+    active: Vec<usize>,
+    /// The branches to visit; the union of the operands' branches for a join, and the intersection for a meet
+    mask: ByteMask,
+    next_idx: usize,
+    /// The merged value at this node
+    val: FatAlgebraicResult<V>,
+    /// The merged children that are not empty
+    children: Vec<(u8, FatAlgebraicResult<PathMap<V, A>>)>,
+}
+
+/// Performs an algebraic operation on any number of maps in a single simultaneous descent
+///
+/// Only the operands that have a given path descend into it.  A subtrie that is shared in memory by all
+/// the operands that reach it is not visited at all.  The identity mask of the result has a bit set for
+/// each operand (among the first 64) that is equal to the result.
+pub(crate) fn nary_op<V, A>(maps: &[&PathMap<V, A>], op: NaryOp) -> FatAlgebraicResult<PathMap<V, A>>
+    where
+    V: Clone + Send + Sync + Unpin + Lattice,
+    A: Allocator,
+{
+    let Some(first_map) = maps.first() else {
+        return FatAlgebraicResult::none()
+    };
+    let alloc = first_map.alloc.clone();
+    let mut zs: Vec<_> = maps.iter().map(|map| map.read_zipper()).collect();
+    let all: Vec<usize> = (0..maps.len()).collect();
+    if let Some(result) = nary_shortcut(&zs, &all, op, &alloc) {
+        return result
+    }
+    let mut stack = vec![nary_enter(&zs, all, 0, op)];
+    loop {
+        let top = stack.last_mut().unwrap();
+        if let Some(byte) = top.mask.indexed_bit::<true>(top.next_idx) {
+            top.next_idx += 1;
+            let child_active: Vec<usize> = top.active.iter().copied().filter(|&i| zs[i].child_mask().test_bit(byte)).collect();
+            for &i in child_active.iter() {
+                zs[i].descend_to_byte(byte);
+            }
+            match nary_shortcut(&zs, &child_active, op, &alloc) {
+                Some(result) => {
+                    for &i in child_active.iter() {
+                        zs[i].ascend_byte();
+                    }
+                    if result.identity_mask != 0 || result.element.is_some() {
+                        top.children.push((byte, result));
+                    }
+                },
+                None => {
+                    let frame = nary_enter(&zs, child_active, byte, op);
+                    stack.push(frame);
+                }
+            }
+            continue
+        }
+
+        let frame = stack.pop().unwrap();
+        let result = nary_finish(&mut zs, &frame, &alloc);
+        match stack.last_mut() {
+            None => return result,
+            Some(parent) => {
+                for &i in frame.active.iter() {
+                    zs[i].ascend_byte();
+                }
+                if result.identity_mask != 0 || result.element.is_some() {
+                    parent.children.push((frame.byte, result));
+                }
+            }
+        }
+    }
+}
+
+/// Returns the result at the zippers' focus without descending, if it can be known from the operands alone
+fn nary_shortcut<V, A>(zs: &[ReadZipperUntracked<V, A>], active: &[usize], op: NaryOp, alloc: &A) -> Option<FatAlgebraicResult<PathMap<V, A>>>
+    where
+    V: Clone + Send + Sync + Unpin,
+    A: Allocator,
+{
+    if op == NaryOp::Meet && active.len() < zs.len() {
+        return Some(FatAlgebraicResult::none())
+    }
+    let first = *active.first()?;
+    if active.len() > 1 {
+        let shared_id = zs[first].shared_node_id()?;
+        if active[1..].iter().any(|&i| zs[i].shared_node_id() != Some(shared_id)) {
+            return None
+        }
+    }
+    let mask = active.iter().fold(0, |mask, &i| mask | nary_ident_bit(i));
+    if mask != 0 {
+        Some(FatAlgebraicResult::new(mask, None))
+    } else {
+        Some(FatAlgebraicResult::element(nary_focus_map(&zs[first], alloc)))
+    }
+}
+
+/// Begins merging the node at the focus of the `active` zippers
+fn nary_enter<V, A>(zs: &[ReadZipperUntracked<V, A>], active: Vec<usize>, byte: u8, op: NaryOp) -> NaryFrame<V, A>
+    where
+    V: Clone + Send + Sync + Unpin + Lattice,
+    A: Allocator,
+{
+    let mut masks = active.iter().map(|&i| zs[i].child_mask());
+    let first_mask = masks.next().unwrap_or_default();
+    let mask = match op {
+        NaryOp::Join => masks.fold(first_mask, |mask, child_mask| mask.or(&child_mask)),
+        NaryOp::Meet => masks.fold(first_mask, |mask, child_mask| mask.and(&child_mask)),
+    };
+
+    let mut val = FatAlgebraicResult::none();
+    for &i in active.iter() {
+        let bit = nary_ident_bit(i);
+        let Some(arg) = zs[i].val() else {
+            if op == NaryOp::Meet {
+                val = FatAlgebraicResult::none();
+                break
+            }
+            continue
+        };
+        let Some(acc) = val.element.take() else {
+            val = FatAlgebraicResult::new(bit, Some(arg.clone()));
+            continue
+        };
+        let result = match op {
+            NaryOp::Join => acc.pjoin(arg),
+            NaryOp::Meet => acc.pmeet(arg),
+        };
+        val = match result {
+            AlgebraicResult::None => {
+                val = FatAlgebraicResult::none();
+                break
+            },
+            AlgebraicResult::Element(e) => FatAlgebraicResult::element(e),
+            AlgebraicResult::Identity(mask) => {
+                if mask & SELF_IDENT > 0 {
+                    let counter_bit = if mask & COUNTER_IDENT > 0 { bit } else { 0 };
+                    FatAlgebraicResult::new(val.identity_mask | counter_bit, Some(acc))
+                } else {
+                    FatAlgebraicResult::new(bit, Some(arg.clone()))
+                }
+            },
+        };
+    }
+
+    NaryFrame { byte, active, mask, next_idx: 0, val, children: vec![] }
+}
+
+/// Completes the merge of a node, once all of its children have been merged
+fn nary_finish<V, A>(zs: &mut [ReadZipperUntracked<V, A>], frame: &NaryFrame<V, A>, alloc: &A) -> FatAlgebraicResult<PathMap<V, A>>
+    where
+    V: Clone + Send + Sync + Unpin,
+    A: Allocator,
+{
+    if frame.val.element.is_none() && frame.children.is_empty() {
+        return FatAlgebraicResult::none()
+    }
+
+    //An operand is an identity if the result has exactly its value and its branches, and every branch is
+    // an identity of that operand
+    let mut result_mask = ByteMask::EMPTY;
+    for (byte, _) in frame.children.iter() {
+        result_mask.set_bit(*byte);
+    }
+    let mut identity_mask = 0;
+    for &i in frame.active.iter() {
+        let bit = nary_ident_bit(i);
+        let val_matches = match zs[i].val() {
+            Some(_) => frame.val.identity_mask & bit > 0,
+            None => frame.val.element.is_none(),
+        };
+        if bit != 0 && val_matches && zs[i].child_mask() == result_mask && frame.children.iter().all(|(_, child)| child.identity_mask & bit > 0) {
+            identity_mask |= bit;
+        }
+    }
+    if identity_mask != 0 {
+        return FatAlgebraicResult::new(identity_mask, None)
+    }
+
+    let mut result = PathMap::new_with_root_in(None, frame.val.element.clone(), alloc.clone());
+    let mut wz = result.write_zipper();
+    for (byte, child) in frame.children.iter() {
+        let (child_node, child_val) = match &child.element {
+            Some(child_map) => child_map.clone().into_root(),
+            None => {
+                let z = &mut zs[child.identity_mask.trailing_zeros() as usize];
+                z.descend_to_byte(*byte);
+                let child_map = nary_focus_map(z, alloc);
+                z.ascend_byte();
+                child_map.into_root()
+            },
+        };
+        wz.descend_to_byte(*byte);
+        wz.graft_map(PathMap::new_with_root_in(child_node, None, alloc.clone()));
+        if let Some(child_val) = child_val {
+            wz.set_val(child_val);
+        }
+        wz.ascend_byte();
+    }
+    drop(wz);
+    FatAlgebraicResult::element(result)
+}
+
+/// Makes a map of the subtrie and the value at the focus of `z`
+fn nary_focus_map<V, A>(z: &ReadZipperUntracked<V, A>, alloc: &A) -> PathMap<V, A>
+    where
+    V: Clone + Send + Sync + Unpin,
+    A: Allocator,
+{
+    //NOTE: The root value of the made map is replaced, because `make_map` only carries the focus value
+    // when the `graft_root_vals` feature is enabled
+    let root_node = z.make_map().and_then(|map| map.into_root().0);
+    PathMap::new_with_root_in(root_node, z.val().cloned(), alloc.clone())
+}
+
+/// Converts the result of [nary_op] into a `PathMap`.  `maps` must not be empty
+pub(crate) fn nary_result_into_map<V: Clone + Send + Sync + Unpin, A: Allocator>(result: FatAlgebraicResult<PathMap<V, A>>, maps: &[&PathMap<V, A>]) -> PathMap<V, A> {
+    match result.element {
+        Some(map) => map,
+        None if result.identity_mask != 0 => maps[result.identity_mask.trailing_zeros() as usize].clone(),
+        None => PathMap::new_in(maps[0].alloc.clone()),
+    }
+}
+
 impl<V: Clone + Lattice + Send + Sync + Unpin, A: Allocator> Lattice for PathMap<V, A> {
     fn pjoin(&self, other: &Self) -> AlgebraicResult<Self> {
         let joined_node = self.root().pjoin(&other.root());
@@ -1452,6 +2487,559 @@ mod tests {
         let result_map = map.meet(&all_but_root_map);
         assert_eq!(result_map.iter().count(), 2);
     }
+
+    #[test]
+    fn map_weighted_test1() {
+        use crate::ring::{Counting, LogProb, Tropical};
+
+        //Weighted join adds the weights of shared paths
+        let a: PathMap<Counting> = [("ab", Counting(1)), ("b", Counting(2))].into_iter().collect();
+        let b: PathMap<Counting> = [("ab", Counting(3)), ("c", Counting(1))].into_iter().collect();
+        let joined = a.weighted_join(&b);
+        assert_eq!(joined.val_count(), 3);
+        assert_eq!(joined.get("ab"), Some(&Counting(4)));
+        assert_eq!(joined.get("b"), Some(&Counting(2)));
+        assert_eq!(joined.get("c"), Some(&Counting(1)));
+
+        //Weighted product counts the number of ways each path can be split
+        let words: PathMap<Counting> = [("a", Counting(1)), ("ab", Counting(1)), ("b", Counting(1)), ("ba", Counting(1))].into_iter().collect();
+        let pairs = words.weighted_product(&words);
+        assert_eq!(pairs.get("aba"), Some(&Counting(2))); //"a"+"ba", "ab"+"a"
+        assert_eq!(pairs.get("abab"), Some(&Counting(1)));
+        assert_eq!(pairs.get("bb"), Some(&Counting(1)));
+        assert_eq!(pairs.get("bab"), Some(&Counting(2))); //"b"+"ab", "ba"+"b"
+        assert_eq!(pairs.val_count(), 14);
+        assert_eq!(pairs.best_weighted_path(), Some((b"aba".to_vec(), Counting(2))));
+
+        //The root value is the empty path, so it acts like the empty string
+        let mut with_empty = words.clone();
+        with_empty.set_val_at([], Counting(1));
+        let pairs = with_empty.weighted_product(&words);
+        assert_eq!(pairs.get("ab"), Some(&Counting(2))); //""+"ab", "a"+"b"
+        assert_eq!(pairs.get("b"), Some(&Counting(1)));
+
+        //Shortest path through a two-step tropical automaton
+        let first: PathMap<Tropical> = [("x", Tropical(1.0)), ("y", Tropical(3.0))].into_iter().collect();
+        let second: PathMap<Tropical> = [("1", Tropical(5.0)), ("2", Tropical(2.0))].into_iter().collect();
+        let paths = first.weighted_product(&second);
+        assert_eq!(paths.val_count(), 4);
+        assert_eq!(paths.best_weighted_path(), Some((b"x2".to_vec(), Tropical(3.0))));
+        assert_eq!(PathMap::<Tropical>::new().best_weighted_path(), None);
+
+        //Most probable path, ignoring zero-probability paths
+        let first: PathMap<LogProb> = [("x", LogProb::from_prob(0.9)), ("y", LogProb::from_prob(0.1)), ("z", LogProb::from_prob(0.0))].into_iter().collect();
+        let second: PathMap<LogProb> = [("1", LogProb::from_prob(0.4)), ("2", LogProb::from_prob(0.6))].into_iter().collect();
+        let paths = first.weighted_product(&second);
+        assert_eq!(paths.val_count(), 4);
+        assert!(paths.get_val_at("z").is_none() && !paths.read_zipper_at_path("z").path_exists());
+        let (best_path, best_w) = paths.best_weighted_path().unwrap();
+        assert_eq!(best_path, b"x2");
+        assert!((best_w.prob() - 0.54).abs() < 1e-12);
+        let total = paths.weighted_join(&paths);
+        assert!((total.get("y1").unwrap().prob() - 0.08).abs() < 1e-12);
+    }
+
+    #[test]
+    fn map_concat_test1() {
+        fn naive_concat(a: &PathMap<Vec<u64>>, b: &PathMap<Vec<u64>>) -> Vec<(Vec<u8>, Vec<u64>)> {
+            let mut expected = PathMap::<Vec<u64>>::new();
+            for (prefix, _) in a.iter() {
+                for (suffix, val) in b.iter() {
+                    let mut path = prefix.clone();
+                    path.extend(suffix);
+                    let existing = expected.get_val_or_set_mut_at(&path, vec![]);
+                    existing.extend(val.iter().cloned());
+                    existing.sort();
+                    existing.dedup();
+                }
+            }
+            expected.iter().map(|(k, v)| (k, v.clone())).collect()
+        }
+
+        let a: PathMap<Vec<u64>> = [("a", vec![1]), ("ab", vec![2]), ("b", vec![3]), ("bab", vec![4])].into_iter().collect();
+        let b: PathMap<Vec<u64>> = [("a", vec![10]), ("ba", vec![20]), ("b", vec![30]), ("", vec![40])].into_iter().collect();
+        let product = a.concat(&b);
+        let product_pairs: Vec<(Vec<u8>, Vec<u64>)> = product.iter().map(|(k, v)| (k, v.clone())).collect();
+        assert_eq!(product_pairs, naive_concat(&a, &b));
+        assert_eq!(product.get("aba"), Some(&vec![10, 20])); //"a"+"ba" joined with "ab"+"a"
+        assert_eq!(product.get("ab"), Some(&vec![30, 40])); //"ab"+"" joined with "a"+"b"
+
+        //Without overlapping paths, every copy of `b` is the same node in memory.  The node id is unavailable
+        // at a focus with a value, so this `b` doesn't have a root value
+        let a: PathMap<Vec<u64>> = [("x", vec![1]), ("yy", vec![2]), ("zzz", vec![3])].into_iter().collect();
+        let b: PathMap<Vec<u64>> = [("a", vec![10]), ("ba", vec![20]), ("b", vec![30])].into_iter().collect();
+        let product = a.concat(&b);
+        assert_eq!(product.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), naive_concat(&a, &b));
+        let mut rz = product.read_zipper();
+        rz.descend_to("x");
+        let b_node_id = rz.shared_node_id();
+        assert!(b_node_id.is_some());
+        for prefix in ["yy", "zzz"] {
+            rz.reset();
+            rz.descend_to(prefix);
+            assert_eq!(rz.shared_node_id(), b_node_id);
+        }
+
+        //Concatenating with an empty map is empty
+        assert!(a.concat(&PathMap::new()).is_empty());
+        assert!(PathMap::<Vec<u64>>::new().concat(&a).is_empty());
+
+        //Combine the values from both maps
+        let a: PathMap<u64> = [("a", 1), ("ab", 2)].into_iter().collect();
+        let b: PathMap<u64> = [("b", 10), ("", 20)].into_iter().collect();
+        let summed = a.concat_with(&b, |prefix, suffix| prefix + suffix);
+        assert_eq!(summed.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>(), vec![
+            (b"a".to_vec(), 21),
+            (b"ab".to_vec(), 22), //"ab"+"" is 22, and the u64 join keeps it over "a"+"b"
+            (b"abb".to_vec(), 12),
+        ]);
+    }
+
+    #[test]
+    fn map_left_quotient_test1() {
+        let map: PathMap<Vec<u64>> = [("ab", vec![1]), ("abc", vec![2]), ("b", vec![3]), ("bc", vec![4]), ("xyz", vec![5]), ("", vec![6])].into_iter().collect();
+
+        //"b" follows "a" and "", "bc" follows "a" and "", etc.  Values from several matches are joined
+        let prefixes: PathMap<Vec<u64>> = [("a", vec![]), ("", vec![]), ("q", vec![])].into_iter().collect();
+        let quotient = map.left_quotient(&prefixes);
+        let mut expected: Vec<(Vec<u8>, Vec<u64>)> = map.iter().map(|(k, v)| (k, v.clone())).collect();
+        assert_eq!(expected[3].0, b"b");
+        expected[3].1 = vec![1, 3];
+        assert_eq!(expected[4].0, b"bc");
+        expected[4].1 = vec![2, 4];
+        assert_eq!(quotient.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected);
+
+        //Prefixes that end on an interior path, and prefixes that don't match
+        let prefixes: PathMap<Vec<u64>> = [("x", vec![]), ("ab", vec![]), ("nope", vec![])].into_iter().collect();
+        let quotient = map.left_quotient(&prefixes);
+        assert_eq!(quotient.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), vec![
+            (vec![], vec![1]),
+            (b"c".to_vec(), vec![2]),
+            (b"yz".to_vec(), vec![5]),
+        ]);
+
+        //Prefixes that don't lead to values don't strip anything
+        let mut prefixes = PathMap::<Vec<u64>>::new();
+        prefixes.create_path("ab");
+        assert!(map.left_quotient(&prefixes).is_empty());
+        assert!(map.left_quotient(&PathMap::new()).is_empty());
+
+        //Quotient is the inverse of concat
+        let a: PathMap<Vec<u64>> = [("k1:", vec![]), ("k2:", vec![])].into_iter().collect();
+        let b: PathMap<Vec<u64>> = [("x", vec![7]), ("yy", vec![8])].into_iter().collect();
+        let round_trip = a.concat(&b).left_quotient(&a);
+        assert_eq!(round_trip.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), b.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn map_compose_test1() {
+        //Fixed-width relations, where `a` and `b` are 2 bytes and `c` is 1 byte
+        let mut r = PathMap::<Vec<u64>>::new();
+        let mut s = PathMap::<Vec<u64>>::new();
+        for i in 0u64..40 {
+            let a = [(i % 7) as u8, (i % 3) as u8];
+            let b = [(i * 5 % 11) as u8, (i % 2) as u8];
+            r.set_val_at([a, b].concat(), vec![1000 + i]);
+            let c = [(i * 3 % 13) as u8];
+            s.set_val_at([b.to_vec(), c.to_vec()].concat(), vec![i]);
+            s.set_val_at([[(i % 11) as u8, 1].to_vec(), c.to_vec()].concat(), vec![100 + i]);
+        }
+
+        let mut expected = PathMap::<Vec<u64>>::new();
+        for (r_path, _) in r.iter() {
+            for (s_path, s_val) in s.iter() {
+                if r_path[2..] == s_path[..2] {
+                    let path = [&r_path[..2], &s_path[2..]].concat();
+                    let existing = expected.get_val_or_set_mut_at(&path, vec![]);
+                    existing.extend(s_val.iter().cloned());
+                    existing.sort();
+                    existing.dedup();
+                }
+            }
+        }
+        let composed = r.compose(&s, 2);
+        assert!(expected.val_count() > 0);
+        assert_eq!(composed.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+
+        //Reachability in exactly three hops
+        let edges: PathMap<()> = ["ab", "bc", "bd", "cd", "de", "ea"].into_iter().map(|k| (k, ())).collect();
+        let three_hops = edges.compose(&edges, 1).compose(&edges, 1);
+        let keys: Vec<Vec<u8>> = three_hops.iter().map(|(k, _)| k).collect();
+        assert_eq!(keys, vec![b"ad".to_vec(), b"ae".to_vec(), b"ba".to_vec(), b"be".to_vec(), b"ca".to_vec(), b"db".to_vec(), b"ec".to_vec(), b"ed".to_vec()]);
+
+        //A `b ++ c` path with an empty `c` puts the value at `a`
+        let r: PathMap<Vec<u64>> = [("xb", vec![]), ("yq", vec![])].into_iter().collect();
+        let s: PathMap<Vec<u64>> = [("b", vec![1]), ("bz", vec![2])].into_iter().collect();
+        let composed = r.compose(&s, 1);
+        assert_eq!(composed.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), vec![
+            (b"x".to_vec(), vec![1]),
+            (b"xz".to_vec(), vec![2]),
+        ]);
+
+        //With an empty `a`, the composition is the left quotient
+        assert_eq!(s.compose(&r, 0).iter().collect::<Vec<_>>(), r.left_quotient(&s).iter().collect::<Vec<_>>());
+        assert!(r.compose(&s, 3).is_empty());
+    }
+
+    #[test]
+    fn map_project_test1() {
+        //Paths are `u32 ++ u64 ++ u16`, with a tail on some of them, and a value at a path shorter than the layout
+        let layout = [4, 8, 2];
+        let mut map = PathMap::<Vec<u64>>::new();
+        let mut paths = vec![];
+        for i in 0u64..200 {
+            let mut path = ((i % 9) as u32).to_be_bytes().to_vec();
+            path.extend_from_slice(&(i * 1000 % 7919).to_be_bytes());
+            path.extend_from_slice(&((i % 5) as u16).to_be_bytes());
+            if i % 4 == 0 {
+                path.extend_from_slice(b"tail");
+            }
+            map.set_val_at(&path, vec![i]);
+            paths.push(path);
+        }
+        map.set_val_at([0, 0, 0, 1, 0], vec![999]);
+
+        let project_expected = |keep: &[usize]| {
+            let mut expected = PathMap::<Vec<u64>>::new();
+            for path in paths.iter() {
+                let mut fields = vec![];
+                let mut start = 0;
+                for width in layout {
+                    fields.push(&path[start..start + width]);
+                    start += width;
+                }
+                let mut projected: Vec<u8> = keep.iter().flat_map(|&field| fields[field].iter().copied()).collect();
+                projected.extend_from_slice(&path[start..]);
+                let val = map.get_val_at(path).unwrap();
+                match expected.get_val_mut_at(&projected) {
+                    Some(existing) => { existing.join_into(val.clone()); },
+                    None => { expected.set_val_at(&projected, val.clone()); },
+                }
+            }
+            expected
+        };
+        for keep in [&[0, 1, 2][..], &[0, 2], &[1], &[2], &[0, 1], &[]] {
+            let projected = map.project(&layout, keep);
+            let expected = project_expected(keep);
+            assert_eq!(projected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+        }
+
+        //Permutations, checked against the projection that keeps every field
+        for order in [[0, 2, 1], [1, 0, 2], [2, 1, 0], [1, 2, 0]] {
+            let permuted = map.permute(&layout, &order);
+            let expected = project_expected(&order);
+            assert_eq!(permuted.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+            let inverse: Vec<usize> = (0..3).map(|field| order.iter().position(|&f| f == field).unwrap()).collect();
+            assert_eq!(permuted.permute(&[layout[order[0]], layout[order[1]], layout[order[2]]], &inverse).val_count(), 200);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn map_permute_bad_order_test() {
+        let map: PathMap<()> = ["abc"].into_iter().map(|k| (k, ())).collect();
+        let _ = map.permute(&[1, 2], &[0, 0]);
+    }
+
+    #[test]
+    fn map_join_all_test1() {
+        //More operands than fit in the identity mask
+        let maps: Vec<PathMap<Vec<u64>>> = (0u64..70).map(|k| {
+            (0u64..60).filter(|i| (i * 7 + k) % 5 < 2)
+                .map(|i| (format!("{:02}:{}", i % 23, (i + k) % 4), vec![k % 9]))
+                .collect()
+        }).collect();
+        let expected = maps.iter().fold(PathMap::new(), |acc, map| acc.join(map));
+        let joined = PathMap::join_all(maps.iter());
+        assert!(expected.val_count() > 0);
+        assert_eq!(joined.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+
+        //A subset joined with its superset gives back the superset, including the root value
+        let mut big = maps[3].join(&maps[4]);
+        big.set_val_at([], vec![1, 2]);
+        let mut small = maps[3].clone();
+        small.set_val_at([], vec![2]);
+        let joined = PathMap::join_all([&small, &big, &PathMap::new()]);
+        assert_eq!(joined.iter().collect::<Vec<_>>(), big.iter().collect::<Vec<_>>());
+        assert_eq!(joined.get_val_at([]), Some(&vec![1, 2]));
+
+        //Branches that only one operand has are grafted, rather than copied
+        let left: PathMap<Vec<u64>> = (0u64..100).map(|i| (format!("left:{i}"), vec![i])).collect();
+        let right: PathMap<Vec<u64>> = (0u64..100).map(|i| (format!("right:{i}"), vec![i])).collect();
+        let joined = PathMap::join_all([&left, &right]);
+        assert_eq!(joined.val_count(), 200);
+        let mut joined_z = joined.read_zipper();
+        joined_z.descend_to(b"left:");
+        let mut left_z = left.read_zipper();
+        left_z.descend_to(b"left:");
+        assert!(joined_z.shared_node_id().is_some());
+        assert_eq!(joined_z.shared_node_id(), left_z.shared_node_id());
+
+        assert!(PathMap::<()>::join_all([]).is_empty());
+    }
+
+    #[test]
+    fn map_meet_all_test1() {
+        let maps: Vec<PathMap<Vec<u64>>> = (0u64..70).map(|k| {
+            (0u64..120).filter(|i| i % 3 == 0 || (i + k) % 11 != 0)
+                .map(|i| (format!("{:02}/{}", i % 37, i % 5), if i % 2 == 0 { vec![0, 1 + k % 3] } else { vec![1] }))
+                .collect()
+        }).collect();
+        let expected = maps[1..].iter().fold(maps[0].clone(), |acc, map| acc.meet(map));
+        let met = PathMap::meet_all(maps.iter());
+        assert!(expected.val_count() > 0);
+        assert_eq!(met.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+
+        //Meeting with a superset gives back the subset
+        let big = maps[0].join(&maps[1]);
+        let met = PathMap::meet_all([&big, &maps[0], &big]);
+        assert_eq!(met.iter().collect::<Vec<_>>(), maps[0].iter().collect::<Vec<_>>());
+
+        //Any empty operand empties the result
+        assert!(PathMap::meet_all([&big, &PathMap::new()]).is_empty());
+        assert!(PathMap::<()>::meet_all([]).is_empty());
+    }
+
+    #[test]
+    fn map_product_test1() {
+        let a: PathMap<()> = ["0", "1"].into_iter().map(|k| (k, ())).collect();
+        let b: PathMap<()> = ["-", "+"].into_iter().map(|k| (k, ())).collect();
+        let c: PathMap<()> = ["x", "yz"].into_iter().map(|k| (k, ())).collect();
+
+        let product = PathMap::product(&[&a, &b, &c]);
+        assert_eq!(product.val_count(), 8);
+        assert!(product.contains("1+yz"));
+        assert!(!product.contains("1+"));
+        assert_eq!(product.iter().map(|(k, _)| k).collect::<Vec<_>>(), a.concat(&b).concat(&c).iter().map(|(k, _)| k).collect::<Vec<_>>());
+
+        //The product zipper traverses the same paths, after skipping the values of the earlier factors
+        let mut pz = ProductZipper::new(a.read_zipper(), [b.read_zipper(), c.read_zipper()]);
+        let mut pz_paths = vec![];
+        while pz.to_next_val() {
+            if pz.focus_factor() == 2 {
+                pz_paths.push(pz.path().to_vec());
+            }
+        }
+        assert_eq!(product.iter().map(|(k, _)| k).collect::<Vec<_>>(), pz_paths);
+
+        assert_eq!(PathMap::product(&[&a]).val_count(), 2);
+        assert!(PathMap::<()>::product(&[]).is_empty());
+    }
+
+    #[test]
+    fn map_join_with_test1() {
+        use crate::ring::{KeepSelf, MergePolicy, TakeOther};
+        let a: PathMap<u64> = [("apple", 1), ("apricot", 2), ("banana", 3), ("", 4)].into_iter().collect();
+        let b: PathMap<u64> = [("apricot", 20), ("blueberry", 30), ("banana", 40), ("", 50)].into_iter().collect();
+
+        let sum = a.join_with(&b, &|a: &u64, b: &u64| AlgebraicResult::Element(a + b));
+        let mut items: Vec<(Vec<u8>, u64)> = sum.iter().map(|(k, v)| (k, *v)).collect();
+        items.sort();
+        assert_eq!(items, vec![(b"".to_vec(), 54), (b"apple".to_vec(), 1), (b"apricot".to_vec(), 22), (b"banana".to_vec(), 43), (b"blueberry".to_vec(), 30)]);
+
+        let last_writer_wins = a.join_with(&b, &TakeOther);
+        assert_eq!(last_writer_wins.val_count(), 5);
+        assert_eq!(last_writer_wins.get("apricot"), Some(&20));
+        assert_eq!(last_writer_wins.get("apple"), Some(&1));
+        assert_eq!(last_writer_wins.root_val(), Some(&50));
+
+        //A policy chosen at runtime
+        let policies: [Box<dyn MergePolicy<u64>>; 2] = [Box::new(KeepSelf), Box::new(|a: &u64, b: &u64| AlgebraicResult::Element(*a.max(b)))];
+        let first_writer_wins = a.join_with(&b, &*policies[0]);
+        assert_eq!(first_writer_wins.get("apricot"), Some(&2));
+        assert_eq!(first_writer_wins.get("blueberry"), Some(&30));
+        let max = a.join_with(&b, &*policies[1]);
+        assert_eq!(max.get("banana"), Some(&40));
+
+        //Removing values where they collide
+        let exclusive = a.join_with(&b, &|_a: &u64, _b: &u64| AlgebraicResult::None);
+        let mut keys: Vec<Vec<u8>> = exclusive.iter().map(|(k, _v)| k).collect();
+        keys.sort();
+        assert_eq!(keys, vec![b"apple".to_vec(), b"blueberry".to_vec()]);
+
+        //Identity results leave the map's nodes shared
+        let sub: PathMap<u64> = [("apricot", 5), ("banana", 6)].into_iter().collect();
+        assert_eq!(policy_op(&a, &sub, &KeepSelf, PolicyOp::Join).identity_mask(), Some(SELF_IDENT));
+        let joined = a.join_with(&sub, &KeepSelf);
+        assert!(joined.root().unwrap().ptr_eq(a.root().unwrap()));
+        assert_eq!(policy_op(&sub, &a, &TakeOther, PolicyOp::Join).identity_mask(), Some(COUNTER_IDENT));
+        assert_eq!(policy_op(&a, &a.clone(), &|_a: &u64, _b: &u64| AlgebraicResult::Identity(SELF_IDENT | COUNTER_IDENT), PolicyOp::Join).identity_mask(),
+            Some(SELF_IDENT | COUNTER_IDENT));
+    }
+
+    #[test]
+    fn map_meet_subtract_with_test1() {
+        let a: PathMap<u64> = [("a", 1), ("ab", 2), ("abc", 3), ("b", 4), ("bcd", 5)].into_iter().collect();
+        let b: PathMap<u64> = [("ab", 10), ("abc", 1), ("bc", 6), ("bcd", 5), ("c", 7)].into_iter().collect();
+
+        let sum = a.meet_with(&b, &|a: &u64, b: &u64| AlgebraicResult::Element(a + b));
+        let mut items: Vec<(Vec<u8>, u64)> = sum.iter().map(|(k, v)| (k, *v)).collect();
+        items.sort();
+        assert_eq!(items, vec![(b"ab".to_vec(), 12), (b"abc".to_vec(), 4), (b"bcd".to_vec(), 10)]);
+
+        let saturating_sub = |a: &u64, b: &u64| {
+            match a.saturating_sub(*b) {
+                0 => AlgebraicResult::None,
+                v if v == *a => AlgebraicResult::Identity(SELF_IDENT),
+                v => AlgebraicResult::Element(v),
+            }
+        };
+        let diff = a.subtract_with(&b, &saturating_sub);
+        let mut items: Vec<(Vec<u8>, u64)> = diff.iter().map(|(k, v)| (k, *v)).collect();
+        items.sort();
+        assert_eq!(items, vec![(b"a".to_vec(), 1), (b"abc".to_vec(), 2), (b"b".to_vec(), 4)]);
+        assert!(!diff.path_exists_at("bcd"));
+
+        //Meeting with disjoint maps is empty
+        let c: PathMap<u64> = [("x", 1)].into_iter().collect();
+        assert!(a.meet_with(&c, &crate::ring::KeepSelf).is_empty());
+        assert_eq!(a.subtract_with(&a, &saturating_sub).val_count(), 0);
+    }
+
+    /// The lattice policies should produce the same results as the lattice operations on `()` values
+    #[test]
+    fn map_lattice_policies_test1() {
+        use crate::ring::{JoinPolicy, MeetPolicy, SubtractPolicy};
+        let key_sets: [&[&str]; 5] = [
+            &[],
+            &["a", "b", "c"],
+            &["ab", "abc", "abcd", "b", "bb"],
+            &["romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus"],
+            &["rom", "roman", "rubicon", "x", "", "xyz"],
+        ];
+        for a_keys in key_sets {
+            for b_keys in key_sets {
+                let a: PathMap<()> = a_keys.iter().map(|k| (k, ())).collect();
+                let b: PathMap<()> = b_keys.iter().map(|k| (k, ())).collect();
+                let sorted_keys = |m: &PathMap<()>| { let mut keys: Vec<Vec<u8>> = m.iter().map(|(k, _v)| k).collect(); keys.sort(); keys };
+                assert_eq!(sorted_keys(&a.join_with(&b, &JoinPolicy)), sorted_keys(&a.join(&b)));
+                assert_eq!(sorted_keys(&a.meet_with(&b, &MeetPolicy)), sorted_keys(&a.meet(&b)));
+                let mut expected: Vec<Vec<u8>> = a_keys.iter().filter(|k| !b_keys.contains(k)).map(|k| k.as_bytes().to_vec()).collect();
+                expected.sort();
+                assert_eq!(sorted_keys(&a.subtract_with(&b, &SubtractPolicy)), expected);
+            }
+        }
+    }
+
+    /// The policy operations should agree with the same operations on a `BTreeMap`, wherever the tries diverge
+    #[test]
+    fn map_policy_op_test1() {
+        use std::collections::BTreeMap;
+        use crate::ring::{KeepSelf, TakeOther};
+        let key_sets: [&[&str]; 6] = [
+            &[],
+            &["pre/ab:wxyz1"],
+            &["pre/ab:wxyz1", "pre/ab:wxyz2", "pre/b"],
+            &["pre/ab:", "pre/ab:wx", "pre/ab:wxyz1", "q"],
+            &["", "pre/a", "pre/ab:wxyz1", "pre/ab:wxyz3", "pre/ab:wy", "z"],
+            &["romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus"],
+        ];
+        let policies: [&dyn MergePolicy<u64>; 4] = [
+            &KeepSelf,
+            &TakeOther,
+            &|a: &u64, b: &u64| AlgebraicResult::Element(a + b),
+            &|a: &u64, b: &u64| if a == b { AlgebraicResult::None } else { AlgebraicResult::Identity(COUNTER_IDENT) },
+        ];
+        let model_merge = |policy: &dyn MergePolicy<u64>, a: &u64, b: &u64| match policy.merge(a, b) {
+            AlgebraicResult::Identity(mask) => Some(if mask & SELF_IDENT > 0 { *a } else { *b }),
+            AlgebraicResult::Element(v) => Some(v),
+            AlgebraicResult::None => None,
+        };
+        for (a_idx, a_keys) in key_sets.iter().enumerate() {
+            for (b_idx, b_keys) in key_sets.iter().enumerate() {
+                let a_model: BTreeMap<Vec<u8>, u64> = a_keys.iter().enumerate().map(|(i, k)| (k.as_bytes().to_vec(), (i % 2) as u64)).collect();
+                let b_model: BTreeMap<Vec<u8>, u64> = b_keys.iter().enumerate().map(|(i, k)| (k.as_bytes().to_vec(), (i % 3) as u64)).collect();
+                let a: PathMap<u64> = a_model.iter().map(|(k, v)| (k, *v)).collect();
+                let b: PathMap<u64> = b_model.iter().map(|(k, v)| (k, *v)).collect();
+                for policy in policies {
+                    let mut joined = a_model.clone();
+                    let mut met = BTreeMap::new();
+                    let mut subtracted = a_model.clone();
+                    for (k, b_val) in b_model.iter() {
+                        match a_model.get(k) {
+                            Some(a_val) => {
+                                let merged = model_merge(policy, a_val, b_val);
+                                joined.remove(k);
+                                subtracted.remove(k);
+                                if let Some(v) = merged {
+                                    joined.insert(k.clone(), v);
+                                    met.insert(k.clone(), v);
+                                    subtracted.insert(k.clone(), v);
+                                }
+                            },
+                            None => { joined.insert(k.clone(), *b_val); },
+                        }
+                    }
+                    let items = |m: &PathMap<u64>| m.iter().map(|(k, v)| (k, *v)).collect::<BTreeMap<Vec<u8>, u64>>();
+                    assert_eq!(items(&a.join_with(&b, policy)), joined, "join {a_idx} {b_idx}");
+                    assert_eq!(items(&a.meet_with(&b, policy)), met, "meet {a_idx} {b_idx}");
+                    assert_eq!(items(&a.subtract_with(&b, policy)), subtracted, "subtract {a_idx} {b_idx}");
+
+                    //The same operations through a write zipper below a path
+                    let results = [(PolicyOp::Join, &joined), (PolicyOp::Meet, &met), (PolicyOp::Subtract, &subtracted)];
+                    for (op, expected) in results {
+                        let mut map = PathMap::new();
+                        map.write_zipper_at_path(b"in:").graft_map(a.clone());
+                        map.set_val_at(b"out", 9);
+                        let mut wz = map.write_zipper_at_path(b"in:");
+                        match op {
+                            PolicyOp::Join => wz.join_into_with(&b.read_zipper(), policy),
+                            PolicyOp::Meet => wz.meet_into_with(&b.read_zipper(), policy, true),
+                            PolicyOp::Subtract => wz.subtract_into_with(&b.read_zipper(), policy, true),
+                        };
+                        drop(wz);
+                        //Without `graft_root_vals`, the root values are neither grafted nor combined
+                        let mut expected: BTreeMap<Vec<u8>, u64> = expected.iter()
+                            .filter(|(k, _v)| cfg!(feature = "graft_root_vals") || !k.is_empty())
+                            .map(|(k, v)| ([&b"in:"[..], k].concat(), *v)).collect();
+                        expected.insert(b"out".to_vec(), 9);
+                        assert_eq!(items(&map), expected, "{op:?} into {a_idx} {b_idx}");
+                    }
+                }
+            }
+        }
+    }
+
+    /// Subtries of the result that are identities of `other` should be shared with `other`
+    #[test]
+    fn map_policy_op_test2() {
+        use crate::ring::TakeOther;
+        use crate::zipper::zipper_priv::ZipperPriv;
+        let a: PathMap<u64> = (0..64u8).map(|i| ([b'a', i], 1)).chain((0..64u8).map(|i| ([b'b', i], 1))).collect();
+        let b: PathMap<u64> = (0..64u8).map(|i| ([b'a', i], 2)).chain((0..64u8).map(|i| ([b'c', i], 2))).collect();
+        let joined = a.join_with(&b, &TakeOther);
+        assert_eq!(joined.val_count(), 192);
+        for (path, source) in [(b"a", &b), (b"b", &a), (b"c", &b)] {
+            let joined_node = joined.read_zipper_at_path(path).get_focus().into_option().unwrap();
+            let source_node = source.read_zipper_at_path(path).get_focus().into_option().unwrap();
+            assert!(joined_node.ptr_eq(&source_node));
+        }
+    }
+
+    #[test]
+    fn zipper_join_into_with_test1() {
+        use crate::ring::KeepSelf;
+        let mut map: PathMap<u64> = [("in:a", 1), ("in:b", 2), ("out", 3)].into_iter().collect();
+        let src: PathMap<u64> = [("b", 10), ("c", 20)].into_iter().collect();
+        let sub: PathMap<u64> = [("a", 100)].into_iter().collect();
+
+        let mut wz = map.write_zipper_at_path(b"in:");
+        let status = wz.join_into_with(&src.read_zipper(), &|a: &u64, b: &u64| AlgebraicResult::Element(a * b));
+        assert_eq!(status, AlgebraicStatus::Element);
+        assert_eq!(wz.join_into_with(&sub.read_zipper(), &KeepSelf), AlgebraicStatus::Identity);
+        assert_eq!(wz.meet_into_with(&src.read_zipper(), &KeepSelf, true), AlgebraicStatus::Element);
+        drop(wz);
+
+        let mut items: Vec<(Vec<u8>, u64)> = map.iter().map(|(k, v)| (k, *v)).collect();
+        items.sort();
+        assert_eq!(items, vec![(b"in:b".to_vec(), 20), (b"in:c".to_vec(), 20), (b"out".to_vec(), 3)]);
+
+        let mut wz = map.write_zipper_at_path(b"in:");
+        assert_eq!(wz.subtract_into_with(&src.read_zipper(), &|_a: &u64, _b: &u64| AlgebraicResult::None, true), AlgebraicStatus::None);
+        drop(wz);
+        assert_eq!(map.val_count(), 1);
+        assert!(!map.path_exists_at("in:"));
+    }
 }
 
 //GOAT, Consider refactor of zipper traits.  `WriteZipper` -> `PathWriter`.  Zipper is split into the zipper
@@ -3,7 +3,7 @@ use core::slice;
 use core::mem::MaybeUninit;
 
 use crate::alloc::{global_alloc, Allocator, GlobalAlloc};
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 use crate::PathMap;
 use crate::trie_node::*;
 use crate::zipper::*;
@@ -242,6 +242,25 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for TrieRefB
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for TrieRefBorrowed<'_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        if !self.path_exists() {
+            return alg_f(&ByteMask::EMPTY, &mut TakableSlice::default(), None, &[])
+        }
+        self.fork_read_zipper().summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        if !self.path_exists() {
+            return 0
+        }
+        self.fork_read_zipper().val_count()
+    }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for TrieRefBorrowed<'_, V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> {
         #[cfg(not(feature = "graft_root_vals"))]
@@ -571,6 +590,25 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for TrieRefO
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for TrieRefOwned<V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        if !self.path_exists() {
+            return alg_f(&ByteMask::EMPTY, &mut TakableSlice::default(), None, &[])
+        }
+        self.fork_read_zipper().summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        if !self.path_exists() {
+            return 0
+        }
+        self.fork_read_zipper().val_count()
+    }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for TrieRefOwned<V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> {
         #[cfg(not(feature = "graft_root_vals"))]
@@ -739,6 +777,25 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for TrieRef<
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for TrieRef<'_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        match self {
+            TrieRef::Borrowed(trie_ref) => trie_ref.summarize_fallible(alg_f),
+            TrieRef::Owned(trie_ref) => trie_ref.summarize_fallible(alg_f),
+        }
+    }
+    fn val_count(&self) -> usize {
+        match self {
+            TrieRef::Borrowed(trie_ref) => trie_ref.val_count(),
+            TrieRef::Owned(trie_ref) => trie_ref.val_count(),
+        }
+    }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for TrieRef<'_, V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> {
         match self {
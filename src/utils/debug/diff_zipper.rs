@@ -1,7 +1,7 @@
 
 use crate::alloc::Allocator;
 use crate::zipper::*;
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 
 /// A [Zipper] type that wraps two other zippers that are expected to behave identically,
 /// and panics when they don't.  Useful to debug a Zipper implementation.
@@ -66,12 +66,6 @@ impl<A: Zipper + ZipperMoving, B: Zipper + ZipperMoving> ZipperMoving for DiffZi
         assert_eq!(a, b);
         a
     }
-    fn val_count(&self) -> usize {
-        let a = self.a.val_count();
-        let b = self.b.val_count();
-        assert_eq!(a, b);
-        a
-    }
     fn descend_to<P: AsRef<[u8]>>(&mut self, path: P) {
         let path = path.as_ref();
         self.a.descend_to(path);
@@ -208,6 +202,24 @@ impl<A: Zipper + ZipperAbsolutePath, B: Zipper + ZipperAbsolutePath> ZipperAbsol
     }
 }
 
+impl<V, A: Zipper + TrieSummary<V>, B: Zipper + TrieSummary<V>> TrieSummary<V> for DiffZipper<A, B>
+{
+    /// NOTE: Only the summary from `a` is returned, because `W` can't be compared
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.a.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        let a = self.a.val_count();
+        let b = self.b.val_count();
+        assert_eq!(a, b);
+        a
+    }
+}
+
 impl<A: Zipper + ZipperPathBuffer, B: Zipper + ZipperPathBuffer> ZipperPathBuffer for DiffZipper<A, B>
 {
     unsafe fn origin_path_assert_len(&self, len: usize) -> &[u8] {
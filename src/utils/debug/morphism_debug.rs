@@ -1,6 +1,6 @@
 //! Debug utilities for catamorphisms and other morphisms
 
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 use crate::alloc::Allocator;
 use crate::PathMap;
 use crate::zipper::*;
@@ -20,16 +20,17 @@ pub trait CatamorphismDebug<V> {
     fn into_cata_jumping_cached_fallible_debug<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8], &[u8]) -> Result<W, E>;
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8], &[u8]) -> Result<W, E>;
 }
 
 impl<'a, Z, V: 'a> CatamorphismDebug<V> for Z where Z: Zipper + ZipperReadOnlyConditionalValues<'a, V> + ZipperConcrete + ZipperAbsolutePath + ZipperPathBuffer {
     fn into_cata_jumping_cached_fallible_debug<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
     where
         W: Clone,
-        AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8], &[u8]) -> Result<W, E>
+        AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8], &[u8]) -> Result<W, E>
     {
-        into_cata_cached_body::<Self, V, W, E, _, DoCache, true, true>(self, alg_f)
+        into_cata_cached_body::<Self, V, W, E, _, _, true, true>(self, DoCache::new(),
+            |_z, mask, children, val, sub_path, path| alg_f(mask, children, val, sub_path, path))
     }
 }
 
@@ -37,7 +38,7 @@ impl<V: 'static + Clone + Send + Sync + Unpin, A: Allocator + 'static> Catamorph
     fn into_cata_jumping_cached_fallible_debug<W, E, AlgF>(self, alg_f: AlgF) -> Result<W, E>
         where
             W: Clone,
-            AlgF: Fn(&ByteMask, &mut [W], Option<&V>, &[u8], &[u8]) -> Result<W, E>
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8], &[u8]) -> Result<W, E>
     {
         let rz = self.into_read_zipper(&[]);
         rz.into_cata_jumping_cached_fallible_debug(alg_f)
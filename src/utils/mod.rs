@@ -5,6 +5,9 @@ pub mod ints;
 
 pub mod debug;
 
+mod takable_slice;
+pub use takable_slice::{TakableSlice, TakableVec};
+
 /// Use `fast_slice_utils` directly.  We don't want to maintain this re-export from pathmap
 //GOAT, remove this re-export when nothing downstream is going to break
 #[deprecated]
@@ -0,0 +1,311 @@
+//! Containers that allow individual elements to be moved out, without requiring `T: Default`
+
+use core::mem::MaybeUninit;
+use core::ops::{Bound, Deref, DerefMut, Index, IndexMut, RangeBounds};
+
+/// A [Vec]-like container, from which a [TakableSlice] can be borrowed
+///
+/// Elements taken through a `TakableSlice` are moved out of the `TakableVec`, and the `TakableVec`
+/// will only drop the elements that remain.
+pub struct TakableVec<T> {
+    elements: Vec<MaybeUninit<T>>,
+    taken: Vec<bool>,
+}
+
+impl<T> Default for TakableVec<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TakableVec<T> {
+    /// Creates a new empty `TakableVec`
+    pub const fn new() -> Self {
+        Self { elements: Vec::new(), taken: Vec::new() }
+    }
+    /// Creates a new empty `TakableVec` with space for at least `capacity` elements
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self { elements: Vec::with_capacity(capacity), taken: Vec::with_capacity(capacity) }
+    }
+    /// Returns the number of elements in the `TakableVec`, including elements that have been taken
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+    /// Returns `true` if the `TakableVec` contains no elements
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+    /// Appends an element to the end of the `TakableVec`
+    #[inline]
+    pub fn push(&mut self, element: T) {
+        self.elements.push(MaybeUninit::new(element));
+        self.taken.push(false);
+    }
+    /// Removes the last element and returns it, or returns `None` if the `TakableVec` is empty
+    ///
+    /// Panics if the last element has already been taken
+    #[inline]
+    pub fn pop(&mut self) -> Option<T> {
+        let element = self.elements.pop()?;
+        if self.taken.pop().unwrap() {
+            panic!("element has already been taken")
+        }
+        // SAFETY: The element hasn't been taken, so it's still initialized
+        Some(unsafe{ element.assume_init() })
+    }
+    /// Shortens the `TakableVec` to `len` elements, dropping the remaining elements that haven't been taken
+    pub fn truncate(&mut self, len: usize) {
+        while self.elements.len() > len {
+            let element = self.elements.pop().unwrap();
+            if !self.taken.pop().unwrap() {
+                // SAFETY: The element hasn't been taken, so it's still initialized
+                drop(unsafe{ element.assume_init() });
+            }
+        }
+    }
+    /// Removes all elements from the `TakableVec`, without freeing its memory
+    #[inline]
+    pub fn clear(&mut self) {
+        self.truncate(0)
+    }
+    /// Borrows a [TakableSlice] over a range of elements
+    ///
+    /// Panics if the range is out of bounds
+    pub fn takable_slice<R: RangeBounds<usize>>(&mut self, range: R) -> TakableSlice<'_, T> {
+        let start = match range.start_bound() {
+            Bound::Included(&start) => start,
+            Bound::Excluded(&start) => start + 1,
+            Bound::Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Bound::Included(&end) => end + 1,
+            Bound::Excluded(&end) => end,
+            Bound::Unbounded => self.elements.len(),
+        };
+        let taken = &mut self.taken[start..end];
+        let taken_cnt = taken.iter().filter(|taken| **taken).count();
+        TakableSlice {
+            elements: &mut self.elements[start..end],
+            taken,
+            taken_cnt,
+        }
+    }
+}
+
+impl<T> Drop for TakableVec<T> {
+    fn drop(&mut self) {
+        self.clear()
+    }
+}
+
+impl<T> From<Vec<T>> for TakableVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        let taken = vec![false; vec.len()];
+        Self { elements: vec.into_iter().map(MaybeUninit::new).collect(), taken }
+    }
+}
+
+impl<T> FromIterator<T> for TakableVec<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Vec::from_iter(iter).into()
+    }
+}
+
+/// A mutable slice, borrowed from a [TakableVec], that allows elements to be moved out
+///
+/// A `TakableSlice` dereferences to an ordinary mutable slice, as long as no elements have been taken.
+/// After an element has been taken, dereferencing the whole slice will panic, but the remaining elements
+/// can still be accessed individually by index or through [get](TakableSlice::get).
+pub struct TakableSlice<'a, T> {
+    elements: &'a mut [MaybeUninit<T>],
+    taken: &'a mut [bool],
+    taken_cnt: usize,
+}
+
+impl<T> Default for TakableSlice<'_, T> {
+    fn default() -> Self {
+        Self { elements: &mut [], taken: &mut [], taken_cnt: 0 }
+    }
+}
+
+impl<T> TakableSlice<'_, T> {
+    /// Returns the number of elements in the slice, including elements that have been taken
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+    /// Returns `true` if the slice has a length of 0
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+    /// Returns `true` if the element at `idx` has been taken
+    ///
+    /// Panics if `idx` is out of bounds
+    #[inline]
+    pub fn is_taken(&self, idx: usize) -> bool {
+        self.taken[idx]
+    }
+    /// Moves the element at `idx` out of the slice
+    ///
+    /// Panics if `idx` is out of bounds or if the element has already been taken
+    #[inline]
+    pub fn take(&mut self, idx: usize) -> T {
+        match self.try_take(idx) {
+            Some(element) => element,
+            None => panic!("element {idx} has already been taken")
+        }
+    }
+    /// Moves the element at `idx` out of the slice, or returns `None` if the element has already been taken
+    ///
+    /// Panics if `idx` is out of bounds
+    #[inline]
+    pub fn try_take(&mut self, idx: usize) -> Option<T> {
+        if self.taken[idx] {
+            return None
+        }
+        self.taken[idx] = true;
+        self.taken_cnt += 1;
+        // SAFETY: The element hasn't been taken, so it's still initialized.  Setting the flag ensures it
+        // won't be read or dropped again
+        Some(unsafe{ self.elements[idx].assume_init_read() })
+    }
+    /// Returns a reference to the element at `idx`, or `None` if `idx` is out of bounds or the element
+    /// has been taken
+    #[inline]
+    pub fn get(&self, idx: usize) -> Option<&T> {
+        match self.taken.get(idx) {
+            // SAFETY: The element hasn't been taken, so it's still initialized
+            Some(false) => Some(unsafe{ self.elements[idx].assume_init_ref() }),
+            _ => None
+        }
+    }
+    /// Returns a mutable reference to the element at `idx`, or `None` if `idx` is out of bounds or the
+    /// element has been taken
+    #[inline]
+    pub fn get_mut(&mut self, idx: usize) -> Option<&mut T> {
+        match self.taken.get(idx) {
+            // SAFETY: The element hasn't been taken, so it's still initialized
+            Some(false) => Some(unsafe{ self.elements[idx].assume_init_mut() }),
+            _ => None
+        }
+    }
+}
+
+impl<T> Deref for TakableSlice<'_, T> {
+    type Target = [T];
+    #[inline]
+    fn deref(&self) -> &[T] {
+        if self.taken_cnt > 0 {
+            panic!("TakableSlice accessed as a slice after an element was taken")
+        }
+        // SAFETY: No elements have been taken, so all elements are initialized
+        unsafe{ &*(self.elements as *const [MaybeUninit<T>] as *const [T]) }
+    }
+}
+
+impl<T> DerefMut for TakableSlice<'_, T> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut [T] {
+        if self.taken_cnt > 0 {
+            panic!("TakableSlice accessed as a slice after an element was taken")
+        }
+        // SAFETY: No elements have been taken, so all elements are initialized
+        unsafe{ &mut *(self.elements as *mut [MaybeUninit<T>] as *mut [T]) }
+    }
+}
+
+impl<T> Index<usize> for TakableSlice<'_, T> {
+    type Output = T;
+    #[inline]
+    fn index(&self, idx: usize) -> &T {
+        match self.get(idx) {
+            Some(element) => element,
+            None => panic!("element {idx} has been taken, or index is out of bounds")
+        }
+    }
+}
+
+impl<T> IndexMut<usize> for TakableSlice<'_, T> {
+    #[inline]
+    fn index_mut(&mut self, idx: usize) -> &mut T {
+        match self.get_mut(idx) {
+            Some(element) => element,
+            None => panic!("element {idx} has been taken, or index is out of bounds")
+        }
+    }
+}
+
+impl<'s, T> IntoIterator for &'s TakableSlice<'_, T> {
+    type Item = &'s T;
+    type IntoIter = core::slice::Iter<'s, T>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref().iter()
+    }
+}
+
+impl<'s, T> IntoIterator for &'s mut TakableSlice<'_, T> {
+    type Item = &'s mut T;
+    type IntoIter = core::slice::IterMut<'s, T>;
+    #[inline]
+    fn into_iter(self) -> Self::IntoIter {
+        self.deref_mut().iter_mut()
+    }
+}
+
+impl<T: core::fmt::Debug> core::fmt::Debug for TakableSlice<'_, T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        let mut list = f.debug_list();
+        for idx in 0..self.len() {
+            match self.get(idx) {
+                Some(element) => list.entry(element),
+                None => list.entry(&format_args!("<taken>")),
+            };
+        }
+        list.finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::rc::Rc;
+
+    #[test]
+    fn takable_slice_test1() {
+        let counter = Rc::new(());
+        let mut vec: TakableVec<Rc<()>> = (0..5).map(|_| counter.clone()).collect();
+        assert_eq!(Rc::strong_count(&counter), 6);
+
+        let mut slice = vec.takable_slice(1..);
+        assert_eq!(slice.len(), 4);
+        assert_eq!(slice.iter().count(), 4);
+        let taken = slice.take(1);
+        assert!(slice.is_taken(1));
+        assert!(slice.try_take(1).is_none());
+        assert!(slice.get(1).is_none());
+        assert!(slice.get(2).is_some());
+        drop(taken);
+        assert_eq!(Rc::strong_count(&counter), 5);
+
+        //Truncating and dropping must skip the taken element
+        vec.truncate(3);
+        assert_eq!(Rc::strong_count(&counter), 3);
+        assert_eq!(vec.takable_slice(..).len(), 3);
+        assert!(vec.takable_slice(..).is_taken(2));
+        drop(vec);
+        assert_eq!(Rc::strong_count(&counter), 1);
+    }
+
+    #[test]
+    #[should_panic]
+    fn takable_slice_test2() {
+        let mut vec = TakableVec::from(vec![String::from("a"), String::from("b")]);
+        let mut slice = vec.takable_slice(..);
+        let _a = slice.take(0);
+        let _ = slice.iter().count();
+    }
+}
@@ -0,0 +1,238 @@
+//! Internal helpers shared by the zippers that present a virtual trie computed from one or more source
+//! zippers, such as [JoinZipper](crate::zipper::JoinZipper) and [FilterZipper](crate::zipper::FilterZipper)
+
+use core::cell::OnceCell;
+
+use crate::zipper::*;
+
+/// Internal.  A set of source zippers that are kept at the same path
+pub(crate) trait ZipperSources {
+    /// Returns the path of the focus shared by all sources
+    fn path(&self) -> &[u8];
+    /// Returns `true` if the sources are at their roots
+    fn at_root(&self) -> bool;
+    /// Resets all the sources to their roots
+    fn reset(&mut self);
+    /// Descends all the sources along `path`
+    fn descend_to(&mut self, path: &[u8]);
+    /// Descends all the sources by one byte
+    fn descend_to_byte(&mut self, k: u8);
+    /// Ascends all the sources by `steps`, returning `false` if any of them hit its root first
+    fn ascend(&mut self, steps: usize) -> bool;
+    /// Moves the sources up to the deepest ancestor of the focus where any source might stop, using each
+    /// source's own [ascend_until](ZipperMoving::ascend_until) or [ascend_until_branch](ZipperMoving::ascend_until_branch)
+    ///
+    /// `focus_buf` is scratch space to hold the path the sources ascended from.
+    fn jump_up(&mut self, branch_only: bool, focus_buf: &mut Vec<u8>);
+}
+
+/// Ascends a single source zipper to the nearest point above its focus where it could stop, and returns
+/// `true` if the source's focus existed before it moved
+///
+/// A source whose focus doesn't exist stops where its path leaves its trie.  That point is where another
+/// trie's branch could diverge from this source's branch, so the virtual trie might have a branch there.
+pub(crate) fn jump_source_up<Z: ZipperMoving>(source: &mut Z, branch_only: bool) -> bool {
+    if !source.path_exists() {
+        while source.ascend_byte() && !source.path_exists() { }
+        return false
+    }
+    if branch_only {
+        source.ascend_until_branch();
+    } else {
+        source.ascend_until();
+    }
+    true
+}
+
+/// Implements [ascend_until](ZipperMoving::ascend_until), or [ascend_until_branch](ZipperMoving::ascend_until_branch)
+/// if `branch_only` is `true`, for a zipper over a virtual trie
+///
+/// `jump_up` must move `z` up to an ancestor of its focus, without passing any point where `z` should stop.
+/// This function then checks the stopping conditions at the point reached, and jumps again if they
+/// don't hold, so the virtual trie is only inspected at the points where a source could stop.
+pub(crate) fn ascend_virtual<Z, F>(z: &mut Z, branch_only: bool, mut jump_up: F) -> bool
+    where
+        Z: ZipperMoving,
+        F: FnMut(&mut Z, bool),
+{
+    if z.at_root() {
+        return false
+    }
+    loop {
+        jump_up(z, branch_only);
+        if z.at_root() || z.child_count() > 1 || (!branch_only && z.is_val()) {
+            return true
+        }
+    }
+}
+
+/// Moves `z` to the adjacent sibling in the visible child mask of the parent
+///
+/// Unlike the default [to_next_sibling_byte](ZipperMoving::to_next_sibling_byte), the focus may be on a
+/// hidden branch when this is called.
+pub(crate) fn step_to_sibling<Z: ZipperMoving>(z: &mut Z, next: bool) -> bool {
+    let cur_byte = match z.path().last() {
+        Some(byte) => *byte,
+        None => return false
+    };
+    if !z.ascend_byte() {
+        return false
+    }
+    let mask = z.child_mask();
+    let sibling = if next { mask.next_bit(cur_byte) } else { mask.prev_bit(cur_byte) };
+    z.descend_to_byte(sibling.unwrap_or(cur_byte));
+    sibling.is_some()
+}
+
+impl<Z: ZipperMoving> ZipperSources for Vec<Z> {
+    fn path(&self) -> &[u8] {
+        self[0].path()
+    }
+    fn at_root(&self) -> bool {
+        self[0].at_root()
+    }
+    fn reset(&mut self) {
+        for source in self.iter_mut() {
+            source.reset();
+        }
+    }
+    fn descend_to(&mut self, path: &[u8]) {
+        for source in self.iter_mut() {
+            source.descend_to(path);
+        }
+    }
+    fn descend_to_byte(&mut self, k: u8) {
+        for source in self.iter_mut() {
+            source.descend_to_byte(k);
+        }
+    }
+    fn ascend(&mut self, steps: usize) -> bool {
+        self.iter_mut().fold(true, |ascended, source| source.ascend(steps) & ascended)
+    }
+    /// Each source stops at its own stopping point, and the sources that went higher descend back to the
+    /// deepest one, which is the first point where the union or intersection of the tries could stop
+    fn jump_up(&mut self, branch_only: bool, focus_buf: &mut Vec<u8>) {
+        focus_buf.clear();
+        focus_buf.extend_from_slice(self[0].path());
+        let mut depth = 0;
+        for source in self.iter_mut() {
+            jump_source_up(source, branch_only);
+            depth = depth.max(source.path().len());
+        }
+        for source in self.iter_mut() {
+            let source_depth = source.path().len();
+            source.descend_to(&focus_buf[source_depth..depth]);
+        }
+    }
+}
+
+/// The second zipper follows the first, so only the first zipper's trie is used to find the stopping points
+impl<AZipper: ZipperMoving, BZipper: ZipperMoving> ZipperSources for (AZipper, BZipper) {
+    fn path(&self) -> &[u8] {
+        self.0.path()
+    }
+    fn at_root(&self) -> bool {
+        self.0.at_root()
+    }
+    fn reset(&mut self) {
+        self.0.reset();
+        self.1.reset();
+    }
+    fn descend_to(&mut self, path: &[u8]) {
+        self.0.descend_to(path);
+        self.1.descend_to(path);
+    }
+    fn descend_to_byte(&mut self, k: u8) {
+        self.0.descend_to_byte(k);
+        self.1.descend_to_byte(k);
+    }
+    fn ascend(&mut self, steps: usize) -> bool {
+        let a_ascended = self.0.ascend(steps);
+        let b_ascended = self.1.ascend(steps);
+        a_ascended & b_ascended
+    }
+    fn jump_up(&mut self, branch_only: bool, _focus_buf: &mut Vec<u8>) {
+        jump_source_up(&mut self.0, branch_only);
+        let steps = self.1.path().len() - self.0.path().len();
+        self.1.ascend(steps);
+    }
+}
+
+/// Internal.  The [ZipperSources] of a virtual zipper, together with a value computed from the sources
+/// at the focus, which is cached until the focus moves
+pub(crate) struct SyncedSources<S, C> {
+    sources: S,
+    cache: OnceCell<C>,
+    focus_buf: Vec<u8>,
+}
+
+impl<S, C> SyncedSources<S, C> {
+    /// Returns a reference to the sources
+    #[inline]
+    pub fn sources(&self) -> &S {
+        &self.sources
+    }
+
+    /// Consumes the `SyncedSources`, and returns the sources
+    pub fn into_sources(self) -> S {
+        self.sources
+    }
+
+    /// Returns the value cached for the focus, computing it with `init` if the focus has moved since
+    /// the last call
+    #[inline]
+    pub fn cached<F: FnOnce(&S) -> C>(&self, init: F) -> &C {
+        self.cache.get_or_init(|| init(&self.sources))
+    }
+}
+
+impl<S: ZipperSources, C> SyncedSources<S, C> {
+    /// Creates a new `SyncedSources`, resetting each source to its root
+    pub fn new(mut sources: S) -> Self {
+        sources.reset();
+        Self {
+            sources,
+            cache: OnceCell::new(),
+            focus_buf: vec![],
+        }
+    }
+
+    #[inline]
+    pub fn path(&self) -> &[u8] {
+        self.sources.path()
+    }
+
+    #[inline]
+    pub fn at_root(&self) -> bool {
+        self.sources.at_root()
+    }
+
+    pub fn reset(&mut self) {
+        self.cache.take();
+        self.sources.reset();
+    }
+
+    pub fn descend_to(&mut self, path: &[u8]) {
+        if path.is_empty() {
+            return
+        }
+        self.cache.take();
+        self.sources.descend_to(path);
+    }
+
+    pub fn descend_to_byte(&mut self, k: u8) {
+        self.cache.take();
+        self.sources.descend_to_byte(k);
+    }
+
+    pub fn ascend(&mut self, steps: usize) -> bool {
+        self.cache.take();
+        self.sources.ascend(steps)
+    }
+
+    /// See [ZipperSources::jump_up]
+    pub fn jump_up(&mut self, branch_only: bool) {
+        self.cache.take();
+        self.sources.jump_up(branch_only, &mut self.focus_buf);
+    }
+}
@@ -1,15 +1,17 @@
 
 use maybe_dangling::MaybeDangling;
 use core::ptr::NonNull;
+use fast_slice_utils::find_prefix_overlap;
 
 use crate::alloc::{Allocator, GlobalAlloc};
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 use crate::trie_node::*;
 use crate::PathMap;
 use crate::zipper::*;
 use crate::zipper::zipper_priv::*;
 use crate::zipper_tracking::*;
-use crate::ring::{AlgebraicResult, AlgebraicStatus, DistributiveLattice, Lattice, COUNTER_IDENT, SELF_IDENT};
+use crate::ring::{AlgebraicResult, AlgebraicStatus, DistributiveLattice, Lattice, MergePolicy, COUNTER_IDENT, SELF_IDENT};
+use crate::trie_map::{nary_op, nary_result_into_map, policy_op_at, NaryOp, PolicyOp};
 
 /// Implemented on [Zipper] types that allow modification of the trie
 pub trait ZipperWriting<V: Clone + Send + Sync, A: Allocator = GlobalAlloc>: WriteZipperPriv<V, A> {
@@ -145,6 +147,21 @@ pub trait ZipperWriting<V: Clone + Send + Sync, A: Allocator = GlobalAlloc>: Wri
     /// Returns `true` if the focus has at least one downstream continuation, otherwise returns `false`.
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice;
 
+    /// Collapses all the paths below the zipper's focus by removing the leading `byte_cnt` bytes from
+    /// each path, and subtracts all the other downstream subtries from the first one
+    ///
+    /// The first subtrie is the one reached by the lowest `byte_cnt`-byte path.  Returns `true` if the
+    /// result has at least one downstream continuation, otherwise returns `false`.
+    fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice;
+
+    /// Collapses the `byte_cnt` bytes that follow the leading `offset` bytes of every path below the
+    /// zipper's focus, and joins together the downstream subtries that end up at the same path
+    ///
+    /// This is equivalent to calling [join_k_path_into](ZipperWriting::join_k_path_into) at every path that is
+    /// `offset` bytes below the focus.  Paths that end within the first `offset` bytes are not affected.
+    /// Returns `true` if any path has a downstream continuation after the collapsed segment.
+    fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice;
+
     /// Deprecated alias for [ZipperWriting::join_k_path_into]
     #[deprecated] //GOAT-old-names
     fn drop_head(&mut self, byte_cnt: usize) -> bool where V: Lattice {
@@ -195,6 +212,80 @@ pub trait ZipperWriting<V: Clone + Send + Sync, A: Allocator = GlobalAlloc>: Wri
         self.subtract_into(read_zipper, true)
     }
 
+    /// Joins the subtrie below the focus of `read_zipper` into the subtrie below the focus of `self`, using
+    /// `policy` to combine the values at paths that exist in both subtries
+    ///
+    /// This method behaves like [join_into](ZipperWriting::join_into), except the [Lattice] impl of `V` is replaced
+    /// by `policy`.  See [MergePolicy] for details.
+    fn join_into_with<Z, P>(&mut self, read_zipper: &Z, policy: &P) -> AlgebraicStatus
+        where
+        Z: ZipperSubtries<V, A>,
+        P: MergePolicy<V> + ?Sized,
+        V: Unpin,
+        Self: ZipperSubtries<V, A> + ZipperMoving + Sized,
+    {
+        policy_op_into(self, read_zipper, policy, PolicyOp::Join, false)
+    }
+
+    /// Meets the subtrie below the zipper's focus with the subtrie below the focus of `read_zipper`, using
+    /// `policy` to combine the values
+    ///
+    /// This method behaves like [meet_into](ZipperWriting::meet_into), except the [Lattice] impl of `V` is replaced
+    /// by `policy`.  See [MergePolicy] for details.
+    fn meet_into_with<Z, P>(&mut self, read_zipper: &Z, policy: &P, prune: bool) -> AlgebraicStatus
+        where
+        Z: ZipperSubtries<V, A>,
+        P: MergePolicy<V> + ?Sized,
+        V: Unpin,
+        Self: ZipperSubtries<V, A> + ZipperMoving + Sized,
+    {
+        policy_op_into(self, read_zipper, policy, PolicyOp::Meet, prune)
+    }
+
+    /// Subtracts the subtrie below the focus of `read_zipper` from the subtrie below the zipper's focus, using
+    /// `policy` to subtract the values
+    ///
+    /// This method behaves like [subtract_into](ZipperWriting::subtract_into), except the [DistributiveLattice]
+    /// impl of `V` is replaced by `policy`.  See [MergePolicy] for details.
+    fn subtract_into_with<Z, P>(&mut self, read_zipper: &Z, policy: &P, prune: bool) -> AlgebraicStatus
+        where
+        Z: ZipperSubtries<V, A>,
+        P: MergePolicy<V> + ?Sized,
+        V: Unpin,
+        Self: ZipperSubtries<V, A> + ZipperMoving + Sized,
+    {
+        policy_op_into(self, read_zipper, policy, PolicyOp::Subtract, prune)
+    }
+
+    /// Joins the subtries below the focus of every zipper in `read_zippers` into the subtrie below the focus
+    /// of `self`
+    ///
+    /// All the subtries are merged together in a single descent, rather than one at a time as with repeated
+    /// calls to [join_into](ZipperWriting::join_into).  If the result is equal to one of the operands, that
+    /// operand's subtrie is used without copying it.
+    fn join_all_into<Z>(&mut self, read_zippers: &[Z]) -> AlgebraicStatus
+        where
+        Z: ZipperSubtries<V, A>,
+        V: Lattice + Unpin,
+        Self: ZipperSubtries<V, A> + Sized,
+    {
+        nary_op_into(self, read_zippers, NaryOp::Join, false)
+    }
+
+    /// Meets the subtrie below the zipper's focus with the subtries below the focus of every zipper in
+    /// `read_zippers`
+    ///
+    /// All the subtries are intersected in a single descent, rather than one at a time as with repeated
+    /// calls to [meet_into](ZipperWriting::meet_into).
+    fn meet_all_into<Z>(&mut self, read_zippers: &[Z], prune: bool) -> AlgebraicStatus
+        where
+        Z: ZipperSubtries<V, A>,
+        V: Lattice + Unpin,
+        Self: ZipperSubtries<V, A> + Sized,
+    {
+        nary_op_into(self, read_zippers, NaryOp::Meet, prune)
+    }
+
     /// Restricts paths in the subtrie downstream of the `self` focus to paths prefixed by a path to a value in
     /// `read_zipper`
     ///
@@ -292,6 +383,8 @@ impl<V: Clone + Send + Sync, Z, A: Allocator> ZipperWriting<V, A> for &mut Z whe
     fn join_into_take<RZ: ZipperSubtries<V, A> + ZipperWriting<V, A>>(&mut self, src_zipper: &mut RZ, prune: bool) -> AlgebraicStatus where V: Lattice { (**self).join_into_take(src_zipper, prune) }
     fn join_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { (**self).join_k_path_into(byte_cnt, prune) }
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { (**self).meet_k_path_into(byte_cnt, prune) }
+    fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice { (**self).subtract_k_path_into(byte_cnt, prune) }
+    fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice { (**self).join_k_path_at_into(offset, byte_cnt, prune) }
     fn insert_prefix<K: AsRef<[u8]>>(&mut self, prefix: K) -> bool { (**self).insert_prefix(prefix) }
     fn remove_prefix(&mut self, n: usize) -> bool { (**self).remove_prefix(n) }
     fn meet_into<RZ: ZipperSubtries<V, A>>(&mut self, read_zipper: &RZ, prune: bool) -> AlgebraicStatus where V: Lattice { (**self).meet_into(read_zipper, prune) }
@@ -347,6 +440,17 @@ impl<'trie, V: Clone + Send + Sync + Unpin, A: Allocator + 'trie> ZipperForking<
     }
 }
 
+impl<'a, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> TrieSummary<V> for WriteZipperTracked<'a, '_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<'a, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperSubtries<V, A> for WriteZipperTracked<'a, '_, V, A>{
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -355,7 +459,6 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperMoving
     fn at_root(&self) -> bool { self.z.at_root() }
     fn reset(&mut self) { self.z.reset() }
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_byte(&mut self, k: u8) { self.z.descend_to_byte(k) }
     fn descend_indexed_byte(&mut self, child_idx: usize) -> bool { self.z.descend_indexed_byte(child_idx) }
@@ -405,18 +508,31 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> WriteZipperTr
     //     Self { z: core, _tracker: Some(tracker), }
     // }
 
+    /// Attaches a label to the zipper's tracker, which will be reported in any [Conflict] caused by the
+    /// zipper, and by [ZipperCreation::outstanding_zippers]
+    ///
+    /// Does nothing if the zipper is not tracked, which is the case for zippers created by `_unchecked`
+    /// methods in release builds.
+    pub fn set_tracker_label<L: Into<String>>(&self, label: L) {
+        if let Some(tracker) = &self._tracker {
+            tracker.set_label(label);
+        }
+    }
+
     /// Consumes the `WriteZipperTracked`, and returns a [ReadZipperTracked] in its place
     ///
-    /// The returned read zipper will have the same root and focus as the the consumed write zipper.
+    /// The returned read zipper will have the same root and focus as the the consumed write zipper.  The
+    /// path remains registered with the [ZipperHead] throughout, so no other write zipper can claim it in
+    /// between.  Use [ReadZipperTracked::try_upgrade] to go in the other direction.
     pub fn into_read_zipper(mut self) -> ReadZipperTracked<'a, 'static, V, A> {
-        let tracker = self._tracker.take().unwrap().into_reader();
+        let tracker = self._tracker.take().map(|tracker| tracker.into_reader());
         let root_node = self.z.focus_stack.take_root().unwrap();
         let root_path = &self.z.key.prefix_buf[..self.z.key.origin_path.len()];
         let descended_path = &self.z.key.prefix_buf[self.z.key.origin_path.len()..];
         let root_val = core::mem::take(&mut self.z.root_val);
         let root_val = root_val.and_then(|root_val| unsafe{ (&*root_val).as_ref() });
 
-        let mut new_zipper = ReadZipperTracked::new_with_node_and_cloned_path_in(root_node, false, root_path, root_path.len(), self.z.key.root_key_start, root_val, self.z.alloc.clone(), Some(tracker));
+        let mut new_zipper = ReadZipperTracked::new_with_node_and_cloned_path_in(root_node, false, root_path, root_path.len(), self.z.key.root_key_start, root_val, self.z.alloc.clone(), tracker);
         new_zipper.descend_to(descended_path);
         new_zipper
     }
@@ -445,6 +561,8 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperWriting
     fn join_into_take<Z: ZipperSubtries<V, A> + ZipperWriting<V, A>>(&mut self, src_zipper: &mut Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.join_into_take(src_zipper, prune) }
     fn join_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_into(byte_cnt, prune) }
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.meet_k_path_into(byte_cnt, prune) }
+    fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice { self.z.subtract_k_path_into(byte_cnt, prune) }
+    fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_at_into(offset, byte_cnt, prune) }
     fn insert_prefix<K: AsRef<[u8]>>(&mut self, prefix: K) -> bool { self.z.insert_prefix(prefix) }
     fn remove_prefix(&mut self, n: usize) -> bool { self.z.remove_prefix(n) }
     fn meet_into<Z: ZipperSubtries<V, A>>(&mut self, read_zipper: &Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.meet_into(read_zipper, prune) }
@@ -466,6 +584,116 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> WriteZipperPr
     fn alloc(&self) -> A { self.z.alloc.clone() }
 }
 
+/// Internal implementation of the `_into_with` methods of [ZipperWriting]
+///
+/// The result is written through `wz` while the subtries are traversed, so an identity result leaves the
+/// trie untouched.  See [policy_op_at].
+fn policy_op_into<V, A, WZ, Z, P>(wz: &mut WZ, read_zipper: &Z, policy: &P, op: PolicyOp, prune: bool) -> AlgebraicStatus
+    where
+    V: Clone + Send + Sync + Unpin,
+    A: Allocator,
+    WZ: ZipperWriting<V, A> + ZipperSubtries<V, A> + ZipperMoving,
+    Z: ZipperSubtries<V, A>,
+    P: MergePolicy<V> + ?Sized,
+{
+    let other_map = read_zipper.make_map().unwrap_or_else(|| PathMap::new_in(wz.alloc()));
+    let mut other_z = other_map.read_zipper();
+    match policy_op_at(wz, &mut other_z, policy, op, cfg!(feature = "graft_root_vals")) {
+        AlgebraicResult::Identity(mask) if mask & SELF_IDENT > 0 => AlgebraicStatus::Identity,
+        AlgebraicResult::Identity(_) => {
+            wz.graft(read_zipper);
+            AlgebraicStatus::Element
+        },
+        AlgebraicResult::Element(()) => AlgebraicStatus::Element,
+        AlgebraicResult::None => {
+            #[cfg(feature = "graft_root_vals")]
+            wz.remove_val(false);
+            wz.remove_branches(prune);
+            AlgebraicStatus::None
+        },
+    }
+}
+
+/// Visits the subtries `k` bytes below the root of `node`, visiting each node along the way once
+///
+/// `f` is called with the path to each subtrie, relative to `node`, and the subtrie, in path order.  A path that
+/// ends in a value exactly `k` bytes down has an empty (`None`) subtrie.  Values that are less than `k` bytes
+/// below the root of `node` are skipped.  `path` is scratch space for the path of `node`, and is restored before
+/// returning.
+fn k_path_subtries<V, A, F>(node: &TrieNodeODRc<V, A>, k: usize, path: &mut Vec<u8>, f: &mut F)
+    where
+    V: Clone + Send + Sync,
+    A: Allocator,
+    F: FnMut(&[u8], Option<TrieNodeODRc<V, A>>),
+{
+    if k == 0 {
+        f(path, Some(node.clone()));
+        return
+    }
+    let node_ref = node.as_tagged();
+    let mut last_key: &[u8] = &[];
+    let mut token = node_ref.new_iter_token();
+    while token != NODE_ITER_FINISHED {
+        let (next_token, key, child, _val) = node_ref.next_items(token);
+        token = next_token;
+        if key.len() < k {
+            if let Some(child) = child {
+                path.extend_from_slice(key);
+                k_path_subtries(child, k - key.len(), path, f);
+                path.truncate(path.len() - key.len());
+            }
+        } else if key[..k] != *last_key {
+            //Several keys in the node may pass through the same path `k` bytes down, so the subtrie is
+            // taken from the node at that path, rather than from the child link
+            last_key = &key[..k];
+            let path_len = path.len();
+            path.extend_from_slice(last_key);
+            f(path, node_ref.get_node_at_key(last_key).into_option());
+            path.truncate(path_len);
+        }
+    }
+}
+
+/// Internal function to implement [ZipperWriting::join_all_into] and [ZipperWriting::meet_all_into]
+///
+/// The subtrie at the focus of `wz` is the first operand, so an identity of it leaves the trie untouched.
+fn nary_op_into<V, A, WZ, Z>(wz: &mut WZ, read_zippers: &[Z], op: NaryOp, prune: bool) -> AlgebraicStatus
+    where
+    V: Clone + Send + Sync + Unpin + Lattice,
+    A: Allocator,
+    WZ: ZipperWriting<V, A> + ZipperSubtries<V, A>,
+    Z: ZipperSubtries<V, A>,
+{
+    let alloc = wz.alloc();
+    let mut operands = Vec::with_capacity(read_zippers.len() + 1);
+    operands.push(wz.make_map().unwrap_or_else(|| PathMap::new_in(alloc.clone())));
+    operands.extend(read_zippers.iter().map(|rz| rz.make_map().unwrap_or_else(|| PathMap::new_in(alloc.clone()))));
+    let result = {
+        let operand_refs: Vec<&PathMap<V, A>> = operands.iter().collect();
+        nary_op(&operand_refs, op)
+    };
+    if result.identity_mask & 1 > 0 {
+        return AlgebraicStatus::Identity
+    }
+    //Release our references to the subtrie before writing, so the write doesn't need to copy any nodes
+    let result_map = match result.element {
+        Some(map) => Some(map),
+        None if result.identity_mask != 0 => Some(operands.swap_remove(result.identity_mask.trailing_zeros() as usize)),
+        None => None,
+    };
+    drop(operands);
+    match result_map {
+        Some(map) => {
+            wz.graft_map(map);
+            AlgebraicStatus::Element
+        },
+        None => {
+            wz.remove_branches(prune);
+            AlgebraicStatus::None
+        },
+    }
+}
+
 // ***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---
 // WriteZipperUntracked
 // ***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---***---
@@ -495,6 +723,17 @@ impl<'trie, V: Clone + Send + Sync + Unpin, A: Allocator + 'trie> ZipperForking<
     }
 }
 
+impl<'a, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> TrieSummary<V> for WriteZipperUntracked<'a, '_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<'a, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperSubtries<V, A> for WriteZipperUntracked<'a, '_, V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -503,7 +742,6 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperMoving
     fn at_root(&self) -> bool { self.z.at_root() }
     fn reset(&mut self) { self.z.reset() }
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_byte(&mut self, k: u8) { self.z.descend_to_byte(k) }
     fn descend_indexed_byte(&mut self, child_idx: usize) -> bool { self.z.descend_indexed_byte(child_idx) }
@@ -595,6 +833,8 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperWriting
     fn join_into_take<Z: ZipperSubtries<V, A> + ZipperWriting<V, A>>(&mut self, src_zipper: &mut Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.join_into_take(src_zipper, prune) }
     fn join_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_into(byte_cnt, prune) }
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.meet_k_path_into(byte_cnt, prune) }
+    fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice { self.z.subtract_k_path_into(byte_cnt, prune) }
+    fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_at_into(offset, byte_cnt, prune) }
     fn insert_prefix<K: AsRef<[u8]>>(&mut self, prefix: K) -> bool { self.z.insert_prefix(prefix) }
     fn remove_prefix(&mut self, n: usize) -> bool { self.z.remove_prefix(n) }
     fn meet_into<Z: ZipperSubtries<V, A>>(&mut self, read_zipper: &Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.meet_into(read_zipper, prune) }
@@ -657,6 +897,17 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for WriteZip
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for WriteZipperOwned<V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for WriteZipperOwned<V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -665,7 +916,6 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperMoving for WriteZipperO
     fn at_root(&self) -> bool { self.z.at_root() }
     fn reset(&mut self) { self.z.reset() }
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_byte(&mut self, k: u8) { self.z.descend_to_byte(k) }
     fn descend_indexed_byte(&mut self, child_idx: usize) -> bool { self.z.descend_indexed_byte(child_idx) }
@@ -762,6 +1012,8 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperWriting<V, A> for Write
     fn join_into_take<Z: ZipperSubtries<V, A> + ZipperWriting<V, A>>(&mut self, src_zipper: &mut Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.join_into_take(src_zipper, prune) }
     fn join_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_into(byte_cnt, prune) }
     fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.meet_k_path_into(byte_cnt, prune) }
+    fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice { self.z.subtract_k_path_into(byte_cnt, prune) }
+    fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice { self.z.join_k_path_at_into(offset, byte_cnt, prune) }
     fn insert_prefix<K: AsRef<[u8]>>(&mut self, prefix: K) -> bool { self.z.insert_prefix(prefix) }
     fn remove_prefix(&mut self, n: usize) -> bool { self.z.remove_prefix(n) }
     fn meet_into<Z: ZipperSubtries<V, A>>(&mut self, read_zipper: &Z, prune: bool) -> AlgebraicStatus where V: Lattice { self.z.meet_into(read_zipper, prune) }
@@ -917,6 +1169,25 @@ impl<'trie, V: Clone + Send + Sync + Unpin, A: Allocator + 'trie> ZipperForking<
     }
 }
 
+impl<'trie, V: Clone + Send + Sync + Unpin, A: Allocator + 'trie> TrieSummary<V> for WriteZipperCore<'trie, '_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.fork_read_zipper().summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize {
+        let root_val = self.is_val() as usize;
+        let focus = self.get_focus();
+        if focus.is_none() {
+            root_val
+        } else {
+            val_count_below_root(focus.as_tagged()) + root_val
+        }
+    }
+}
+
 impl<'a, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> WriteZipperCore<'a, '_, V, A> {
     fn make_map(&self) -> Option<PathMap<V, A>> {
         #[cfg(not(feature = "graft_root_vals"))]
@@ -953,15 +1224,6 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ZipperMoving
         }
     }
 
-    fn val_count(&self) -> usize {
-        let root_val = self.is_val() as usize;
-        let focus = self.get_focus();
-        if focus.is_none() {
-            root_val
-        } else {
-            val_count_below_root(focus.as_tagged()) + root_val
-        }
-    }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
         let key = k.as_ref();
         self.key.prepare_buffers();
@@ -1520,63 +1782,91 @@ impl <'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> WriteZipperC
     }
     /// See [ZipperWriting::meet_k_path_into]
     pub fn meet_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice {
-        //GOAT, this is a provisional implementation with the wrong performance characteristics, but should have the right behavior
-        let temp_map = if self.descend_first_k_path(byte_cnt) {
-            let mut temp_map = self.take_map(false).unwrap_or_else(|| PathMap::new_in(self.alloc.clone()));
-
-            while self.to_next_k_path(byte_cnt) {
-                if temp_map.is_empty() {
-                    self.ascend(byte_cnt);
-                    break;
-                }
-                let other_map = self.take_map(false).unwrap_or_else(|| PathMap::new_in(self.alloc.clone()));
-                temp_map = temp_map.meet(&other_map);
+        self.k_path_merge_into(byte_cnt, prune, |subtries| {
+            Some(nary_result_into_map(nary_op(subtries, NaryOp::Meet), subtries))
+        })
+    }
+    /// See [ZipperWriting::subtract_k_path_into]
+    pub fn subtract_k_path_into(&mut self, byte_cnt: usize, prune: bool) -> bool where V: Lattice + DistributiveLattice {
+        self.k_path_merge_into(byte_cnt, prune, |subtries| {
+            let (first, rest) = subtries.split_first().unwrap();
+            if rest.is_empty() {
+                return Some((*first).clone())
             }
-            temp_map
-        } else {
-            PathMap::new_in(self.alloc.clone())
-        };
-        if temp_map.is_empty() {
-            self.remove_branches(prune);
-            false
-        } else {
-            self.graft_map(temp_map);
-            true
-        }
+            let rest = nary_result_into_map(nary_op(rest, NaryOp::Join), rest);
+            match first.psubtract(&rest) {
+                AlgebraicResult::Element(difference) => Some(difference),
+                AlgebraicResult::Identity(mask) => {
+                    debug_assert_eq!(mask & SELF_IDENT, SELF_IDENT);
+                    Some((*first).clone())
+                },
+                AlgebraicResult::None => None,
+            }
+        })
     }
-
-    /// GOAT.  Trash impl of k_path iteration, to facilitate provisional impl of `meet_k_path_into`
-    fn descend_first_k_path(&mut self, k: usize) -> bool {
-        self.k_path_internal(k, self.path().len())
+    /// See [ZipperWriting::join_k_path_at_into]
+    pub fn join_k_path_at_into(&mut self, offset: usize, byte_cnt: usize, prune: bool) -> bool where V: Lattice {
+        if offset == 0 {
+            return self.join_k_path_into(byte_cnt, prune)
+        }
+        //The segment is collapsed in each subtrie as the subtries are found, so only the results need to be
+        // grafted back
+        let mut collapsed = vec![];
+        if let Some(focus_node) = self.get_focus().into_option() {
+            k_path_subtries(&focus_node, offset, &mut vec![], &mut |prefix, subtrie| {
+                let node = subtrie.and_then(|mut node| node.make_mut().drop_head_dyn(byte_cnt));
+                collapsed.push((prefix.to_vec(), node));
+            });
+        }
+        //NOTE: The prefixes are visited from the last to the first, so pruning a dangling prefix never
+        // disturbs a prefix that has yet to be visited.  Each move only retraces the part of the path that
+        // differs from the previous prefix
+        let mut result = false;
+        let mut cur_prefix: &[u8] = &[];
+        for (prefix, node) in collapsed.iter().rev() {
+            let overlap = find_prefix_overlap(cur_prefix, prefix);
+            self.ascend(cur_prefix.len() - overlap);
+            self.descend_to(&prefix[overlap..]);
+            cur_prefix = prefix;
+            let collapsed_to_something = node.is_some();
+            self.graft_internal(node.clone());
+            if collapsed_to_something {
+                result = true;
+            } else if prune {
+                self.prune_path();
+            }
+        }
+        self.ascend(cur_prefix.len());
+        result
     }
 
-    /// GOAT.  Trash impl of k_path iteration, to facilitate provisional impl of `meet_k_path_into`
-    fn to_next_k_path(&mut self, k: usize) -> bool {
-        let base_idx = if self.path().len() >= k {
-            self.path().len() - k
+    /// Internal method to implement the `_k_path_into` methods.  Collects the subtries `byte_cnt` bytes below
+    /// the focus in a single pass over the nodes, and replaces the focus subtrie with the result of `merge_f`
+    ///
+    /// `merge_f` is only called if there is at least one subtrie, and the subtries are passed in path order.
+    fn k_path_merge_into<F>(&mut self, byte_cnt: usize, prune: bool, merge_f: F) -> bool
+        where F: FnOnce(&[&PathMap<V, A>]) -> Option<PathMap<V, A>>
+    {
+        let mut nodes = vec![];
+        if let Some(focus_node) = self.get_focus().into_option() {
+            k_path_subtries(&focus_node, byte_cnt, &mut vec![], &mut |_path, subtrie| nodes.push(subtrie));
+        }
+        let result = if nodes.is_empty() {
+            None
         } else {
-            return false
+            let subtries: Vec<PathMap<V, A>> = nodes.into_iter().map(|node| PathMap::new_with_root_in(node, None, self.alloc.clone())).collect();
+            let subtrie_refs: Vec<&PathMap<V, A>> = subtries.iter().collect();
+            merge_f(&subtrie_refs).and_then(|map| map.into_root().0)
         };
-        self.k_path_internal(k, base_idx)
-    }
-
-    /// GOAT.  Trash impl of k_path iteration, to facilitate provisional impl of `meet_k_path_into`
-    #[inline]
-    fn k_path_internal(&mut self, k: usize, base_idx: usize) -> bool {
-        loop {
-            if self.path().len() < base_idx + k {
-                while self.descend_first_byte() {
-                    if self.path().len() == base_idx + k { return true }
-                }
-            }
-            if self.to_next_sibling_byte() {
-                if self.path().len() == base_idx + k { return true }
-                continue
-            }
-            while self.path().len() > base_idx {
-                self.ascend_byte();
-                if self.path().len() == base_idx { return false }
-                if self.to_next_sibling_byte() { break }
+        //The subtries are all released by now, so the graft doesn't need to copy any nodes
+        match result {
+            Some(node) if !node.as_tagged().node_is_empty() => {
+                self.graft_internal(Some(node));
+                true
+            },
+            _ => {
+                self.remove_branches(prune);
+                false
             }
         }
     }
@@ -2740,6 +3030,38 @@ mod tests {
         assert_eq!(src.get_val_at(b"one:two:junk"), None);
     }
 
+    /// Tests grafting and removing at a focus inside a node, where the node's keys continue past the focus
+    #[test]
+    fn write_zipper_graft_test3() {
+        let keys = |map: &PathMap<u64>| map.iter().map(|(k, v)| (k, *v)).collect::<Vec<_>>();
+        let src: PathMap<u64> = [("pre/ab:wxyz1", 2)].into_iter().collect();
+
+        //The graft replaces the old branch, rather than being added alongside it
+        let mut dst: PathMap<u64> = [("pre/ab:wxyz1", 0)].into_iter().collect();
+        let mut wz = dst.write_zipper_at_path(b"pre/ab:wxy");
+        wz.graft(&src.read_zipper_at_path(b"pre/ab:wxy"));
+        drop(wz);
+        assert_eq!(keys(&dst), vec![(b"pre/ab:wxyz1".to_vec(), 2)]);
+
+        //A value can be set beside a dangling path left by removing the branches
+        let mut wz = dst.write_zipper_at_path(b"pre/ab:wxyz");
+        wz.remove_branches(false);
+        wz.descend_to_byte(b'2');
+        wz.set_val(3);
+        drop(wz);
+        assert_eq!(keys(&dst), vec![(b"pre/ab:wxyz2".to_vec(), 3)]);
+
+        //Removing the value at a zipper's root doesn't disturb the branches below it
+        let sub: PathMap<u64> = [("", 0), ("p", 1), ("z", 2)].into_iter().collect();
+        let mut dst = PathMap::new();
+        dst.write_zipper_at_path(b"in:").graft_map(sub);
+        let mut wz = dst.write_zipper_at_path(b"in:");
+        wz.remove_val(false);
+        wz.remove_unmasked_branches([b'p'].into_iter().collect(), false);
+        drop(wz);
+        assert_eq!(keys(&dst), vec![(b"in:p".to_vec(), 1)]);
+    }
+
     #[test]
     fn write_zipper_join_into_test() {
         let a_keys = ["arrow", "bow", "cannon", "roman", "romane", "romanus", "romulus", "rubens", "ruber", "rubicon", "rubicundus", "rom'i"];
@@ -3393,6 +3715,105 @@ mod tests {
         assert_eq!(map.get(b"123:e0"), Some(&()));
     }
 
+    #[test]
+    fn write_zipper_meet_k_path_into_test3() {
+        //Compare against meeting the subtries one at a time, with keys that split nodes at many depths
+        let mut map = PathMap::<Vec<u64>>::new();
+        for head in 0u64..13 {
+            for tail in (0u64..40).filter(|&tail| tail != head * 2) {
+                map.set_val_at(format!("root:{:02}{}:{}", head, tail % 10, tail / 10), vec![tail % 3, 10 + head]);
+            }
+        }
+        let mut expected: Option<PathMap<Vec<u64>>> = None;
+        for head in 0..13 {
+            let subtrie = map.read_zipper_at_path(format!("root:{:02}", head)).make_map().unwrap();
+            expected = Some(match expected {
+                Some(acc) => acc.meet(&subtrie),
+                None => subtrie,
+            });
+        }
+        let expected = expected.unwrap();
+        assert!(expected.val_count() > 0);
+
+        let mut wz = map.write_zipper_at_path(b"root:");
+        assert!(wz.meet_k_path_into(2, true));
+        drop(wz);
+        let met = map.read_zipper_at_path(b"root:").make_map().unwrap();
+        assert_eq!(met.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>(), expected.iter().map(|(k, v)| (k, v.clone())).collect::<Vec<_>>());
+
+        //A path that ends exactly `k` bytes down contributes an empty subtrie
+        let mut map: PathMap<()> = ["a:x", "a:y", "b:x", "c:"].into_iter().map(|k| (k, ())).collect();
+        let mut wz = map.write_zipper();
+        assert!(!wz.meet_k_path_into(2, true));
+        assert_eq!(wz.val_count(), 0);
+    }
+
+    #[test]
+    fn write_zipper_subtract_k_path_into_test1() {
+        let keys = [
+            "123:abc:Bob",
+            "123:abc:Jim",
+            "123:abc:Pam",
+            "123:abc:Sue",
+            "123:def:Pam",
+            "123:ghi:Bob",
+            "123:ghi:Mel"];
+        let mut map: PathMap<()> = keys.iter().map(|k| (k, ())).collect();
+        let mut wz = map.write_zipper_at_path(b"123:");
+        assert!(wz.subtract_k_path_into(4, true));
+        drop(wz);
+        assert_eq!(map.iter().map(|(k, _v)| k).collect::<Vec<Vec<u8>>>(), vec![b"123:Jim".to_vec(), b"123:Sue".to_vec()]);
+
+        //Subtracting everything leaves nothing
+        let mut map: PathMap<()> = ["x:a", "y:a", "y:b"].into_iter().map(|k| (k, ())).collect();
+        let mut wz = map.write_zipper();
+        assert!(!wz.subtract_k_path_into(2, true));
+        assert_eq!(wz.val_count(), 0);
+    }
+
+    #[test]
+    fn write_zipper_join_k_path_at_into_test1() {
+        let keys = [
+            "123:abc:Bob",
+            "123:abc:Jim",
+            "123:def:Bob",
+            "123:def:Sue",
+            "456:abc:Pam",
+            "456:ghi:Pam",
+            "4",
+            "45"];
+        let mut map: PathMap<()> = keys.iter().map(|k| (k, ())).collect();
+        let mut wz = map.write_zipper();
+        assert!(wz.join_k_path_at_into(4, 4, true));
+        drop(wz);
+        let ref_keys: Vec<&[u8]> = vec![
+            b"123:Bob",
+            b"123:Jim",
+            b"123:Sue",
+            b"4",
+            b"45",
+            b"456:Pam"];
+        assert_eq!(map.iter().map(|(k, _v)| k).collect::<Vec<Vec<u8>>>(), ref_keys);
+
+        //Collapsing a segment in the middle of long keys, compared against building the result directly
+        let paths: Vec<Vec<u8>> = (0u64..200).map(|i| format!("{}/{:03}/{}", i % 3, (i * 37) % 101, i % 7).into_bytes()).collect();
+        let mut map: PathMap<()> = paths.iter().map(|p| (p, ())).collect();
+        let expected: PathMap<()> = paths.iter().map(|p| ([&p[..2], &p[6..]].concat(), ())).collect();
+        let mut wz = map.write_zipper();
+        assert!(wz.join_k_path_at_into(2, 4, true));
+        drop(wz);
+        assert_eq!(map.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+
+        //Prefixes whose segment collapses to nothing are pruned, and the focus doesn't move
+        let mut map: PathMap<()> = ["pre/ab:wxyz1", "pre/ab:qrst2", "pre/ef:zz", "pre/ef:yy", "pre/gh:"].into_iter().map(|k| (k, ())).collect();
+        let mut wz = map.write_zipper_at_path(b"pre/");
+        assert!(wz.join_k_path_at_into(3, 4, true));
+        assert_eq!(wz.path(), b"");
+        assert_eq!(wz.child_mask(), ByteMask::from_iter([b'a', b'g']));
+        drop(wz);
+        assert_eq!(map.iter().map(|(k, _v)| k).collect::<Vec<Vec<u8>>>(), vec![b"pre/ab:1".to_vec(), b"pre/ab:2".to_vec(), b"pre/gh:".to_vec()]);
+    }
+
     #[test]
     fn write_zipper_insert_prefix_test() {
         let keys = [
@@ -3738,6 +4159,41 @@ mod tests {
         drop(rz);
     }
 
+    #[test]
+    fn write_zipper_join_all_into_test1() {
+        let mut map = PathMap::<Vec<u64>>::new();
+        for (i, src) in [b"srcA:", b"srcB:", b"srcC:"].into_iter().enumerate() {
+            for j in 0..20u64 {
+                map.set_val_at([&src[..], format!("{}", j * (i as u64 + 1) % 30).as_bytes()].concat(), vec![i as u64]);
+            }
+        }
+        map.set_val_at(b"dst:7", vec![9]);
+        map.set_val_at(b"dst:99", vec![9]);
+
+        let mut expected = map.clone();
+        let mut wz = expected.write_zipper_at_path(b"dst:");
+        for src in [b"srcA:", b"srcB:", b"srcC:"] {
+            wz.join_into(&map.read_zipper_at_path(src));
+        }
+        drop(wz);
+
+        let head = map.zipper_head();
+        let mut wz = head.write_zipper_at_exclusive_path(b"dst:").unwrap();
+        let rzs = [b"srcA:", b"srcB:", b"srcC:"].map(|src| head.read_zipper_at_path(src).unwrap());
+        assert_eq!(wz.join_all_into(&rzs), AlgebraicStatus::Element);
+        assert_eq!(wz.join_all_into(&rzs), AlgebraicStatus::Identity); //Subsequent call should be `Identity`
+        assert_eq!(wz.meet_all_into(&rzs[..0], true), AlgebraicStatus::Identity);
+        assert_eq!(wz.meet_all_into(&rzs[..1], true), AlgebraicStatus::Element);
+        drop(wz);
+        drop(rzs);
+        drop(head);
+
+        let mut wz = expected.write_zipper_at_path(b"dst:");
+        wz.meet_into(&map.read_zipper_at_path(b"srcA:"), true);
+        drop(wz);
+        assert_eq!(map.iter().collect::<Vec<_>>(), expected.iter().collect::<Vec<_>>());
+    }
+
     /// Tests correctness of the `origin_path` for a WriteZipper off a map
     #[test]
     fn origin_path_test1() {
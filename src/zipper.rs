@@ -10,7 +10,7 @@ use maybe_dangling::MaybeDangling;
 use fast_slice_utils::find_prefix_overlap;
 
 use crate::alloc::{Allocator, GlobalAlloc};
-use crate::utils::ByteMask;
+use crate::utils::{ByteMask, TakableSlice};
 use crate::trie_node::*;
 use crate::PathMap;
 
@@ -19,9 +19,16 @@ pub use crate::trie_ref::*;
 pub use crate::zipper_head::*;
 pub use crate::product_zipper::{ProductZipper, ProductZipperG, ProductZipperLike};
 pub use crate::overlay_zipper::{OverlayZipper};
+pub use crate::trie_join::TrieJoin;
+pub use crate::join_zipper::JoinZipper;
+pub use crate::meet_zipper::MeetZipper;
+pub use crate::subtract_zipper::SubtractZipper;
+pub use crate::filter_zipper::FilterZipper;
+pub use crate::map_zipper::{MapZipper, MapZipperWitness};
 pub use crate::prefix_zipper::{PrefixZipper};
 pub use crate::empty_zipper::{EmptyZipper};
 pub use crate::poly_zipper::PolyZipper;
+pub use crate::morphisms::TrieSummary;
 use crate::zipper_tracking::*;
 
 
@@ -149,12 +156,6 @@ pub trait ZipperMoving: Zipper {
     /// Returns the path from the zipper's root to the current focus
     fn path(&self) -> &[u8];
 
-    /// Returns the total number of values contained at and below the zipper's focus, including the focus itself
-    ///
-    /// WARNING: This is not a cheap method. It may have an order-N cost
-    //GOAT! This doesn't belong here.  Should be a function that uses a non-side-effect catamorphism
-    fn val_count(&self) -> usize;
-
     /// Moves the zipper's focus to a specific location specified by `path`, relative to the zipper's root
     ///
     /// Returns the number of bytes shared between the old and new location
@@ -776,7 +777,6 @@ impl<Z> ZipperMoving for &mut Z where Z: ZipperMoving + Zipper {
     fn at_root(&self) -> bool { (**self).at_root() }
     fn reset(&mut self) { (**self).reset() }
     fn path(&self) -> &[u8] { (**self).path() }
-    fn val_count(&self) -> usize { (**self).val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { (**self).descend_to(k) }
     fn descend_to_check<K: AsRef<[u8]>>(&mut self, k: K) -> bool { (**self).descend_to_check(k) }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize { (**self).descend_to_existing(k) }
@@ -810,6 +810,17 @@ impl<V, Z> ZipperValues<V> for &mut Z where Z: ZipperValues<V> {
     fn val(&self) -> Option<&V> { (**self).val() }
 }
 
+impl<V, Z> TrieSummary<V> for &mut Z where Z: TrieSummary<V> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        (**self).summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { (**self).val_count() }
+}
+
 impl<V, Z> ZipperForking<V> for &mut Z where Z: ZipperForking<V> {
     type ReadZipperT<'a> = Z::ReadZipperT<'a> where Self: 'a;
     fn fork_read_zipper<'a>(&'a self) -> Self::ReadZipperT<'a> { (**self).fork_read_zipper() }
@@ -904,6 +915,17 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for ReadZipp
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for ReadZipperTracked<'_, '_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for ReadZipperTracked<'_, '_, V, A>{
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -913,7 +935,6 @@ impl<'trie, V: Clone + Send + Sync + Unpin + 'trie, A: Allocator + 'trie> Zipper
     fn reset(&mut self) { self.z.reset() }
     #[inline]
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_check<K: AsRef<[u8]>>(&mut self, k: K) -> bool { self.z.descend_to_check(k) }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize { self.z.descend_to_existing(k) }
@@ -999,6 +1020,43 @@ impl<'a, 'path, V: Clone + Send + Sync + Unpin, A: Allocator + 'a> ReadZipperTra
         let core = ReadZipperCore::new_with_node_and_cloned_path_in(root_node, owned_root, path, root_prefix_len, root_key_start, root_val, alloc);
         Self { z: core, tracker }
     }
+    /// Attaches a label to the zipper's tracker, which will be reported in any [Conflict] caused by the
+    /// zipper, and by [ZipperCreation::outstanding_zippers]
+    ///
+    /// Does nothing if the zipper is not tracked, which is the case for zippers created by `_unchecked`
+    /// methods in release builds.
+    pub fn set_tracker_label<L: Into<String>>(&self, label: L) {
+        if let Some(tracker) = &self.tracker {
+            tracker.set_label(label);
+        }
+    }
+    /// Attempts to upgrade a read zipper to a [WriteZipperTracked] at the same root and focus
+    ///
+    /// This is a convenience for [ZipperCreation::try_upgrade_read_zipper].  The `zipper_head` must be the
+    /// [ZipperHead] that created the zipper.
+    #[allow(clippy::result_large_err)] //The zipper is handed back so the caller doesn't lose its place
+    pub fn try_upgrade<'trie, ZH>(self, zipper_head: &'a ZH) -> Result<WriteZipperTracked<'a, 'static, V, A>, (Self, Conflict)>
+        where ZH: ZipperCreation<'trie, V, A>, 'trie: 'a
+    {
+        zipper_head.try_upgrade_read_zipper(self)
+    }
+    /// Internal method to exchange the zipper's read tracker for a write tracker at the same path
+    ///
+    /// The zipper keeps its tracker if the upgrade fails.  Panics if the zipper's tracker isn't registered
+    /// with `tracker_paths`.
+    pub(crate) fn try_upgrade_tracker(&mut self, tracker_paths: &SharedTrackerPaths) -> Result<ZipperTracker<TrackingWrite>, Conflict> {
+        match self.tracker.take() {
+            Some(tracker) => {
+                assert!(tracker.is_tracked_by(tracker_paths), "ReadZipper did not originate from this ZipperHead");
+                tracker.try_upgrade().map_err(|(tracker, conflict)| {
+                    self.tracker = Some(tracker);
+                    conflict
+                })
+            },
+            //The zipper was created by an `_unchecked` method in release mode, so there is no registration to upgrade
+            None => ZipperTracker::<TrackingWrite>::new(tracker_paths.clone(), self.root_prefix_path())
+        }
+    }
 }
 
 //GOAT, the standard prototype of IntoIterator isn't compatible with ReadZipperTracked anymore because 
@@ -1053,6 +1111,17 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for ReadZipp
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for ReadZipperUntracked<'_, '_, V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for ReadZipperUntracked<'_, '_, V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -1062,7 +1131,6 @@ impl<'trie, V: Clone + Send + Sync + Unpin + 'trie, A: Allocator + 'trie> Zipper
     fn reset(&mut self) { self.z.reset() }
     #[inline]
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_check<K: AsRef<[u8]>>(&mut self, k: K) -> bool { self.z.descend_to_check(k) }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize { self.z.descend_to_existing(k) }
@@ -1252,6 +1320,17 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperForking<V> for ReadZipp
     }
 }
 
+impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for ReadZipperOwned<V, A> {
+    fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+        where
+            W: Clone,
+            AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+    {
+        self.z.summarize_fallible(alg_f)
+    }
+    fn val_count(&self) -> usize { self.z.val_count() }
+}
+
 impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for ReadZipperOwned<V, A> {
     fn make_map(&self) -> Option<PathMap<Self::V, A>> { self.z.make_map() }
 }
@@ -1261,7 +1340,6 @@ impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperMoving for ReadZipperOw
     fn reset(&mut self) { self.z.reset() }
     #[inline]
     fn path(&self) -> &[u8] { self.z.path() }
-    fn val_count(&self) -> usize { self.z.val_count() }
     fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) { self.z.descend_to(k) }
     fn descend_to_check<K: AsRef<[u8]>>(&mut self, k: K) -> bool { self.z.descend_to_check(k) }
     fn descend_to_existing<K: AsRef<[u8]>>(&mut self, k: K) -> usize { self.z.descend_to_existing(k) }
@@ -1349,6 +1427,7 @@ pub(crate) const EXPECTED_PATH_LEN: usize = 64;
 pub(crate) mod read_zipper_core {
     use crate::trie_node::*;
     use crate::PathMap;
+    use crate::morphisms::Catamorphism;
     use crate::zipper::*;
 
     /// A [Zipper] that is unable to modify the trie
@@ -1547,6 +1626,29 @@ pub(crate) mod read_zipper_core {
         }
     }
 
+    impl<V: Clone + Send + Sync + Unpin, A: Allocator> TrieSummary<V> for ReadZipperCore<'_, '_, V, A> {
+        fn summarize_fallible<W, E, AlgF>(&self, alg_f: AlgF) -> Result<W, E>
+            where
+                W: Clone,
+                AlgF: Fn(&ByteMask, &mut TakableSlice<W>, Option<&V>, &[u8]) -> Result<W, E>
+        {
+            self.fork_read_zipper().into_cata_jumping_cached_fallible(alg_f)
+        }
+        fn val_count(&self) -> usize {
+            let root_val = self.is_val() as usize;
+            if self.node_key().len() == 0 {
+                val_count_below_root(*self.focus_node) + root_val
+            } else {
+                let focus = self.get_focus();
+                if focus.is_none() {
+                    root_val
+                } else {
+                    val_count_below_root(focus.as_tagged()) + root_val
+                }
+            }
+        }
+    }
+
     impl<V: Clone + Send + Sync + Unpin, A: Allocator> ZipperSubtries<V, A> for ReadZipperCore<'_, '_, V, A> {
         fn make_map(&self) -> Option<PathMap<Self::V, A>> {
             #[cfg(not(feature = "graft_root_vals"))]
@@ -1589,19 +1691,6 @@ pub(crate) mod read_zipper_core {
             }
         }
 
-        fn val_count(&self) -> usize {
-            let root_val = self.is_val() as usize;
-            if self.node_key().len() == 0 {
-                val_count_below_root(*self.focus_node) + root_val
-            } else {
-                let focus = self.get_focus();
-                if focus.is_none() {
-                    root_val
-                } else {
-                    val_count_below_root(focus.as_tagged()) + root_val
-                }
-            }
-        }
         fn descend_to<K: AsRef<[u8]>>(&mut self, k: K) {
             let k = k.as_ref();
             if k.len() == 0 {
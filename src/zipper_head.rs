@@ -1,5 +1,6 @@
 
 use core::cell::UnsafeCell;
+use std::time::Duration;
 
 use crate::alloc::{Allocator, GlobalAlloc};
 use crate::PathMap;
@@ -30,6 +31,27 @@ pub trait ZipperCreation<'trie, V: Clone + Send + Sync, A: Allocator = GlobalAll
     /// The returned type is [ReadZipperTracked] although the tracking logic will be skipped in release mode.
     unsafe fn read_zipper_at_borrowed_path_unchecked<'a, 'path>(&'a self, path: &'path[u8]) -> ReadZipperTracked<'a, 'path, V, A> where 'trie: 'a;
 
+    /// Creates a new read-only [Zipper] with the path specified from the `ZipperHead`, blocking the calling
+    /// thread until every conflicting zipper has been dropped
+    ///
+    /// Waiting requests are granted in the order they were made, so a reader will not jump ahead of a
+    /// writer that is already waiting for an overlapping path.  This method will deadlock if the calling
+    /// thread itself holds a conflicting zipper.
+    fn read_zipper_at_path_blocking<'a, K: AsRef<[u8]>>(&'a self, path: K) -> ReadZipperTracked<'a, 'static, V, A> where 'trie: 'a;
+
+    /// Behaves like [read_zipper_at_path_blocking](ZipperCreation::read_zipper_at_path_blocking), but gives
+    /// up and returns the [Conflict] if the zipper couldn't be created within `timeout`
+    fn read_zipper_at_path_timeout<'a, K: AsRef<[u8]>>(&'a self, path: K, timeout: Duration) -> Result<ReadZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a;
+
+    /// Behaves like [read_zipper_at_path_timeout](ZipperCreation::read_zipper_at_path_timeout), or like
+    /// [read_zipper_at_path_blocking](ZipperCreation::read_zipper_at_path_blocking) if `timeout` is `None`,
+    /// and attaches `label` to the request
+    ///
+    /// While the request is waiting, the label identifies it in the [Conflict]s reported to the requests
+    /// it holds up.  The returned zipper keeps the label, as if [ReadZipperTracked::set_tracker_label] had
+    /// been called.
+    fn read_zipper_at_path_queued<'a, K: AsRef<[u8]>, L: Into<String>>(&'a self, path: K, label: L, timeout: Option<Duration>) -> Result<ReadZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a;
+
     //GOAT-TrackedOwnedZippers: This is a proposed feature to create owned variants of zippers, but still
     // track them using the ZipperHead infrastructure.  Creating owned zippers safely is easy to do and
     // doesn't require a new API.  However, those zippers will operate outside the management of a tracker,
@@ -56,6 +78,27 @@ pub trait ZipperCreation<'trie, V: Clone + Send + Sync, A: Allocator = GlobalAll
     /// break the rules, it's just an optimization that affects when to spend time enforcing them.
     unsafe fn write_zipper_at_exclusive_path_unchecked<'a, K: AsRef<[u8]>>(&'a self, path: K) -> WriteZipperTracked<'a, 'static, V, A> where 'trie: 'a;
 
+    /// Creates a new [write zippers](ZipperWriting) with the specified path from the `ZipperHead`, blocking
+    /// the calling thread until every conflicting zipper has been dropped
+    ///
+    /// Waiting requests are granted in the order they were made, so a waiting writer can't be starved by
+    /// a stream of new readers.  This method will deadlock if the calling thread itself holds a conflicting
+    /// zipper.
+    fn write_zipper_at_exclusive_path_blocking<'a, K: AsRef<[u8]>>(&'a self, path: K) -> WriteZipperTracked<'a, 'static, V, A> where 'trie: 'a;
+
+    /// Behaves like [write_zipper_at_exclusive_path_blocking](ZipperCreation::write_zipper_at_exclusive_path_blocking),
+    /// but gives up and returns the [Conflict] if the zipper couldn't be created within `timeout`
+    fn write_zipper_at_exclusive_path_timeout<'a, K: AsRef<[u8]>>(&'a self, path: K, timeout: Duration) -> Result<WriteZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a;
+
+    /// Behaves like [write_zipper_at_exclusive_path_timeout](ZipperCreation::write_zipper_at_exclusive_path_timeout),
+    /// or like [write_zipper_at_exclusive_path_blocking](ZipperCreation::write_zipper_at_exclusive_path_blocking)
+    /// if `timeout` is `None`, and attaches `label` to the request
+    ///
+    /// While the request is waiting, the label identifies it in the [Conflict]s reported to the requests
+    /// it holds up.  The returned zipper keeps the label, as if [WriteZipperTracked::set_tracker_label] had
+    /// been called.
+    fn write_zipper_at_exclusive_path_queued<'a, K: AsRef<[u8]>, L: Into<String>>(&'a self, path: K, label: L, timeout: Option<Duration>) -> Result<WriteZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a;
+
     //GOAT-TrackedOwnedZippers
     // /// Creates a [WriteZipperOwned] from the specified path by temporarily cutting the trie
     // ///
@@ -74,6 +117,17 @@ pub trait ZipperCreation<'trie, V: Clone + Send + Sync, A: Allocator = GlobalAll
     // /// May panic if `zipper` did not originate from the `self` `ZipperHead`.
     // fn replace_owned_write_zipper(&self, zipper: WriteZipperOwned<V>);
 
+    /// Attempts to exchange a read zipper that was provided by the `ZipperHead` for a write zipper with the
+    /// same root and focus
+    ///
+    /// The upgrade is atomic, in that the zipper's path is never released, so no other zipper can claim it
+    /// in between.  If any other zipper overlaps the path, including another read zipper, the original
+    /// zipper is returned along with the [Conflict].
+    ///
+    /// May panic if `z` did not originate from the `self` `ZipperHead`.
+    #[allow(clippy::result_large_err)] //The zipper is handed back so the caller doesn't lose its place
+    fn try_upgrade_read_zipper<'a, 'path>(&'a self, z: ReadZipperTracked<'a, 'path, V, A>) -> Result<WriteZipperTracked<'a, 'static, V, A>, (ReadZipperTracked<'a, 'path, V, A>, Conflict)> where 'trie: 'a;
+
     /// Reclaims ownership of a write zipper that was provided by the `ZipperHead` to ensure the zipper's
     /// root prefix path is pruned
     ///
@@ -82,6 +136,15 @@ pub trait ZipperCreation<'trie, V: Clone + Send + Sync, A: Allocator = GlobalAll
     ///
     /// May panic if `zipper` did not originate from the `self` `ZipperHead`.
     fn cleanup_write_zipper<Z: ZipperWriting<V, A> + ZipperAbsolutePath>(&self, z: Z);
+
+    /// Returns a description of every outstanding zipper created from the `ZipperHead`, in the order
+    /// they were created
+    ///
+    /// This is useful to find out which zipper is responsible for a [Conflict].  Use
+    /// [ReadZipperTracked::set_tracker_label] or [WriteZipperTracked::set_tracker_label] to make the
+    /// zippers easier to identify.  Zippers created by `_unchecked` methods are not included in release
+    /// builds.
+    fn outstanding_zippers(&self) -> Vec<TrackerInfo>;
 }
 
 trait ZipperCreationPriv<'trie, V, A: Allocator> {
@@ -230,16 +293,22 @@ impl<'trie, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie> Zip
     fn read_zipper_at_path<'a, K: AsRef<[u8]>>(&'a self, path: K) -> Result<ReadZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
         let path = path.as_ref();
         let zipper_tracker = ZipperTracker::<TrackingRead>::new(self.tracker_paths().clone(), path)?;
-        self.with_inner_core_z(|z| {
-            let (root_node, root_val) = z.splitting_borrow_focus();
-
-            //SAFETY: See identical code in `read_zipper_at_borrowed_path` for more discussion
-            let root_node: &'trie TrieNodeODRc<V, A> = unsafe{ core::mem::transmute(root_node) };
-            let root_val: Option<&'trie V> = root_val.map(|v| unsafe{ &*v.as_ptr() } );
-
-            let new_zipper = ReadZipperTracked::new_with_node_and_cloned_path_in(root_node, true, path.as_ref(), path.len(), 0, root_val, z.alloc.clone(), Some(zipper_tracker));
-            Ok(new_zipper)
-        })
+        Ok(tracked_read_zipper_at_path(self, path, zipper_tracker))
+    }
+    fn read_zipper_at_path_blocking<'a, K: AsRef<[u8]>>(&'a self, path: K) -> ReadZipperTracked<'a, 'static, V, A> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingRead>::new_blocking(self.tracker_paths().clone(), path);
+        tracked_read_zipper_at_path(self, path, zipper_tracker)
+    }
+    fn read_zipper_at_path_timeout<'a, K: AsRef<[u8]>>(&'a self, path: K, timeout: Duration) -> Result<ReadZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingRead>::new_timeout(self.tracker_paths().clone(), path, timeout)?;
+        Ok(tracked_read_zipper_at_path(self, path, zipper_tracker))
+    }
+    fn read_zipper_at_path_queued<'a, K: AsRef<[u8]>, L: Into<String>>(&'a self, path: K, label: L, timeout: Option<Duration>) -> Result<ReadZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingRead>::new_queued(self.tracker_paths().clone(), path, Some(label.into()), timeout)?;
+        Ok(tracked_read_zipper_at_path(self, path, zipper_tracker))
     }
     unsafe fn read_zipper_at_path_unchecked<'a, K: AsRef<[u8]>>(&'a self, path: K) -> ReadZipperTracked<'a, 'static, V, A> where 'trie: 'a {
         let path = path.as_ref();
@@ -264,14 +333,22 @@ impl<'trie, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie> Zip
     fn write_zipper_at_exclusive_path<'a, K: AsRef<[u8]>>(&'a self, path: K) -> Result<WriteZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
         let path = path.as_ref();
         let zipper_tracker = ZipperTracker::<TrackingWrite>::new(self.tracker_paths().clone(), path)?;
-        self.with_inner_core_z(|z| {
-            let (zipper_root_node, zipper_root_val) = prepare_exclusive_write_path(z, path);
-            //SAFETY: See similar code in `read_zipper_at_borrowed_path` for more discussion
-            let zipper_root_node: &'trie mut TrieNodeODRc<V, A> = unsafe{ &mut *(zipper_root_node as *mut _) };
-            let zipper_root_val: &'trie mut Option<V> = unsafe{ &mut *(zipper_root_val as *mut _) };
-
-            Ok(WriteZipperTracked::new_with_node_and_cloned_path_internal_in(zipper_root_node, Some(zipper_root_val), path, path.len(), z.alloc.clone(), Some(zipper_tracker)))
-        })
+        Ok(tracked_write_zipper_at_path(self, path, zipper_tracker))
+    }
+    fn write_zipper_at_exclusive_path_blocking<'a, K: AsRef<[u8]>>(&'a self, path: K) -> WriteZipperTracked<'a, 'static, V, A> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingWrite>::new_blocking(self.tracker_paths().clone(), path);
+        tracked_write_zipper_at_path(self, path, zipper_tracker)
+    }
+    fn write_zipper_at_exclusive_path_timeout<'a, K: AsRef<[u8]>>(&'a self, path: K, timeout: Duration) -> Result<WriteZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingWrite>::new_timeout(self.tracker_paths().clone(), path, timeout)?;
+        Ok(tracked_write_zipper_at_path(self, path, zipper_tracker))
+    }
+    fn write_zipper_at_exclusive_path_queued<'a, K: AsRef<[u8]>, L: Into<String>>(&'a self, path: K, label: L, timeout: Option<Duration>) -> Result<WriteZipperTracked<'a, 'static, V, A>, Conflict> where 'trie: 'a {
+        let path = path.as_ref();
+        let zipper_tracker = ZipperTracker::<TrackingWrite>::new_queued(self.tracker_paths().clone(), path, Some(label.into()), timeout)?;
+        Ok(tracked_write_zipper_at_path(self, path, zipper_tracker))
     }
     unsafe fn write_zipper_at_exclusive_path_unchecked<'a, K: AsRef<[u8]>>(&'a self, path: K) -> WriteZipperTracked<'a, 'static, V, A> where 'trie: 'a {
         let path = path.as_ref();
@@ -292,6 +369,21 @@ impl<'trie, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie> Zip
             WriteZipperTracked::new_with_node_and_cloned_path_internal_in(zipper_root_node, Some(zipper_root_val), path, path.len(), z.alloc.clone(), tracker)
         })
     }
+    fn try_upgrade_read_zipper<'a, 'path>(&'a self, mut z: ReadZipperTracked<'a, 'path, V, A>) -> Result<WriteZipperTracked<'a, 'static, V, A>, (ReadZipperTracked<'a, 'path, V, A>, Conflict)> where 'trie: 'a {
+        let zipper_tracker = match z.try_upgrade_tracker(self.tracker_paths()) {
+            Ok(tracker) => tracker,
+            Err(conflict) => return Err((z, conflict))
+        };
+        let path = z.root_prefix_path().to_vec();
+        let focus_path = z.path().to_vec();
+        drop(z);
+        let mut new_zipper = tracked_write_zipper_at_path(self, &path, zipper_tracker);
+        new_zipper.descend_to(focus_path);
+        Ok(new_zipper)
+    }
+    fn outstanding_zippers(&self) -> Vec<TrackerInfo> {
+        self.tracker_paths().outstanding_zippers()
+    }
     fn cleanup_write_zipper<ChildZ: ZipperWriting<V, A> + ZipperAbsolutePath>(&self, mut z: ChildZ) {
         let origin_path = z.take_root_prefix_path();
         drop(z);
@@ -311,6 +403,35 @@ impl<'trie, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie> Zip
     }
 }
 
+/// Internal function to create a [ReadZipperTracked] once the tracker has been acquired
+fn tracked_read_zipper_at_path<'a, 'trie: 'a, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie>(zh: &'a Z, path: &[u8], zipper_tracker: ZipperTracker<TrackingRead>) -> ReadZipperTracked<'a, 'static, V, A>
+    where Z: ZipperCreationPriv<'trie, V, A>
+{
+    zh.with_inner_core_z(|z| {
+        let (root_node, root_val) = z.splitting_borrow_focus();
+
+        //SAFETY: See identical code in `read_zipper_at_borrowed_path` for more discussion
+        let root_node: &'trie TrieNodeODRc<V, A> = unsafe{ core::mem::transmute(root_node) };
+        let root_val: Option<&'trie V> = root_val.map(|v| unsafe{ &*v.as_ptr() } );
+
+        ReadZipperTracked::new_with_node_and_cloned_path_in(root_node, true, path, path.len(), 0, root_val, z.alloc.clone(), Some(zipper_tracker))
+    })
+}
+
+/// Internal function to create a [WriteZipperTracked] once the tracker has been acquired
+fn tracked_write_zipper_at_path<'a, 'trie: 'a, Z, V: 'trie + Clone + Send + Sync + Unpin, A: Allocator + 'trie>(zh: &'a Z, path: &[u8], zipper_tracker: ZipperTracker<TrackingWrite>) -> WriteZipperTracked<'a, 'static, V, A>
+    where Z: ZipperCreationPriv<'trie, V, A>
+{
+    zh.with_inner_core_z(|z| {
+        let (zipper_root_node, zipper_root_val) = prepare_exclusive_write_path(z, path);
+        //SAFETY: See similar code in `read_zipper_at_borrowed_path` for more discussion
+        let zipper_root_node: &'trie mut TrieNodeODRc<V, A> = unsafe{ &mut *(zipper_root_node as *mut _) };
+        let zipper_root_val: &'trie mut Option<V> = unsafe{ &mut *(zipper_root_val as *mut _) };
+
+        WriteZipperTracked::new_with_node_and_cloned_path_internal_in(zipper_root_node, Some(zipper_root_val), path, path.len(), z.alloc.clone(), Some(zipper_tracker))
+    })
+}
+
 /// Ensures that the node at the specified path exists, and is a [CellByteNode]
 ///
 /// Discussion: This function is fairly complicated because we are only able to safely access the top
@@ -454,6 +575,7 @@ mod tests {
     use crate::zipper::*;
     use crate::tests::prefix_key;
     use std::{thread, thread::ScopedJoinHandle};
+    use std::time::Duration;
 
     #[test]
     fn parallel_insert_test() {
@@ -1450,4 +1572,228 @@ mod tests {
 
         drop(zh);
     }
+    /// Tests [ZipperCreation::read_zipper_at_path_blocking] waits for a conflicting writer on another thread
+    #[test]
+    fn blocking_zipper_test1() {
+        let mut map = PathMap::<isize>::new();
+        let zh = map.zipper_head();
+
+        thread::scope(|scope| {
+            let mut wz = zh.write_zipper_at_exclusive_path(b"shared").unwrap();
+            assert!(zh.read_zipper_at_path(b"shared").is_err());
+            scope.spawn(move || {
+                thread::sleep(Duration::from_millis(20));
+                wz.descend_to(b":val");
+                wz.set_val(42);
+            });
+
+            //This will wait until the thread has finished with the WriteZipper
+            let mut rz = zh.read_zipper_at_path_blocking(b"shared");
+            rz.descend_to(b":val");
+            assert_eq!(rz.val(), Some(&42));
+        });
+        drop(zh);
+        assert_eq!(map.get_val_at(b"shared:val"), Some(&42));
+    }
+
+    /// Tests the `_timeout` flavors of zipper creation
+    #[test]
+    fn blocking_zipper_test2() {
+        let mut map = PathMap::<isize>::new();
+        let zh = map.zipper_head();
+
+        let rz = zh.read_zipper_at_path(b"path").unwrap();
+        assert!(zh.write_zipper_at_exclusive_path_timeout(b"path:below", Duration::from_millis(5)).is_err());
+        assert!(zh.read_zipper_at_path_timeout(b"path", Duration::from_millis(5)).is_ok());
+        drop(rz);
+
+        let mut wz = zh.write_zipper_at_exclusive_path_timeout(b"path:below", Duration::from_millis(5)).unwrap();
+        wz.set_val(1);
+        assert!(zh.read_zipper_at_path_timeout(b"path", Duration::from_millis(5)).is_err());
+        assert!(zh.read_zipper_at_path_timeout(b"other", Duration::from_millis(5)).is_ok());
+        drop(wz);
+
+        //A timed-out request must not leave anything behind in the queue
+        let rz = zh.read_zipper_at_path_timeout(b"path:below", Duration::from_millis(5)).unwrap();
+        assert_eq!(rz.val(), Some(&1));
+    }
+
+    /// Ensures a waiting writer isn't starved by new readers arriving after it
+    #[test]
+    fn blocking_zipper_test3() {
+        let map = PathMap::<isize>::new();
+        let zh = map.into_zipper_head([]);
+
+        let rz = zh.read_zipper_at_path(b"path").unwrap();
+        thread::scope(|scope| {
+            let zh = &zh;
+            let writer_thread = scope.spawn(move || {
+                let mut wz = zh.write_zipper_at_exclusive_path_blocking(b"path");
+                wz.set_val(1);
+            });
+
+            //Once the writer is queued, new readers must be turned away, even though they don't conflict
+            // with any existing zipper
+            while zh.read_zipper_at_path(b"path").is_ok() {
+                thread::yield_now();
+            }
+            assert!(zh.read_zipper_at_path(b"path:below").is_err());
+            assert!(zh.read_zipper_at_path(b"other").is_ok());
+
+            drop(rz);
+            writer_thread.join().unwrap();
+        });
+        let map = zh.into_map();
+        assert_eq!(map.get_val_at(b"path"), Some(&1));
+    }
+
+    /// Ensures a request turned away by a queued request gets a [Conflict] that identifies the queued request
+    #[test]
+    fn blocking_zipper_test4() {
+        use crate::zipper_tracking::TrackerKind;
+
+        let map = PathMap::<isize>::new();
+        let zh = map.into_zipper_head([]);
+
+        let rz = zh.read_zipper_at_path(b"path").unwrap();
+        thread::scope(|scope| {
+            let zh = &zh;
+            let writer_thread = scope.spawn(move || {
+                let wz = zh.write_zipper_at_exclusive_path_queued(b"path:below", "ingest", None).unwrap();
+                assert_eq!(zh.outstanding_zippers()[0].label(), Some("ingest"));
+                drop(wz);
+            });
+
+            let conflict = loop {
+                match zh.read_zipper_at_path(b"path:below") {
+                    Ok(_) => thread::yield_now(),
+                    Err(conflict) => break conflict
+                }
+            };
+            assert_eq!(conflict.kind(), TrackerKind::Queued);
+            assert!(conflict.is_write());
+            assert_eq!(conflict.path(), b"path:below");
+            assert_eq!(conflict.label(), Some("ingest"));
+            assert_eq!(format!("{conflict}"), "conflicts with a queued request for a write zipper labeled \"ingest\" at path \"path:below\"");
+
+            //The queued request doesn't hold the path yet
+            assert_eq!(zh.outstanding_zippers().len(), 1);
+
+            drop(rz);
+            writer_thread.join().unwrap();
+        });
+    }
+
+    /// Tests upgrading a [ReadZipperTracked] and downgrading the resulting [WriteZipperTracked]
+    #[test]
+    fn upgrade_zipper_test1() {
+        let mut map = PathMap::<isize>::new();
+        map.set_val_at(b"path:one", 1);
+        let zh = map.zipper_head();
+
+        //The upgrade must fail while another reader overlaps the path, and give us back the original zipper
+        let mut rz = zh.read_zipper_at_path(b"path").unwrap();
+        rz.descend_to(b":one");
+        let other_rz = zh.read_zipper_at_path(b"path:one").unwrap();
+        let Err((rz, _conflict)) = rz.try_upgrade(&zh) else { panic!() };
+        assert_eq!(rz.path(), b":one");
+        drop(other_rz);
+
+        //A clone of the zipper is another reader too
+        let cloned_rz = rz.clone();
+        let Err((rz, _conflict)) = zh.try_upgrade_read_zipper(rz) else { panic!() };
+        drop(cloned_rz);
+
+        //Now the upgrade should succeed, and preserve the focus
+        let Ok(mut wz) = rz.try_upgrade(&zh) else { panic!() };
+        assert_eq!(wz.path(), b":one");
+        assert_eq!(wz.val(), Some(&1));
+        assert!(zh.read_zipper_at_path(b"path").is_err());
+        wz.set_val(2);
+
+        //Downgrade again, and make sure other readers are permitted, but writers aren't
+        let rz = wz.into_read_zipper();
+        assert_eq!(rz.path(), b":one");
+        assert_eq!(rz.val(), Some(&2));
+        assert!(zh.read_zipper_at_path(b"path").is_ok());
+        assert!(zh.write_zipper_at_exclusive_path(b"path:one").is_err());
+        drop(rz);
+
+        assert!(zh.write_zipper_at_exclusive_path(b"path").is_ok());
+        drop(zh);
+        assert_eq!(map.get_val_at(b"path:one"), Some(&2));
+    }
+
+    /// Tests [ZipperCreation::outstanding_zippers], and the labels reported in a [Conflict]
+    #[test]
+    fn outstanding_zippers_test1() {
+        use crate::zipper_tracking::TrackerKind;
+
+        let mut map = PathMap::<isize>::new();
+        let zh = map.zipper_head();
+        assert!(zh.outstanding_zippers().is_empty());
+
+        let wz = zh.write_zipper_at_exclusive_path(b"writer").unwrap();
+        wz.set_tracker_label("ingest");
+        let rz = zh.read_zipper_at_path(b"reader").unwrap();
+        let cloned_rz = rz.clone();
+
+        let outstanding = zh.outstanding_zippers();
+        assert_eq!(outstanding.len(), 3);
+        assert_eq!(outstanding[0].path(), b"writer");
+        assert_eq!(outstanding[0].kind(), TrackerKind::Write);
+        assert_eq!(outstanding[0].label(), Some("ingest"));
+        assert_eq!(outstanding[1].path(), b"reader");
+        assert_eq!(outstanding[1].kind(), TrackerKind::Read);
+        assert_eq!(outstanding[1].label(), None);
+        assert_eq!(outstanding[2].path(), b"reader");
+
+        let Err(conflict) = zh.read_zipper_at_path(b"writer:below") else { panic!() };
+        assert_eq!(conflict.path(), b"writer");
+        assert_eq!(conflict.kind(), TrackerKind::Write);
+        assert_eq!(conflict.label(), Some("ingest"));
+        assert_eq!(format!("{conflict}"), "conflicts with an outstanding write zipper labeled \"ingest\" at path \"writer\"");
+
+        cloned_rz.set_tracker_label("query");
+        let Err(conflict) = zh.write_zipper_at_exclusive_path(b"reader") else { panic!() };
+        assert_eq!(conflict.kind(), TrackerKind::Read);
+        assert_eq!(conflict.label(), Some("query"));
+
+        //Converting the zipper keeps its label
+        let rz_from_wz = wz.into_read_zipper();
+        let outstanding = zh.outstanding_zippers();
+        assert_eq!(outstanding[0].kind(), TrackerKind::Read);
+        assert_eq!(outstanding[0].label(), Some("ingest"));
+
+        drop(rz);
+        drop(cloned_rz);
+        drop(rz_from_wz);
+        assert!(zh.outstanding_zippers().is_empty());
+    }
+
+    /// Ensures a [Conflict] carries the label of the zipper that actually refused the request
+    #[test]
+    fn outstanding_zippers_test2() {
+        let mut map = PathMap::<isize>::new();
+        let zh = map.zipper_head();
+
+        //The upgrade is refused by the other reader, not by the zipper being upgraded
+        let rz = zh.read_zipper_at_path(b"path").unwrap();
+        rz.set_tracker_label("upgrading");
+        let other_rz = zh.read_zipper_at_path(b"path").unwrap();
+        let Err((rz, conflict)) = rz.try_upgrade(&zh) else { panic!() };
+        assert_eq!(conflict.label(), None);
+
+        other_rz.set_tracker_label("other");
+        let Err((rz, conflict)) = rz.try_upgrade(&zh) else { panic!() };
+        assert_eq!(conflict.label(), Some("other"));
+        assert_eq!(format!("{conflict}"), "conflicts with 1 outstanding read zipper(s) labeled \"other\" at path \"path\"");
+
+        //The refused upgrade leaves the zipper registered as a reader
+        let Err(conflict) = zh.write_zipper_at_exclusive_path(b"path:below") else { panic!() };
+        assert_eq!(format!("{conflict}"), "conflicts with 2 outstanding read zipper(s) labeled \"upgrading\" at path \"path\"");
+        drop(other_rz);
+        drop(rz);
+        assert!(zh.write_zipper_at_exclusive_path(b"path").is_ok());
+    }
 }
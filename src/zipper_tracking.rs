@@ -1,11 +1,10 @@
+use std::collections::{BTreeMap, VecDeque};
 use std::marker::PhantomData;
-use std::num::NonZero;
-use std::num::NonZeroU32;
-use std::sync::Arc;
-use std::sync::RwLock;
+use std::sync::{Arc, Condvar, Mutex};
+use std::time::{Duration, Instant};
 
 use crate::PathMap;
-use crate::zipper::{ReadZipperUntracked, Zipper, ZipperAbsolutePath, ZipperForking, ZipperMoving, ZipperReadOnlyValues, ZipperWriting, ZipperIteration, ZipperReadOnlyIteration, };
+use crate::zipper::{ReadZipperUntracked, Zipper, ZipperAbsolutePath, ZipperForking, ZipperMoving, ZipperReadOnlyValues, ZipperWriting, ZipperReadOnlyIteration, };
 
 /// Marker to track an outstanding read zipper
 pub struct TrackingRead;
@@ -48,16 +47,19 @@ use tracking_internal::TrackingMode;
 pub struct ZipperTracker<M: TrackingMode> {
     all_paths: SharedTrackerPaths,
     this_path: Vec<u8>,
+    /// Identifies this tracker's [TrackerRecord] in the registry
+    id: u64,
     _is_tracking: PhantomData<M>,
 }
 
 impl Clone for ZipperTracker<TrackingRead> {
     fn clone(&self) -> Self {
-        self.all_paths
-            .add_reader_unchecked(self.this_path.as_slice());
+        let id = self.all_paths
+            .with_paths(|paths| paths.add_cloned_reader(self.this_path.as_slice(), self.id));
         Self {
             all_paths: self.all_paths.clone(),
             this_path: self.this_path.clone(),
+            id,
             _is_tracking: PhantomData,
         }
     }
@@ -71,43 +73,150 @@ impl<M: TrackingMode> ZipperTracker<M> {
     }
 }
 
+impl<M: TrackingMode> ZipperTracker<M> {
+    /// Create a new `ZipperTracker`, waiting in the queue until all conflicting trackers have been dropped,
+    /// or until `timeout` has elapsed
+    ///
+    /// While the request is queued, `label` identifies it in the [Conflict]s reported to the requests it
+    /// blocks, and once the path is acquired it becomes the label of the returned tracker.  Without a
+    /// `timeout`, this method will deadlock if the calling thread holds the conflicting tracker.
+    pub fn new_queued(shared_paths: SharedTrackerPaths, path: &[u8], label: Option<String>, timeout: Option<Duration>) -> Result<Self, Conflict> {
+        let deadline = timeout.map(|timeout| Instant::now() + timeout);
+        let id = shared_paths.add_waiting(path, M::tracks_writes(), label, deadline)?;
+        Ok(Self {
+            all_paths: shared_paths,
+            this_path: path.to_vec(),
+            id,
+            _is_tracking: PhantomData,
+        })
+    }
+
+    /// Attaches a label to the tracker, to identify it in [Conflict]s and in the list returned by
+    /// [SharedTrackerPaths::outstanding_zippers]
+    pub fn set_label<L: Into<String>>(&self, label: L) {
+        let label = label.into();
+        self.all_paths.with_paths(|paths| {
+            paths.records.get_mut(&self.id).unwrap().label = Some(label);
+        })
+    }
+}
+
+/// The kind of zipper guarded by a tracker
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TrackerKind {
+    /// A read-only zipper, which may share its path with other read-only zippers
+    Read,
+    /// A write zipper, which has exclusive access to its path
+    Write,
+    /// A request for a zipper that is queued behind other zippers, and doesn't hold its path yet
+    ///
+    /// Queued requests are never listed by [SharedTrackerPaths::outstanding_zippers], but new requests that
+    /// would overlap a queued write request, or a queued read request in the case of a new write request, are
+    /// refused until the queued request has been served.
+    Queued,
+}
+
+impl TrackerKind {
+    fn from_is_write(is_write: bool) -> Self {
+        if is_write { Self::Write } else { Self::Read }
+    }
+}
+
+/// Describes an outstanding zipper, returned by [SharedTrackerPaths::outstanding_zippers]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackerInfo {
+    path: Vec<u8>,
+    kind: TrackerKind,
+    label: Option<String>,
+}
+
+impl TrackerInfo {
+    /// Returns the path guarded by the tracker, relative to the [ZipperHead](crate::zipper::ZipperHead)
+    pub fn path(&self) -> &[u8] {
+        &self.path
+    }
+    /// Returns the kind of zipper that holds the path
+    pub fn kind(&self) -> TrackerKind {
+        self.kind
+    }
+    /// Returns the label that was attached to the tracker, if there is one
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+}
+
 /// An error type that may be produced when attempting to create a zipper or otherwise acquire
 /// permission to access a path
 #[derive(Debug)]
 pub struct Conflict {
     with: IsTracking,
     at: Vec<u8>,
+    label: Option<String>,
 }
 
 impl std::fmt::Display for Conflict {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let _ = write!(f, "conflicts with existing zipper: ");
-        let _ = match self.with {
-            IsTracking::WriteZipper => write!(f, "write zipper"),
-            IsTracking::ReadZipper(cnt) => write!(f, "read zipper (arity: {cnt:?})"),
+        match self.with {
+            IsTracking::WriteZipper => write!(f, "conflicts with an outstanding write zipper")?,
+            IsTracking::ReadZipper(cnt) => write!(f, "conflicts with {cnt} outstanding read zipper(s)")?,
+            IsTracking::QueuedWrite => write!(f, "conflicts with a queued request for a write zipper")?,
+            IsTracking::QueuedRead => write!(f, "conflicts with a queued request for a read zipper")?,
         };
-        let path = &self.at;
-        writeln!(f, " @ {path:?}")
+        if let Some(label) = &self.label {
+            write!(f, " labeled {label:?}")?;
+        }
+        write!(f, " at path \"{}\"", self.at.escape_ascii())
     }
 }
 
 impl std::error::Error for Conflict {}
 
 impl Conflict {
-    fn write_conflict(path: &[u8]) -> Conflict {
+    fn write_conflict(path: &[u8], label: Option<String>) -> Conflict {
         Conflict {
             with: IsTracking::WriteZipper,
             at: path.to_vec(),
+            label,
         }
     }
 
-    fn read_conflict(cnt: NonZeroU32, path: &[u8]) -> Conflict {
+    fn read_conflict(cnt: usize, path: &[u8], label: Option<String>) -> Conflict {
         Conflict {
             with: IsTracking::ReadZipper(cnt),
             at: path.to_vec(),
+            label,
+        }
+    }
+
+    fn queued_conflict(waiter: &TrackerWaiter) -> Conflict {
+        Conflict {
+            with: if waiter.is_write { IsTracking::QueuedWrite } else { IsTracking::QueuedRead },
+            at: waiter.path.clone(),
+            label: waiter.label.clone(),
+        }
+    }
+
+    /// Returns the kind of zipper that holds the conflicting path
+    pub fn kind(&self) -> TrackerKind {
+        match self.with {
+            IsTracking::WriteZipper => TrackerKind::Write,
+            IsTracking::ReadZipper(_) => TrackerKind::Read,
+            IsTracking::QueuedWrite | IsTracking::QueuedRead => TrackerKind::Queued,
         }
     }
 
+    /// Returns `true` if the conflicting request is for a write zipper, whether it's outstanding or queued
+    pub fn is_write(&self) -> bool {
+        matches!(self.with, IsTracking::WriteZipper | IsTracking::QueuedWrite)
+    }
+
+    /// Returns the label of the conflicting tracker or queued request, if it was given one
+    ///
+    /// If several read zippers share the conflicting path, this is the label of the oldest labeled one.
+    pub fn label(&self) -> Option<&str> {
+        self.label.as_deref()
+    }
+
     fn check_for_lock_along_path<'a, A: Clone + Send + Sync + Unpin>(
         path: &[u8],
         zipper: &'a mut ReadZipperUntracked<A>,
@@ -128,7 +237,9 @@ impl Conflict {
         }
     }
 
-    fn check_for_write_conflict<C, ConflictF: FnOnce(&[u8])->C>(path: &[u8], all_paths: &PathMap<()>, conflict_f: ConflictF) -> Result<(), C> {
+    /// Checks `path` against the writers in `all_paths`, and calls `conflict_f` with the id of the
+    /// conflicting writer's [TrackerRecord] and its path
+    fn check_for_write_conflict<C, ConflictF: FnOnce(u64, &[u8])->C>(path: &[u8], all_paths: &PathMap<u64>, conflict_f: ConflictF) -> Result<(), C> {
         let mut zipper = all_paths.read_zipper();
         match Conflict::check_for_lock_along_path(path, &mut zipper) {
             None =>
@@ -137,21 +248,23 @@ impl Conflict {
             {
                 if zipper.path().len() == path.len() {
                     let mut subtree = zipper.fork_read_zipper();
-                    match subtree.to_next_val() {
-                        false => Ok(()),
-                        true => Err(conflict_f(subtree.origin_path())),
+                    match subtree.to_next_get_val() {
+                        None => Ok(()),
+                        Some(id) => Err(conflict_f(*id, subtree.origin_path())),
                     }
                 } else {
                     Ok(())
                 }
             }
-            Some(_) => Err(conflict_f(zipper.path())),
+            Some(id) => Err(conflict_f(*id, zipper.path())),
         }
     }
 
-    fn check_for_read_conflict<C, ConflictF: FnOnce(NonZeroU32, &[u8])->C>(
+    /// Checks `path` against the readers in `all_paths`, and calls `conflict_f` with the ids of the
+    /// [TrackerRecord]s of all readers that share the first conflicting path, and that path
+    fn check_for_read_conflict<C, ConflictF: FnOnce(&[u64], &[u8])->C>(
         path: &[u8],
-        all_paths: &PathMap<NonZeroU32>,
+        all_paths: &PathMap<Vec<u64>>,
         conflict_f: ConflictF
     ) -> Result<(), C> {
         let mut zipper = all_paths.read_zipper();
@@ -161,8 +274,8 @@ impl Conflict {
                     let mut subtree = zipper.fork_read_zipper();
                     match subtree.to_next_get_val() {
                         None => Ok(()),
-                        Some(lock) => Err(conflict_f(
-                            *lock,
+                        Some(ids) => Err(conflict_f(
+                            ids,
                             subtree.origin_path(),
                         )),
                     }
@@ -170,10 +283,14 @@ impl Conflict {
                     Ok(())
                 }
             }
-            Some(lock) => Err(conflict_f(*lock, zipper.path())),
+            Some(ids) => {
+                let ids = ids.clone();
+                Err(conflict_f(&ids, zipper.path()))
+            },
         }
     }
 
+    /// Returns the path held by the conflicting tracker
     pub fn path(&self) -> &[u8] {
         &self.at[..]
     }
@@ -183,21 +300,54 @@ impl Conflict {
 ///
 /// Use [SharedTrackerPaths::default] to make a new registry
 //
-// NOTE for the future: We were considering using a lockless queue in place of the `RwLock` to guard
+// NOTE for the future: We were considering using a lockless queue in place of the `Mutex` to guard
 // this object, with the understanding that all new zipper creation would be serialized.  The idea is
 // that the zipper `Drop` implementations would enquque paths, and then the queue would be drained prior
 // to creating any new zippers.  So a queue with lockless enqueue means there is no critical section
-// even if the zippers move to different threads.  This is still a valid approach.  If, however,
-// `SharedTrackerPaths` is changed to be `!Sync` by replacing the `RwLock`, we should move the
-// `SharedTrackerPaths` inside the `Mutex` inside [ZipperHeadOwned](crate::zipper::ZipperHeadOwned) so
-// `ZipperHeadOwned` remains `Sync`
+// even if the zippers move to different threads.  This is still a valid approach, although the blocking
+// acquisition methods would need another way to be woken up.  If `SharedTrackerPaths` is changed to be
+// `!Sync` by replacing the `Mutex`, we should move the `SharedTrackerPaths` inside the `Mutex` inside
+// [ZipperHeadOwned](crate::zipper::ZipperHeadOwned) so `ZipperHeadOwned` remains `Sync`
 #[derive(Clone, Default)]
-pub struct SharedTrackerPaths(Arc<RwLock<TrackerPaths>>);
+pub struct SharedTrackerPaths(Arc<TrackerShared>);
+
+#[derive(Default)]
+struct TrackerShared {
+    paths: Mutex<TrackerPaths>,
+    /// Signaled whenever a lock is released or a waiter leaves the queue
+    released: Condvar,
+}
 
 #[derive(Clone, Default)]
 struct TrackerPaths {
-    read_paths: PathMap<NonZeroU32>,
-    written_paths: PathMap<()>,
+    /// The ids of the [TrackerRecord]s of the readers at each path, oldest first
+    read_paths: PathMap<Vec<u64>>,
+    /// The id of the [TrackerRecord] of the writer at each path
+    written_paths: PathMap<u64>,
+    /// Every live tracker, in the order they were created
+    records: BTreeMap<u64, TrackerRecord>,
+    next_id: u64,
+    /// Threads blocked waiting to acquire a path, in the order they arrived
+    waiters: VecDeque<TrackerWaiter>,
+    next_ticket: u64,
+}
+
+/// Information about one live [ZipperTracker], kept for introspection
+#[derive(Clone)]
+struct TrackerRecord {
+    path: Vec<u8>,
+    is_write: bool,
+    label: Option<String>,
+}
+
+/// A request for a path that is queued, waiting for conflicting zippers to be dropped
+#[derive(Clone)]
+struct TrackerWaiter {
+    ticket: u64,
+    path: Vec<u8>,
+    is_write: bool,
+    /// Identifies the request in [Conflict]s, and becomes the label of the tracker once the path is acquired
+    label: Option<String>,
 }
 
 /// Represents the status of a specific path, returned by [SharedTrackerPaths::path_status]
@@ -211,12 +361,163 @@ pub enum PathStatus {
     Unavailable,
 }
 
+impl TrackerPaths {
+    /// Checks a request against the waiters that are queued ahead of it.  Pass `None` for `ticket` if
+    /// the request isn't in the queue, in which case every waiter is considered to be ahead of it
+    ///
+    /// This is what makes acquisition fair; A stream of readers won't be able to starve a queued writer
+    /// because they will be refused for as long as the writer is waiting.
+    fn check_for_queued_conflict(&self, path: &[u8], is_write: bool, ticket: Option<u64>) -> Result<(), Conflict> {
+        for waiter in self.waiters.iter() {
+            if ticket.map(|ticket| waiter.ticket >= ticket).unwrap_or(false) {
+                break;
+            }
+            if (is_write || waiter.is_write) && (waiter.path.starts_with(path) || path.starts_with(&waiter.path)) {
+                return Err(Conflict::queued_conflict(waiter))
+            }
+        }
+        Ok(())
+    }
+
+    /// Attempts to add a writer at `path`, and returns the id of its [TrackerRecord]
+    fn try_add_writer(&mut self, path: &[u8], ticket: Option<u64>) -> Result<u64, Conflict> {
+        self.check_for_writer_conflict(path, ticket)?;
+        let id = self.add_record(path, true, None);
+        self.written_paths.set_val_at(path, id);
+        Ok(id)
+    }
+
+    fn check_for_writer_conflict(&self, path: &[u8], ticket: Option<u64>) -> Result<(), Conflict> {
+        Conflict::check_for_write_conflict(path, &self.written_paths, |id, at| self.write_conflict(id, at))?;
+        Conflict::check_for_read_conflict(path, &self.read_paths, |ids, at| self.read_conflict(ids, at))?;
+        self.check_for_queued_conflict(path, true, ticket)
+    }
+
+    /// Attempts to add a reader at `path`, and returns the id of its [TrackerRecord]
+    fn try_add_reader(&mut self, path: &[u8], ticket: Option<u64>) -> Result<u64, Conflict> {
+        Conflict::check_for_write_conflict(path, &self.written_paths, |id, at| self.write_conflict(id, at))?;
+        self.check_for_queued_conflict(path, false, ticket)?;
+        let id = self.add_record(path, false, None);
+        self.add_reader_unchecked(path, id);
+        Ok(id)
+    }
+
+    fn add_record(&mut self, path: &[u8], is_write: bool, label: Option<String>) -> u64 {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.records.insert(id, TrackerRecord { path: path.to_vec(), is_write, label });
+        id
+    }
+
+    fn record_label(&self, id: u64) -> Option<String> {
+        self.records.get(&id).and_then(|record| record.label.clone())
+    }
+
+    /// Makes a [Conflict] with the writer identified by `id`
+    fn write_conflict(&self, id: u64, path: &[u8]) -> Conflict {
+        Conflict::write_conflict(path, self.record_label(id))
+    }
+
+    /// Makes a [Conflict] with the readers identified by `ids`, which all hold the same path
+    fn read_conflict(&self, ids: &[u64], path: &[u8]) -> Conflict {
+        Conflict::read_conflict(ids.len(), path, ids.iter().find_map(|id| self.record_label(*id)))
+    }
+
+    /// Adds another reader at `path` with the same label as the tracker identified by `id`
+    fn add_cloned_reader(&mut self, path: &[u8], id: u64) -> u64 {
+        let new_id = self.add_record(path, false, self.record_label(id));
+        self.add_reader_unchecked(path, new_id);
+        new_id
+    }
+
+    /// Adds the reader identified by `id` without checking to see whether it conflicts with existing writers
+    fn add_reader_unchecked(&mut self, path: &[u8], id: u64) {
+        let mut writer = self.read_paths.write_zipper_at_path(path);
+        match writer.get_val_mut() {
+            Some(ids) => {
+                //Keep the ids sorted, so the oldest reader comes first
+                let idx = ids.partition_point(|other_id| *other_id < id);
+                ids.insert(idx, id);
+            },
+            None => {
+                writer.set_val(vec![id]);
+            }
+        }
+    }
+
+    /// Removes the reader identified by `id` from the path.  Returns `false` if there was no such reader
+    fn remove_reader(&mut self, path: &[u8], id: u64) -> bool {
+        let mut write_zipper = self.read_paths.write_zipper_at_path(path);
+        match write_zipper.get_val_mut() {
+            Some(ids) => {
+                let Some(idx) = ids.iter().position(|other_id| *other_id == id) else {
+                    return false
+                };
+                ids.remove(idx);
+                if ids.is_empty() {
+                    write_zipper.remove_val(true);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Removes the writer from the path.  Returns `false` if there was no writer to remove
+    fn remove_writer(&mut self, path: &[u8]) -> bool {
+        self.written_paths.write_zipper_at_path(path).remove_val(true).is_some()
+    }
+
+    /// Exchanges the reader identified by `id` for a writer, provided no other zipper would conflict
+    /// with the writer
+    ///
+    /// The registry is left unchanged if the upgrade fails.
+    fn try_upgrade_reader(&mut self, path: &[u8], id: u64) -> Result<(), Conflict> {
+        if !self.remove_reader(path, id) {
+            panic!("Lock is missing.\nContents {path:#?}");
+        }
+        match self.check_for_writer_conflict(path, None) {
+            Ok(()) => {
+                self.written_paths.set_val_at(path, id);
+                self.records.get_mut(&id).unwrap().is_write = true;
+                Ok(())
+            },
+            Err(conflict) => {
+                self.add_reader_unchecked(path, id);
+                Err(conflict)
+            }
+        }
+    }
+
+    /// Exchanges the writer identified by `id` for a reader
+    fn downgrade_writer(&mut self, path: &[u8], id: u64) {
+        self.add_reader_unchecked(path, id);
+        if !self.remove_writer(path) {
+            panic!("Lock is missing.\nContents {path:#?}");
+        }
+        self.records.get_mut(&id).unwrap().is_write = false;
+    }
+
+    fn enqueue_waiter(&mut self, path: &[u8], is_write: bool, label: Option<String>) -> u64 {
+        let ticket = self.next_ticket;
+        self.next_ticket += 1;
+        self.waiters.push_back(TrackerWaiter { ticket, path: path.to_vec(), is_write, label });
+        ticket
+    }
+
+    /// Removes the waiter from the queue, and returns its label
+    fn dequeue_waiter(&mut self, ticket: u64) -> Option<String> {
+        let idx = self.waiters.iter().position(|waiter| waiter.ticket == ticket).unwrap();
+        self.waiters.remove(idx).unwrap().label
+    }
+}
+
 impl SharedTrackerPaths {
     fn with_paths<F, R>(&self, f: F) -> R
     where
         F: FnOnce(&mut TrackerPaths) -> R,
     {
-        let mut guard = self.0.write().unwrap();
+        let mut guard = self.0.paths.lock().unwrap();
         let r = f(&mut guard);
         drop(guard);
         r
@@ -231,176 +532,238 @@ impl SharedTrackerPaths {
     pub fn path_status<P: AsRef<[u8]>>(&self, path: P) -> PathStatus {
         let path = path.as_ref();
         self.with_paths(|all_paths: &mut TrackerPaths| {
-            match Conflict::check_for_write_conflict(path, &all_paths.written_paths, |_| ()) {
+            match Conflict::check_for_write_conflict(path, &all_paths.written_paths, |_, _| ()) {
                 Ok(()) => {},
                 Err(()) => return PathStatus::Unavailable
             }
+            if all_paths.check_for_queued_conflict(path, false, None).is_err() {
+                return PathStatus::Unavailable
+            }
             match Conflict::check_for_read_conflict(path, &all_paths.read_paths, |_, _| ()) {
                 Ok(()) => {},
                 Err(()) => return PathStatus::AvailableForReading
             }
+            if all_paths.check_for_queued_conflict(path, true, None).is_err() {
+                return PathStatus::AvailableForReading
+            }
             PathStatus::Available
         })
     }
 
-    fn try_add_writer(&self, path: &[u8]) -> Result<(), Conflict> {
-        let try_add_writer_internal = |all_paths: &mut TrackerPaths| {
-            Conflict::check_for_write_conflict(path, &all_paths.written_paths, Conflict::write_conflict)?;
-            Conflict::check_for_read_conflict(path, &all_paths.read_paths, Conflict::read_conflict)?;
-            let mut writer = all_paths.written_paths.write_zipper_at_path(path);
-            writer.set_val(());
-            Ok(())
-        };
-
-        self.with_paths(try_add_writer_internal)
+    /// Returns a description of every outstanding zipper tracked by the registry, in the order they were
+    /// created
+    ///
+    /// As with [path_status](SharedTrackerPaths::path_status), the returned list is just a snapshot, and
+    /// zippers may have been created or dropped by the time the caller inspects it.
+    pub fn outstanding_zippers(&self) -> Vec<TrackerInfo> {
+        self.with_paths(|all_paths| {
+            all_paths.records.values().map(|record| TrackerInfo {
+                path: record.path.clone(),
+                kind: TrackerKind::from_is_write(record.is_write),
+                label: record.label.clone(),
+            }).collect()
+        })
     }
 
-    fn try_add_reader(&self, path: &[u8]) -> Result<(), Conflict> {
-        let try_add_reader_internal = |all_paths: &mut TrackerPaths| {
-            Conflict::check_for_write_conflict(path, &all_paths.written_paths, Conflict::write_conflict)?;
-            let mut writer = all_paths.read_paths.write_zipper_at_path(path);
-            let value = writer.get_val_mut();
-            match value {
-                Some(cnt) => match cnt.checked_add(1) {
-                    Some(new_cnt) => {
-                        *cnt = new_cnt;
-                        Ok(())
-                    }
-                    None => Err(Conflict::read_conflict(NonZero::<u32>::MAX, path)),
-                },
-                None => {
-                    writer.set_val(NonZero::<u32>::MIN);
-                    Ok(())
-                }
-            }
-        };
+    fn try_add_writer(&self, path: &[u8]) -> Result<u64, Conflict> {
+        self.with_paths(|all_paths| all_paths.try_add_writer(path, None))
+    }
 
-        self.with_paths(try_add_reader_internal)
+    fn try_add_reader(&self, path: &[u8]) -> Result<u64, Conflict> {
+        self.with_paths(|all_paths| all_paths.try_add_reader(path, None))
     }
 
-    /// Adds a new reader without checking to see whether it conflicts with existing writers
-    fn add_reader_unchecked(&self, path: &[u8]) {
-        let add_reader = |paths: &mut TrackerPaths| {
-            let mut writer = paths.read_paths.write_zipper_at_path(path);
-            match writer.get_val_mut() {
-                Some(cnt) => {
-                    *cnt = unsafe { NonZero::new_unchecked(cnt.get() + 1) };
-                },
-                None => {
-                    writer.set_val(unsafe { NonZero::new_unchecked(1) });
+    /// Waits in the queue until the requested lock can be taken, or until the `deadline` passes
+    ///
+    /// Returns the most recent `Conflict` if the deadline passed before the lock could be acquired.
+    fn add_waiting(&self, path: &[u8], is_write: bool, label: Option<String>, deadline: Option<Instant>) -> Result<u64, Conflict> {
+        let mut guard = self.0.paths.lock().unwrap();
+        let ticket = guard.enqueue_waiter(path, is_write, label);
+        let result = loop {
+            let conflict = match if is_write {
+                guard.try_add_writer(path, Some(ticket))
+            } else {
+                guard.try_add_reader(path, Some(ticket))
+            } {
+                Ok(id) => break Ok(id),
+                Err(conflict) => conflict
+            };
+            guard = match deadline {
+                None => self.0.released.wait(guard).unwrap(),
+                Some(deadline) => {
+                    let now = Instant::now();
+                    if now >= deadline {
+                        break Err(conflict)
+                    }
+                    self.0.released.wait_timeout(guard, deadline - now).unwrap().0
                 }
-            }
+            };
         };
-
-        self.with_paths(add_reader)
+        let label = guard.dequeue_waiter(ticket);
+        if let Ok(id) = result {
+            guard.records.get_mut(&id).unwrap().label = label;
+        }
+        drop(guard);
+        //Requests queued behind this one may have been waiting on it
+        self.0.released.notify_all();
+        result
     }
 }
 
 #[derive(Clone, Debug, PartialEq)]
 enum IsTracking {
     WriteZipper,
-    ReadZipper(NonZeroU32),
+    ReadZipper(usize),
+    QueuedWrite,
+    QueuedRead,
 }
 
 impl<M: TrackingMode> core::fmt::Debug for ZipperTracker<M> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let all_paths = self.all_paths.0.read().unwrap();
+        let all_paths = self.all_paths.0.paths.lock().unwrap();
         let _ = writeln!(
             f,
             "ZipperTracker {{ type = {:?}, path = {:?}",
             M::as_str(),
             self.this_path
         );
-        let _ = writeln!(f, "\tRead Zippers:");
-        for (rz, cnt) in all_paths.read_paths.iter() {
-            let _ = writeln!(f, "\t\t{rz:?} ({cnt:?})");
-        }
-        let _ = writeln!(f, "\tWrite Zippers:");
-        for (wz, _) in all_paths.written_paths.iter() {
-            let _ = writeln!(f, "\t\t{wz:?}");
+        for (title, is_write) in [("Read Zippers", false), ("Write Zippers", true)] {
+            let _ = writeln!(f, "\t{title}:");
+            for record in all_paths.records.values().filter(|record| record.is_write == is_write) {
+                let _ = match &record.label {
+                    Some(label) => writeln!(f, "\t\t{:?} ({label:?})", record.path),
+                    None => writeln!(f, "\t\t{:?}", record.path),
+                };
+            }
         }
         write!(f, "}}")
     }
 }
 
 impl<M: TrackingMode> ZipperTracker<M> {
+    /// Returns `true` if the tracker is registered in `shared_paths`
+    pub(crate) fn is_tracked_by(&self, shared_paths: &SharedTrackerPaths) -> bool {
+        Arc::ptr_eq(&self.all_paths.0, &shared_paths.0)
+    }
+
     /// Destroy a `ZipperTracker` without invoking Drop code to release the lock
-    fn dismantle(self) -> (SharedTrackerPaths, Vec<u8>) {
+    fn dismantle(self) -> (SharedTrackerPaths, Vec<u8>, u64) {
         let tracker_shell = core::mem::ManuallyDrop::new(self);
         let all_paths = unsafe { core::ptr::read(&tracker_shell.all_paths) };
         let this_path = unsafe { core::ptr::read(&tracker_shell.this_path) };
-        (all_paths, this_path)
+        (all_paths, this_path, tracker_shell.id)
     }
 }
 
 impl ZipperTracker<TrackingRead> {
     /// Create a new `ZipperTracker` to track a read zipper
     pub fn new(shared_paths: SharedTrackerPaths, path: &[u8]) -> Result<Self, Conflict> {
-        shared_paths.try_add_reader(path)?;
+        let id = shared_paths.try_add_reader(path)?;
         Ok(Self {
             all_paths: shared_paths,
             this_path: path.to_vec(),
+            id,
             _is_tracking: PhantomData,
         })
     }
+
+    /// Create a new `ZipperTracker` to track a read zipper, blocking the calling thread until all
+    /// conflicting trackers have been dropped
+    ///
+    /// Requests are served in the order they arrive, so a reader will not jump ahead of a writer that
+    /// is already waiting for an overlapping path.  This method will deadlock if the calling thread
+    /// holds the conflicting tracker.
+    pub fn new_blocking(shared_paths: SharedTrackerPaths, path: &[u8]) -> Self {
+        //Without a deadline, the only way to leave the queue is by acquiring the lock
+        Self::new_queued(shared_paths, path, None, None).unwrap()
+    }
+
+    /// Create a new `ZipperTracker` to track a read zipper, waiting at most `timeout` for conflicting
+    /// trackers to be dropped.  See [new_blocking](Self::new_blocking)
+    pub fn new_timeout(shared_paths: SharedTrackerPaths, path: &[u8], timeout: Duration) -> Result<Self, Conflict> {
+        Self::new_queued(shared_paths, path, None, Some(timeout))
+    }
+
+    /// Attempts to consume the reader tracker, and return a writer tracker with the same path
+    ///
+    /// The path is never released during the upgrade, so no other zipper can claim it in between.  If
+    /// any other tracker overlaps the path, including another reader, the original tracker is returned
+    /// along with the [Conflict].
+    pub fn try_upgrade(self) -> Result<ZipperTracker<TrackingWrite>, (Self, Conflict)> {
+        match self.all_paths.with_paths(|paths| paths.try_upgrade_reader(&self.this_path, self.id)) {
+            Ok(()) => {
+                let (all_paths, this_path, id) = self.dismantle();
+                Ok(ZipperTracker::<TrackingWrite> {
+                    all_paths, this_path, id, _is_tracking: PhantomData
+                })
+            },
+            Err(conflict) => Err((self, conflict))
+        }
+    }
 }
 
 impl ZipperTracker<TrackingWrite> {
     /// Create a new `ZipperTracker` to track a write zipper
     pub fn new(shared_paths: SharedTrackerPaths, path: &[u8]) -> Result<Self, Conflict> {
-        shared_paths.try_add_writer(path)?;
+        let id = shared_paths.try_add_writer(path)?;
         Ok(Self {
             all_paths: shared_paths,
             this_path: path.to_vec(),
+            id,
             _is_tracking: PhantomData,
         })
     }
+
+    /// Create a new `ZipperTracker` to track a write zipper, blocking the calling thread until all
+    /// conflicting trackers have been dropped
+    ///
+    /// Requests are served in the order they arrive, so a waiting writer can't be starved by a stream
+    /// of new readers.  This method will deadlock if the calling thread holds the conflicting tracker.
+    pub fn new_blocking(shared_paths: SharedTrackerPaths, path: &[u8]) -> Self {
+        //Without a deadline, the only way to leave the queue is by acquiring the lock
+        Self::new_queued(shared_paths, path, None, None).unwrap()
+    }
+
+    /// Create a new `ZipperTracker` to track a write zipper, waiting at most `timeout` for conflicting
+    /// trackers to be dropped.  See [new_blocking](Self::new_blocking)
+    pub fn new_timeout(shared_paths: SharedTrackerPaths, path: &[u8], timeout: Duration) -> Result<Self, Conflict> {
+        Self::new_queued(shared_paths, path, None, Some(timeout))
+    }
+
     /// Consumes the writer tracker, and returns a new reader tracker with the same path
     pub fn into_reader(self) -> ZipperTracker<TrackingRead> {
-        let (all_paths, this_path) = self.dismantle();
-        //We add the reader lock first before removing the writer, so there is no chance another thread will
-        // grab a writer in between.
-        all_paths.add_reader_unchecked(&this_path);
-        Self::remove_lock(&all_paths, &this_path);
+        let (all_paths, this_path, id) = self.dismantle();
+        //The reader lock replaces the writer in the same critical section, so there is no chance another
+        // thread will grab a writer in between.
+        all_paths.with_paths(|paths| paths.downgrade_writer(&this_path, id));
+        //Readers may have been waiting for the writer to go away
+        all_paths.0.released.notify_all();
         ZipperTracker::<TrackingRead> {
-            all_paths, this_path, _is_tracking: PhantomData
+            all_paths, this_path, id, _is_tracking: PhantomData
         }
     }
 }
 
 impl<M: TrackingMode> ZipperTracker<M> {
     /// Internal method to remove a lock, called after it has been confirmed to be the correct thing to do
-    fn remove_lock(all_paths: &SharedTrackerPaths, this_path: &[u8]) {
+    fn remove_lock(all_paths: &SharedTrackerPaths, this_path: &[u8], id: u64) {
         let is_removed = all_paths.with_paths(|paths| {
+            paths.records.remove(&id);
             if M::tracks_reads() {
-                let mut write_zipper = paths.read_paths.write_zipper_at_path(this_path);
-                match write_zipper.get_val_mut() {
-                    Some(cnt) => {
-                        if *cnt == NonZero::<u32>::MIN {
-                            write_zipper.remove_val(true);
-                        } else {
-                            *cnt = unsafe { NonZero::new_unchecked(cnt.get() - 1) };
-                        };
-                        true
-                    }
-                    None => false,
-                }
+                paths.remove_reader(this_path, id)
             } else {
-                let removed = paths
-                    .written_paths
-                    .write_zipper_at_path(this_path)
-                    .remove_val(true);
-                removed.is_some()
+                paths.remove_writer(this_path)
             }
         });
         if !is_removed {
             panic!("Lock is missing.\nContents {this_path:#?}");
         }
+        all_paths.0.released.notify_all();
     }
 }
 
 impl<M: TrackingMode> Drop for ZipperTracker<M> {
     fn drop(&mut self) {
-        Self::remove_lock(&self.all_paths, &self.this_path);
+        Self::remove_lock(&self.all_paths, &self.this_path, self.id);
     }
 }